@@ -5,6 +5,8 @@ fn main() {
         .input_extern_file("src/model/skid_image.rs")
         .input_extern_file("src/model/skid_vector2.rs")
         .input_extern_file("src/model/skid_vector3.rs")
+        .input_extern_file("src/model/skid_matrix3.rs")
+        .input_extern_file("src/model/skid_atlas.rs")
         .input_extern_file("src/model/ffi_modules/skid_image_ffi.rs")
         .input_extern_file("src/model/ffi_modules/mod.rs")
         .input_extern_file("src/api/ffi_modules/mod.rs")
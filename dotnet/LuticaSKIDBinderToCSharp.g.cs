@@ -17,12 +17,15 @@ namespace LuticaSKIDBinder
 
 
 
+        /// <summary>
+        ///  빈 이미지를 만들어 소유권을 호출 측에 넘깁니다.
+        ///
+        ///  # Safety
+        ///  반환된 이미지는 이 라이브러리의 할당자로 만든 것이므로 다른 할당자로 해제하면 안 됩니다.
+        /// </summary>
         [DllImport(__DllName, EntryPoint = "skid_image_new", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern SKIDImage* skid_image_new(nuint width, nuint height);
 
-        [DllImport(__DllName, EntryPoint = "skid_image_get_size", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern SKIDSizeVector2 skid_image_get_size(SKIDImage* handle);
-
         [DllImport(__DllName, EntryPoint = "skid_color_new", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern SKIDColor skid_color_new(float r, float g, float b, float a);
 
@@ -71,6 +74,55 @@ namespace LuticaSKIDBinder
         [DllImport(__DllName, EntryPoint = "skid_vector3_neg", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern SKIDVector3 skid_vector3_neg(SKIDVector3 v);
 
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_identity", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern SKIDMatrix3 skid_matrix3_identity();
+
+        /// <summary>
+        ///  행 우선 9개 원소로 행렬을 만든다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_from_rows", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern SKIDMatrix3 skid_matrix3_from_rows(float* values);
+
+        /// <summary>
+        ///  행 우선 9개 원소를 `out_values`에 쓴다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_to_rows", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void skid_matrix3_to_rows(SKIDMatrix3 m, float* out_values);
+
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_mul", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern SKIDMatrix3 skid_matrix3_mul(SKIDMatrix3 m1, SKIDMatrix3 m2);
+
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_mul_vector3", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern SKIDVector3 skid_matrix3_mul_vector3(SKIDMatrix3 m, SKIDVector3 v);
+
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_determinant", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern float skid_matrix3_determinant(SKIDMatrix3 m);
+
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_transpose", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern SKIDMatrix3 skid_matrix3_transpose(SKIDMatrix3 m);
+
+        /// <summary>
+        ///  역행렬을 `out`에 쓴다. 특이 행렬이면 false 를 반환하고 `out`은 건드리지 않는다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_inverse", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        [return: MarshalAs(UnmanagedType.U1)]
+        internal static extern bool skid_matrix3_inverse(SKIDMatrix3 m, SKIDMatrix3* @out);
+
+        /// <summary>
+        ///  2D 점을 원근 변환한다. w 가 0 이면 false.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_transform_point", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        [return: MarshalAs(UnmanagedType.U1)]
+        internal static extern bool skid_matrix3_transform_point(SKIDMatrix3 m, SKIDVector2 p, SKIDVector2* @out);
+
+        /// <summary>
+        ///  원본 네 꼭짓점(`src`)을 대상 네 꼭짓점(`dst`)으로 보내는 호모그래피를 `out`에 쓴다.
+        ///  사각형이 퇴화(세 점이 한 직선 위)했으면 false.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_matrix3_homography_from_corners", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        [return: MarshalAs(UnmanagedType.U1)]
+        internal static extern bool skid_matrix3_homography_from_corners(SKIDVector2* src, SKIDVector2* dst, SKIDMatrix3* @out);
+
         [DllImport(__DllName, EntryPoint = "skid_generate_normal_map", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern SKIDImage skid_generate_normal_map(SKIDImage input_image, NormalMapOptions options);
 
@@ -80,8 +132,40 @@ namespace LuticaSKIDBinder
         [DllImport(__DllName, EntryPoint = "skid_image_create_from_f32_array", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ulong skid_image_create_from_f32_array(float* bytes, nuint width, nuint height);
 
+        /// <summary>
+        ///  네이티브 픽셀 형식의 바이트 배열로 SKIDImage 를 생성하고 핸들을 반환합니다.
+        ///  f32 로 넓히지 않고 `format` 그대로 저장합니다. (format: SKIDPixelFormat 코드)
+        ///  행 사이 여백이 없는 RGBA 순서 버퍼용이며, 그 외에는 `skid_image_create_from_u8_buffer`를 사용하세요.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_create_from_bytes", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_create_from_bytes(byte* bytes, nuint bytes_len, nuint width, nuint height, uint format);
+
+        /// <summary>
+        ///  u8 버퍼(8비트 형식 또는 임의 형식의 원시 바이트)로 SKIDImage 를 생성하고 핸들을 반환합니다.
+        ///
+        ///  - row_stride   : 한 행의 바이트 수 (0 이면 여백 없음)
+        ///  - format       : SKIDPixelFormat 코드
+        ///  - channel_order: 0 RGBA, 1 BGRA, 2 ARGB (4채널 형식에만 의미가 있음)
+        ///
+        ///  버퍼 크기나 인자가 맞지 않으면 0을 반환합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_create_from_u8_buffer", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_create_from_u8_buffer(byte* bytes, nuint bytes_len, nuint width, nuint height, nuint row_stride, uint format, uint channel_order);
+
+        /// <summary>
+        ///  u16 버퍼(R16, RGBA16, RGBA16F)로 SKIDImage 를 생성하고 핸들을 반환합니다.
+        ///
+        ///  - data_len     : 버퍼의 u16 요소 개수
+        ///  - row_stride   : 한 행의 **바이트** 수 (0 이면 여백 없음)
+        ///  - format       : SKIDPixelFormat 코드 (채널당 2바이트 형식만 허용)
+        ///  - channel_order: 0 RGBA, 1 BGRA, 2 ARGB
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_create_from_u16_buffer", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_create_from_u16_buffer(ushort* data, nuint data_len, nuint width, nuint height, nuint row_stride, uint format, uint channel_order);
+
         /// <summary>
         ///  핸들을 사용하여 이미지의 메모리를 해제합니다. (매우 중요!)
+        ///  잠금(`skid_image_lock`) 중인 핸들은 마지막 `skid_image_unlock` 시점까지 해제가 미뤄집니다.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "skid_image_free", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void skid_image_free(ulong handle);
@@ -98,6 +182,68 @@ namespace LuticaSKIDBinder
         [DllImport(__DllName, EntryPoint = "skid_image_get_data_as_f32_array", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern int skid_image_get_data_as_f32_array(ulong handle, float* out_bytes, nuint buffer_len);
 
+        /// <summary>
+        ///  핸들을 사용하여 이미지의 픽셀 형식 코드를 가져옵니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_get_pixel_format", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_get_pixel_format(ulong handle, uint* out_format);
+
+        /// <summary>
+        ///  핸들을 사용하여 이미지 데이터를 네이티브 픽셀 형식의 바이트 배열로 복사합니다.
+        ///  필요한 크기는 width * height * bytes_per_pixel 입니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_get_data_as_bytes", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_get_data_as_bytes(ulong handle, byte* out_bytes, nuint buffer_len);
+
+        /// <summary>
+        ///  이미지를 `format` 형식으로 디더링 양자화한 바이트를 복사합니다. (원본 이미지는 바뀌지 않음)
+        ///  필요한 크기는 width * height * bytes_per_pixel(format) 입니다.
+        ///  (0: 성공, -1: null 포인터, -2: 버퍼 부족, -3: 유효하지 않은 핸들, -4: 잘못된 형식/방식)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_get_data_as_bytes_dithered", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_get_data_as_bytes_dithered(ulong handle, uint format, uint method, byte* out_bytes, nuint buffer_len);
+
+        /// <summary>
+        ///  지정한 픽셀 형식으로 0 으로 채워진 이미지를 만들고 핸들을 반환합니다.
+        ///  `skid_image_lock`으로 받은 포인터에 직접 쓰면 호스트 → SKID 복사가 한 번으로 끝납니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_create_with_format", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_create_with_format(nuint width, nuint height, uint format);
+
+        /// <summary>
+        ///  이미지의 픽셀 메모리를 잠그고 복사 없이 읽고 쓸 수 있는 포인터를 돌려줍니다.
+        ///
+        ///  - 같은 핸들을 여러 번 잠글 수 있으며(같은 포인터), 잠근 횟수만큼 `skid_image_unlock`을 호출해야 합니다.
+        ///  - 잠금 중에는 다른 함수(resize, get_size 등)가 이 핸들을 유효하지 않은 핸들로 보고 실패합니다.
+        ///  - 잠금 중 `skid_image_free`는 마지막 unlock 까지 미뤄집니다.
+        ///
+        ///  반환값: 0 성공, -1 null 포인터, -2 유효하지 않은(또는 해제 예정인) 핸들
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_lock", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_lock(ulong handle, SKIDImageLock* out_lock);
+
+        /// <summary>
+        ///  `skid_image_lock`으로 잠근 이미지를 해제합니다. 마지막 unlock 이후 포인터는 더 이상 사용할 수 없고,
+        ///  핸들은 다시 다른 함수에서 쓸 수 있습니다.
+        ///
+        ///  반환값: 0 성공, -2 잠겨 있지 않은 핸들
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_unlock", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_unlock(ulong handle);
+
+        /// <summary>
+        ///  이미지를 다른 픽셀 형식으로 변환하고 새 핸들을 반환합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_convert_format", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_convert_format(ulong handle, uint format);
+
+        /// <summary>
+        ///  이미지를 다른 픽셀 형식으로 변환하고 새 핸들을 반환합니다.
+        ///  정수 형식으로 줄일 때 method(0 반올림, 1 Bayer, 2 블루 노이즈, 3 Floyd-Steinberg)로 디더링합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_convert_format_dithered", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_convert_format_dithered(ulong handle, uint format, uint method);
+
         /// <summary>
         ///  이미지 리사이즈 함수 (핸들 기반)
         ///
@@ -109,6 +255,422 @@ namespace LuticaSKIDBinder
         [DllImport(__DllName, EntryPoint = "skid_image_resize", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ulong skid_image_resize(ulong handle, nuint new_width, nuint new_height);
 
+        /// <summary>
+        ///  이미지의 색 공간 코드를 가져옵니다. (0: sRGB, 1: Linear, 2: HSV, 3: HSL, 4: YCbCr, 5: Lab)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_get_color_space", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_get_color_space(ulong handle, uint* out_color_space);
+
+        /// <summary>
+        ///  픽셀 값은 그대로 두고 색 공간 태그만 변경합니다. (외부에서 linear 데이터를 넘긴 경우 등)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_set_color_space", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_set_color_space(ulong handle, uint color_space);
+
+        /// <summary>
+        ///  이미지를 다른 색 공간으로 변환하고 새 핸들을 반환합니다. (GPU 커널)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_convert_color_space", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_convert_color_space(ulong handle, uint target);
+
+        /// <summary>
+        ///  이미지의 알파 모드를 가져옵니다. (0: Straight, 1: Premultiplied)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_get_alpha_mode", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_get_alpha_mode(ulong handle, uint* out_alpha_mode);
+
+        /// <summary>
+        ///  픽셀 값은 그대로 두고 알파 모드 태그만 변경합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_set_alpha_mode", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_set_alpha_mode(ulong handle, uint alpha_mode);
+
+        /// <summary>
+        ///  RGB 에 알파를 곱한 프리멀티플라이드 이미지를 새 핸들로 반환합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_premultiply", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_premultiply(ulong handle);
+
+        /// <summary>
+        ///  프리멀티플라이드 이미지를 straight 알파로 되돌려 새 핸들로 반환합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_unpremultiply", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_unpremultiply(ulong handle);
+
+        /// <summary>
+        ///  overlay 를 base 위에 합성하고 새 핸들을 반환합니다.
+        ///  mode: 0 Normal, 1 Add, 2 Subtract, 3 Multiply, 4 Divide
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_blend", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_blend(ulong base_handle, ulong overlay_handle, uint mode, float opacity);
+
+        /// <summary>
+        ///  이미지에 kernel_width x kernel_height 컨볼루션 커널(행 우선 가중치)을 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - edge_mode    : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant
+        ///  - constant     : Constant 모드에서 이미지 밖을 채우는 값
+        ///  - channel_mask : 비트 i 가 켜진 채널만 필터링 (0b1111 = RGBA 전체)
+        ///
+        ///  분리 가능한 커널은 자동으로 두 번의 1D 패스로 실행됩니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_convolve", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_convolve(ulong handle, float* weights, nuint kernel_width, nuint kernel_height, uint edge_mode, SKIDColor constant, uint channel_mask);
+
+        /// <summary>
+        ///  가우시안 블러를 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - mode        : 0 Exact (분리 가능 커널), 1 Approximate (박스 블러 3회, 큰 반경용)
+        ///  - edge_mode   : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant(투명 검정)
+        ///  - channel_mask: 비트 i 가 켜진 채널만 블러 (0b1111 = RGBA 전체)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_gaussian_blur", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_gaussian_blur(ulong handle, float sigma, uint mode, uint edge_mode, uint channel_mask);
+
+        /// <summary>
+        ///  (2 * radius + 1) 크기의 박스 블러를 적용하고 새 핸들을 반환합니다. 반경과 무관하게 일정한 비용이 듭니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_box_blur", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_box_blur(ulong handle, uint radius, uint edge_mode, uint channel_mask);
+
+        /// <summary>
+        ///  가장자리를 보존하는 바이리터럴 필터를 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - spatial_sigma: 거리 가중치의 표준편차 (픽셀)
+        ///  - range_sigma  : 값 차이 가중치의 표준편차 (0~1 값 기준)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_bilateral_blur", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_bilateral_blur(ulong handle, float spatial_sigma, float range_sigma, uint edge_mode, uint channel_mask);
+
+        /// <summary>
+        ///  하이패스 필터(0.5 + (원본 - 블러) * amount)를 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - radius   : 블러 표준편차 (픽셀)
+        ///  - threshold: |원본 - 블러| 가 이보다 작으면 0.5
+        ///  - mode     : 0 채널별, 1 휘도
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_high_pass", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_high_pass(ulong handle, float radius, float amount, float threshold, uint mode);
+
+        /// <summary>
+        ///  언샤프 마스크(원본 + (원본 - 블러) * amount)를 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - radius   : 블러 표준편차 (픽셀)
+        ///  - threshold: |원본 - 블러| 가 이보다 작은 픽셀은 그대로 둠
+        ///  - mode     : 0 채널별, 1 휘도
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_unsharp_mask", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_unsharp_mask(ulong handle, float radius, float amount, float threshold, uint mode);
+
+        /// <summary>
+        ///  높이맵에서 단일 채널 AO 맵을 생성하고 새 핸들을 반환합니다. (1.0 = 가려지지 않음)
+        ///
+        ///  - ray_count / step_count: 방향 수 / 방향당 샘플 수
+        ///  - radius      : 탐색 반경 (픽셀)
+        ///  - height_scale: 높이 1.0 에 해당하는 픽셀 높이
+        ///  - strength    : 차폐량 배율
+        ///  - tileable    : 가장자리를 반대편으로 이어서 샘플링 (타일링 텍스처)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_make_ao_map", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_make_ao_map(ulong handle, uint ray_count, uint step_count, float radius, float height_scale, float strength, [MarshalAs(UnmanagedType.U1)] bool tileable);
+
+        /// <summary>
+        ///  높이맵 또는 노멀맵에서 곡률 맵을 생성하고 새 핸들을 반환합니다.
+        ///
+        ///  - source      : 0 높이맵, 1 노멀맵 (`skid_generate_normal_map` 결과)
+        ///  - output      : 0 부호 있는 1채널 (0.5 = 평면), 1 RGBA 분리 (R 볼록, G 오목)
+        ///  - radius      : 가장 작은 스케일의 샘플 거리 (픽셀), scale_count 개 스케일을 2배씩 늘려 평균
+        ///  - height_scale: 높이 1.0 에 해당하는 픽셀 높이 (높이맵 입력)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_make_curvature_map", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_make_curvature_map(ulong handle, uint source, uint output, float radius, uint scale_count, float height_scale, float strength, [MarshalAs(UnmanagedType.U1)] bool tileable);
+
+        /// <summary>
+        ///  최대 4개 이미지의 채널을 골라 하나의 이미지로 패킹하고 새 핸들을 반환합니다. (ORM/MRA 텍스처 등)
+        ///
+        ///  - handles : 입력 이미지 핸들 배열 (1~4개, 크기가 모두 같아야 함)
+        ///  - mapping : 출력 R, G, B, A 순서의 `SKIDChannelSource` 4개
+        ///  - format  : 결과 픽셀 형식 (`SKIDPixelFormat` 값)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_pack_channels", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_pack_channels(ulong* handles, nuint handle_count, SKIDChannelSource* mapping, uint format);
+
+        /// <summary>
+        ///  스위즐 문자열(UTF-8, 예: "BGRA", "RRR1")에 따라 채널을 재배치하고 새 핸들을 반환합니다.
+        ///  R/G/B/A(X/Y/Z/W)는 입력 채널, 0/1 은 상수입니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_swizzle", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_swizzle(ulong handle, byte* pattern, nuint pattern_len);
+
+        /// <summary>
+        ///  타일링 시 이음매가 보이지 않도록 이미지를 가공하고 새 핸들을 반환합니다.
+        ///
+        ///  - mode        : 0 OffsetBlend (크기 유지), 1 CrossFade (가로/세로가 blend_width 만큼 줄어듦)
+        ///  - blend_width : 섞는 폭 (픽셀)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_make_seamless", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_make_seamless(ulong handle, uint mode, uint blend_width);
+
+        /// <summary>
+        ///  이음매 확인용으로 이미지를 columns x rows 번 반복해 붙인 미리보기 이미지의 새 핸들을 반환합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_tile_preview", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_tile_preview(ulong handle, uint columns, uint rows);
+
+        /// <summary>
+        ///  절차적 노이즈(0~1) 이미지를 GPU 에서 생성하고 핸들을 반환합니다.
+        ///
+        ///  - noise_type     : 0 Value, 1 Perlin, 2 Simplex, 3 Worley
+        ///  - fractal        : 0 None, 1 fBm, 2 Ridged, 3 Turbulence
+        ///  - frequency      : 가로 방향 셀 수 (첫 옥타브)
+        ///  - warp_strength  : 도메인 워핑 세기 (셀 단위, 0 = 워핑 없음), warp_frequency 는 워핑 필드 주파수 배율
+        ///  - tileable       : true 이면 이음매 없이 반복되는 결과
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_generate_noise", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_generate_noise(nuint width, nuint height, uint format, uint noise_type, uint fractal, uint seed, float frequency, uint octaves, float lacunarity, float gain, float warp_strength, float warp_frequency, [MarshalAs(UnmanagedType.U1)] bool tileable);
+
+        /// <summary>
+        ///  테스트 패턴 이미지를 GPU 에서 생성하고 핸들을 반환합니다.
+        ///
+        ///  - pattern   : 0 체커보드, 1 선형 그라디언트, 2 원형 그라디언트, 3 SMPTE 컬러 바, 4 UV 격자, 5 단색, 6 견본
+        ///  - cell_size : 체커보드 칸 / UV 격자 간격 (픽셀)
+        ///  - angle     : 선형 그라디언트 방향 (라디안)
+        ///  - color_a, color_b : 패턴 색 (컬러 바/견본은 사용하지 않음, UV 격자는 color_a 로 선을 그림)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_generate_test_pattern", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_generate_test_pattern(nuint width, nuint height, uint format, uint pattern, uint cell_size, float angle, SKIDColor color_a, SKIDColor color_b);
+
+        /// <summary>
+        ///  채널별 최솟값/최댓값/평균/표준편차를 GPU 에서 계산해 `out_stats`에 씁니다.
+        ///  (0: 성공, -1: null 포인터, -2: 유효하지 않은 핸들)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_compute_statistics", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_compute_statistics(ulong handle, SKIDImageStatistics* out_stats);
+
+        /// <summary>
+        ///  채널별 bins 칸 히스토그램을 GPU 에서 계산해 `out_counts`에 씁니다.
+        ///  결과는 채널 순서로 이어 붙인 channel_count * bins 개이며, [range_min, range_max] 밖의 값은 양 끝 칸에 셉니다.
+        ///  (0: 성공, -1: null 포인터, -2: 버퍼가 작음, -3: 유효하지 않은 핸들, -4: 잘못된 bins/범위)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_compute_histogram", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_compute_histogram(ulong handle, uint bins, float range_min, float range_max, uint* out_counts, nuint buffer_len);
+
+        /// <summary>
+        ///  레벨 조정(입력 검정/흰색 점, 감마, 출력 범위)을 적용하고 새 핸들을 반환합니다.
+        ///  channel_mask 의 비트 i 가 켜진 채널만 바뀝니다 (0b0111 = RGB, 알파 유지).
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_levels", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_levels(ulong handle, float in_black, float in_white, float gamma, float out_black, float out_white, uint channel_mask);
+
+        /// <summary>
+        ///  톤 커브를 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - points       : 모든 커브의 제어점 (x, y) 쌍을 rgb, R, G, B, A 커브 순서로 이어 붙인 배열
+        ///  - point_counts : 커브 5개 각각의 제어점 수 (0 이면 항등 커브)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_curves", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_curves(ulong handle, float* points, uint* point_counts);
+
+        /// <summary>
+        ///  백분위 기반 자동 대비를 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - low_percentile, high_percentile : 검정/흰색으로 자를 누적 비율 (예: 0.005, 0.995)
+        ///  - per_channel : true 이면 채널마다 따로 범위를 정함 (색 캐스트 보정)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_auto_contrast", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_auto_contrast(ulong handle, float low_percentile, float high_percentile, [MarshalAs(UnmanagedType.U1)] bool per_channel);
+
+        /// <summary>
+        ///  3D LUT 를 적용하고 새 핸들을 반환합니다.
+        ///
+        ///  - lut_data      : R 이 가장 빠르게 변하는 순서의 RGB 항목 lut_size³ 개 (float 3 * lut_size³ 개)
+        ///  - interpolation : 0 Trilinear, 1 Tetrahedral
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_apply_lut3d", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_apply_lut3d(ulong handle, float* lut_data, uint lut_size, uint interpolation);
+
+        /// <summary>
+        ///  .cube 파일(경로는 UTF-8)을 읽어 3D LUT 를 적용하고 새 핸들을 반환합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_apply_cube_file", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_apply_cube_file(ulong handle, byte* path, nuint path_len, uint interpolation);
+
+        /// <summary>
+        ///  항등 LUT 스트립 이미지((size*size) x size)를 만들고 핸들을 반환합니다.
+        ///  이 이미지를 외부 툴에서 그레이딩한 뒤 `skid_image_strip_to_cube_file`로 .cube 로 저장할 수 있습니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_create_identity_lut", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_create_identity_lut(uint size, uint format);
+
+        /// <summary>
+        ///  그레이딩된 LUT 스트립 이미지를 .cube 파일로 저장합니다.
+        ///  (0: 성공, -1: null 포인터, -2: 유효하지 않은 핸들, -4: 잘못된 스트립 크기 또는 쓰기 실패)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_strip_to_cube_file", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_strip_to_cube_file(ulong handle, byte* path, nuint path_len);
+
+        /// <summary>
+        ///  HDR 이미지를 0~1 범위의 sRGB 로 톤 매핑하고 새 핸들을 반환합니다.
+        ///
+        ///  - operator    : 0 Reinhard, 1 확장 Reinhard, 2 ACES 근사, 3 AgX, 4 Uncharted2
+        ///  - exposure    : 노출 보정 (EV)
+        ///  - white_point : 확장 Reinhard / Uncharted2 의 흰색 점 (0 이하이면 연산자 기본값)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_tone_map", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_tone_map(ulong handle, uint @operator, float exposure, float white_point);
+
+        /// <summary>
+        ///  이미지에서 최대 color_count 색 팔레트를 만들어 `out_colors`에 씁니다.
+        ///
+        ///  - method : 0 중앙값 분할, 1 Lab k-means
+        ///  - 반환값 : 실제 색 수 (투명 픽셀이 있으면 0번이 투명 항목), 또는 -1 null 포인터, -2 유효하지 않은 핸들,
+        ///    -3 버퍼 부족, -4 잘못된 인자
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_generate_palette", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_generate_palette(ulong handle, uint color_count, uint method, SKIDColor* out_colors, nuint capacity);
+
+        /// <summary>
+        ///  팔레트 파일(.gpl, JASC .pal, .hex — 경로는 UTF-8)을 읽어 `out_colors`에 씁니다.
+        ///  (반환값: 색 수, 또는 -1 null 포인터, -3 버퍼 부족, -4 읽기/파싱 실패)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_palette_load", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_palette_load(byte* path, nuint path_len, SKIDColor* out_colors, nuint capacity);
+
+        /// <summary>
+        ///  이미지를 주어진 팔레트 색으로만 표현한 새 핸들을 반환합니다.
+        ///  dither : 0 없음, 1 Bayer, 2 블루 노이즈, 3 Floyd-Steinberg
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_map_to_palette", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_map_to_palette(ulong handle, SKIDColor* colors, nuint color_count, uint dither);
+
+        /// <summary>
+        ///  이미지를 팔레트로 매핑해 인덱스 컬러 PNG 로 저장합니다. (경로는 UTF-8)
+        ///  (0: 성공, -1: null 포인터, -2: 유효하지 않은 핸들, -4: 잘못된 인자 또는 저장 실패)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_export_indexed_png", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_export_indexed_png(ulong handle, SKIDColor* colors, nuint color_count, uint dither, byte* path, nuint path_len);
+
+        /// <summary>
+        ///  지정한 보간 방식으로 리사이즈하고 새 핸들을 반환합니다. (sampling: 0 최근접, 1 바이리니어, 2 바이큐빅)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_resize_with_sampling", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_resize_with_sampling(ulong handle, nuint new_width, nuint new_height, uint sampling);
+
+        /// <summary>
+        ///  (x, y)에서 width x height 영역을 잘라낸 새 핸들을 반환합니다. 영역이 이미지를 벗어나면 0.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_crop", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_crop(ulong handle, nuint x, nuint y, nuint width, nuint height);
+
+        /// <summary>
+        ///  이미지 둘레에 픽셀을 덧붙인 새 핸들을 반환합니다.
+        ///
+        ///  - edge_mode : 0 Clamp (가장자리 연장), 1 Wrap, 2 Mirror, 3 Constant (`fill`로 채움)
+        ///  - fill      : 이미지와 같은 색 공간/알파 모드의 채움 색
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_pad", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_pad(ulong handle, nuint left, nuint top, nuint right, nuint bottom, uint edge_mode, SKIDColor fill);
+
+        /// <summary>
+        ///  이미지를 뒤집은 새 핸들을 반환합니다. (axis: 0 좌우, 1 상하)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_flip", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_flip(ulong handle, uint axis);
+
+        /// <summary>
+        ///  행과 열을 바꾼 새 핸들을 반환합니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_transpose", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_transpose(ulong handle);
+
+        /// <summary>
+        ///  2x3 아핀 변환(원본 → 출력 좌표, 행 우선 [a, b, tx, c, d, ty])을 적용한 out_width x out_height 새 핸들을 반환합니다.
+        ///
+        ///  - sampling  : 0 최근접, 1 바이리니어, 2 바이큐빅
+        ///  - edge_mode : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant
+        ///  - constant  : Constant 모드에서 원본 밖을 채우는 값 (linear + 프리멀티플라이드)
+        ///
+        ///  역변환이 없는 행렬이면 0.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_affine_warp", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_affine_warp(ulong handle, float* matrix, nuint out_width, nuint out_height, uint sampling, uint edge_mode, SKIDColor constant);
+
+        /// <summary>
+        ///  중심 기준으로 radians 만큼(시계 방향) 회전한 새 핸들을 반환합니다.
+        ///  expand 가 true 이면 회전한 이미지 전체가 들어가도록 크기를 키우며, 밖은 투명 검정으로 채웁니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_rotate", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_rotate(ulong handle, float radians, [MarshalAs(UnmanagedType.U1)] bool expand, uint sampling);
+
+        /// <summary>
+        ///  90도 단위로 시계 방향 turns 번 회전한 새 핸들을 반환합니다. (음수는 반시계 방향)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_rotate_quarter_turns", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_rotate_quarter_turns(ulong handle, int turns);
+
+        /// <summary>
+        ///  호모그래피(원본 → 출력 좌표)로 원근 변환한 out_width x out_height 새 핸들을 반환합니다.
+        ///
+        ///  - sampling  : 0 최근접, 1 바이리니어, 2 바이큐빅
+        ///  - edge_mode : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant (투명 검정)
+        ///
+        ///  역변환이 없는 행렬이면 0.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_perspective_warp", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_perspective_warp(ulong handle, SKIDMatrix3 matrix, nuint out_width, nuint out_height, uint sampling, uint edge_mode);
+
+        /// <summary>
+        ///  비스듬히 찍힌 사각형(corners: 왼쪽 위, 오른쪽 위, 오른쪽 아래, 왼쪽 아래 4개)을 정면 직사각형으로 편 새 핸들을 반환합니다.
+        ///  out_width 나 out_height 가 0 이면 변 길이로 크기를 정합니다. (sampling: 0 최근접, 1 바이리니어, 2 바이큐빅)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_correct_perspective", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_correct_perspective(ulong handle, SKIDVector2* corners, nuint out_width, nuint out_height, uint sampling);
+
+        /// <summary>
+        ///  높이맵(height_handle)의 roi 영역만 노멀맵을 다시 계산해 normal_handle 이미지에 제자리로 씁니다.
+        ///  roi 의 width 나 height 가 0 이면 전체를 다시 계산합니다. 두 이미지는 크기가 같아야 합니다.
+        ///  (0: 성공, -2: 유효하지 않은 핸들, -4: 잘못된 인자)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_make_normal_map_region", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_image_make_normal_map_region(ulong height_handle, ulong normal_handle, SKIDRect roi, float x_factor, float y_factor);
+
+        /// <summary>
+        ///  GPU 메모리에 한 번에 올리지 않고 타일 단위로 노멀맵을 만들어 새 핸들을 반환합니다.
+        ///
+        ///  - tile_size      : 타일 한 변의 픽셀 수 (0 이면 기본값 2048)
+        ///  - parallel_tiles : 동시에 처리할 타일 수 (0 또는 1 이면 순서대로)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_make_normal_map_tiled", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_make_normal_map_tiled(ulong handle, float x_factor, float y_factor, nuint tile_size, nuint parallel_tiles);
+
+        /// <summary>
+        ///  타일 단위로 리사이즈해 새 핸들을 반환합니다. 인자는 `skid_image_resize_with_sampling`,
+        ///  `skid_image_make_normal_map_tiled`와 같습니다.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_resize_tiled", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_resize_tiled(ulong handle, nuint new_width, nuint new_height, uint sampling, nuint tile_size, nuint parallel_tiles);
+
+        /// <summary>
+        ///  여러 이미지를 하나의 텍스처 아틀라스로 패킹하고 아틀라스 핸들을 반환합니다. (실패하면 0)
+        ///
+        ///  - handles        : 입력 이미지 핸들 배열
+        ///  - max_width/max_height : 아틀라스 최대 크기
+        ///  - padding        : 이미지 둘레에 가장자리 픽셀을 늘여 붙일 폭 (번짐 방지)
+        ///  - allow_rotation : 90도 회전 배치 허용
+        ///  - power_of_two   : 아틀라스 가로/세로를 2의 거듭제곱으로
+        ///  - format         : 아틀라스 픽셀 형식 (`SKIDPixelFormat` 값, 4채널)
+        ///  - out_entries    : 입력 순서대로 각 이미지의 위치/UV 를 받을 배열 (handle_count 개 이상)
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_image_build_atlas", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong skid_image_build_atlas(ulong* handles, nuint handle_count, nuint max_width, nuint max_height, nuint padding, [MarshalAs(UnmanagedType.U1)] bool allow_rotation, [MarshalAs(UnmanagedType.U1)] bool power_of_two, uint format, SKIDAtlasEntry* out_entries, nuint entries_capacity);
+
+        /// <summary>
+        ///  `skid_image_build_atlas`의 결과를 JSON 매니페스트(UTF-8)로 만들어 `out_bytes`에 씁니다.
+        ///
+        ///  - 반환값 : 쓴 바이트 수 (NUL 종료 없음), 또는 -1 null 포인터, -3 버퍼 부족
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "skid_atlas_manifest_to_json", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern int skid_atlas_manifest_to_json(nuint atlas_width, nuint atlas_height, nuint padding, SKIDAtlasEntry* entries, nuint entry_count, byte* out_bytes, nuint buffer_len);
+
 
     }
 
@@ -128,11 +690,27 @@ namespace LuticaSKIDBinder
         public nuint height;
     }
 
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct SKIDRect
+    {
+        public nuint x;
+        public nuint y;
+        public nuint width;
+        public nuint height;
+    }
+
     [StructLayout(LayoutKind.Sequential)]
     internal unsafe partial struct SKIDImage
     {
     }
 
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct SKIDVector2
+    {
+        public float x;
+        public float y;
+    }
+
     [StructLayout(LayoutKind.Sequential)]
     internal unsafe partial struct SKIDVector3
     {
@@ -141,6 +719,23 @@ namespace LuticaSKIDBinder
         public float z;
     }
 
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct SKIDMatrix3
+    {
+        public SKIDVector3 x_axis;
+        public SKIDVector3 y_axis;
+        public SKIDVector3 z_axis;
+    }
+
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct SKIDAtlasEntry
+    {
+        public SKIDRect rect;
+        [MarshalAs(UnmanagedType.U1)] public bool rotated;
+        public SKIDVector2 uv_min;
+        public SKIDVector2 uv_max;
+    }
+
     [StructLayout(LayoutKind.Sequential)]
     internal unsafe partial struct CalcDevice
     {
@@ -157,6 +752,17 @@ namespace LuticaSKIDBinder
         public CalcDevice gpu_option;
     }
 
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct SKIDImageLock
+    {
+        public byte* data;
+        public nuint len;
+        public nuint row_bytes;
+        public nuint width;
+        public nuint height;
+        public uint format;
+    }
+
 
 
 }
@@ -0,0 +1,38 @@
+package dev.lutica.skid;
+
+/**
+ * 외부 버퍼에서 4채널 픽셀의 채널이 놓인 순서.
+ *
+ * 네이티브 측 {@code SKIDChannelOrder}의 판별값과 {@link #code()}가 일치해야 한다.
+ * 네이티브 이미지 내부는 항상 RGBA 순서로 저장된다.
+ */
+public enum SKIDChannelOrder {
+    RGBA(0),
+    BGRA(1),
+    ARGB(2);
+
+    private final int code;
+
+    SKIDChannelOrder(int code) {
+        this.code = code;
+    }
+
+    /**
+     * 네이티브 코드 값을 반환한다.
+     */
+    public int code() {
+        return code;
+    }
+
+    /**
+     * 네이티브 코드 값으로부터 채널 순서를 찾는다.
+     */
+    public static SKIDChannelOrder fromCode(int code) {
+        for (SKIDChannelOrder o : values()) {
+            if (o.code == code) {
+                return o;
+            }
+        }
+        throw new IllegalArgumentException("Unknown channel order code: " + code);
+    }
+}
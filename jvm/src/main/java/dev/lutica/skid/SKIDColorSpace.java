@@ -0,0 +1,41 @@
+package dev.lutica.skid;
+
+/**
+ * 이미지 픽셀 값이 인코딩된 색 공간.
+ *
+ * 네이티브 측 {@code SKIDColorSpace}의 판별값과 {@link #code()}가 일치해야 한다.
+ * 모든 채널은 0.0~1.0 범위로 정규화되어 저장된다 (HSV/HSL의 H, Lab의 a/b 포함).
+ */
+public enum SKIDColorSpace {
+    SRGB(0),
+    LINEAR(1),
+    HSV(2),
+    HSL(3),
+    YCBCR(4),
+    LAB(5);
+
+    private final int code;
+
+    SKIDColorSpace(int code) {
+        this.code = code;
+    }
+
+    /**
+     * 네이티브 코드 값을 반환한다.
+     */
+    public int code() {
+        return code;
+    }
+
+    /**
+     * 네이티브 코드 값으로부터 색 공간을 찾는다.
+     */
+    public static SKIDColorSpace fromCode(int code) {
+        for (SKIDColorSpace cs : values()) {
+            if (cs.code == code) {
+                return cs;
+            }
+        }
+        throw new IllegalArgumentException("Unknown color space code: " + code);
+    }
+}
@@ -0,0 +1,38 @@
+package dev.lutica.skid;
+
+/**
+ * f32 값을 8/16비트 정수 채널로 줄일 때의 양자화 방식.
+ *
+ * 네이티브 측 {@code SKIDDitherMethod}의 판별값과 {@link #code()}가 일치해야 한다.
+ */
+public enum SKIDDitherMethod {
+    ROUND(0),
+    BAYER(1),
+    BLUE_NOISE(2),
+    FLOYD_STEINBERG(3);
+
+    private final int code;
+
+    SKIDDitherMethod(int code) {
+        this.code = code;
+    }
+
+    /**
+     * 네이티브 코드 값을 반환한다.
+     */
+    public int code() {
+        return code;
+    }
+
+    /**
+     * 네이티브 코드 값으로부터 양자화 방식을 찾는다.
+     */
+    public static SKIDDitherMethod fromCode(int code) {
+        for (SKIDDitherMethod m : values()) {
+            if (m.code == code) {
+                return m;
+            }
+        }
+        throw new IllegalArgumentException("Unknown dither method code: " + code);
+    }
+}
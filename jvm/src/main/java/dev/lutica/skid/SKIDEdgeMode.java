@@ -0,0 +1,38 @@
+package dev.lutica.skid;
+
+/**
+ * 필터가 이미지 밖 좌표를 샘플링할 때의 처리 방식.
+ *
+ * 네이티브 측 {@code SKIDEdgeMode}의 판별값과 {@link #code()}가 일치해야 한다.
+ */
+public enum SKIDEdgeMode {
+    CLAMP(0),
+    WRAP(1),
+    MIRROR(2),
+    CONSTANT(3);
+
+    private final int code;
+
+    SKIDEdgeMode(int code) {
+        this.code = code;
+    }
+
+    /**
+     * 네이티브 코드 값을 반환한다.
+     */
+    public int code() {
+        return code;
+    }
+
+    /**
+     * 네이티브 코드 값으로부터 가장자리 모드를 찾는다.
+     */
+    public static SKIDEdgeMode fromCode(int code) {
+        for (SKIDEdgeMode m : values()) {
+            if (m.code == code) {
+                return m;
+            }
+        }
+        throw new IllegalArgumentException("Unknown edge mode code: " + code);
+    }
+}
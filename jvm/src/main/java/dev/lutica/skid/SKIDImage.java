@@ -1,6 +1,8 @@
 package dev.lutica.skid;
 
 import java.lang.ref.Cleaner;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
 
 /**
  * SKIDImage의 자원 안전 래퍼.
@@ -21,6 +23,9 @@ public class SKIDImage implements AutoCloseable {
 
     private static final Cleaner CLEANER = Cleaner.create();
 
+    /** 필터를 모든 채널(RGBA)에 적용하는 채널 마스크. */
+    private static final int ALL_CHANNELS = 0b1111;
+
     private long handle;
     private final Cleaner.Cleanable cleanable;
 
@@ -55,6 +60,29 @@ public class SKIDImage implements AutoCloseable {
         return new SKIDImage(SKIDNative.createFromF32Array(data, width, height));
     }
 
+    /**
+     * 네이티브 픽셀 형식의 byte 배열로부터 이미지를 생성한다.
+     */
+    public static SKIDImage fromBytes(byte[] data, int width, int height, SKIDPixelFormat format) {
+        return new SKIDImage(SKIDNative.createFromBytes(data, width, height, format.code()));
+    }
+
+    /**
+     * 행 간격과 채널 순서를 지정해 byte 배열로부터 이미지를 생성한다.
+     */
+    public static SKIDImage fromBytes(byte[] data, int width, int height, int rowStride,
+                                      SKIDPixelFormat format, SKIDChannelOrder order) {
+        return new SKIDImage(SKIDNative.createFromU8Buffer(data, width, height, rowStride, format.code(), order.code()));
+    }
+
+    /**
+     * 16비트 short 배열(R16, RGBA16, RGBA16F)로부터 이미지를 생성한다.
+     */
+    public static SKIDImage fromShorts(short[] data, int width, int height, int rowStride,
+                                       SKIDPixelFormat format, SKIDChannelOrder order) {
+        return new SKIDImage(SKIDNative.createFromU16Buffer(data, width, height, rowStride, format.code(), order.code()));
+    }
+
     /**
      * 이미지 너비를 반환한다.
      */
@@ -79,6 +107,85 @@ public class SKIDImage implements AutoCloseable {
         return SKIDNative.getDataAsF32Array(handle);
     }
 
+    /**
+     * 이미지의 픽셀 형식을 반환한다.
+     */
+    public SKIDPixelFormat getPixelFormat() {
+        ensureValid();
+        return SKIDPixelFormat.fromCode(SKIDNative.getPixelFormat(handle));
+    }
+
+    /**
+     * 이미지 데이터를 네이티브 픽셀 형식의 byte 배열로 반환한다.
+     */
+    public byte[] getBytes() {
+        ensureValid();
+        return SKIDNative.getDataAsBytes(handle);
+    }
+
+    /**
+     * 이미지를 지정한 픽셀 형식으로 디더링 양자화한 byte 배열로 반환한다.
+     */
+    public byte[] getBytes(SKIDPixelFormat format, SKIDDitherMethod method) {
+        ensureValid();
+        return SKIDNative.getDataAsBytesDithered(handle, format.code(), method.code());
+    }
+
+    /**
+     * 다른 픽셀 형식으로 변환한 이미지를 반환한다.
+     */
+    public SKIDImage convertFormat(SKIDPixelFormat format) {
+        ensureValid();
+        return new SKIDImage(SKIDNative.convertFormat(handle, format.code()));
+    }
+
+    /**
+     * 지정한 픽셀 형식으로 0으로 채워진 이미지를 생성한다.
+     */
+    public static SKIDImage create(int width, int height, SKIDPixelFormat format) {
+        return new SKIDImage(SKIDNative.createWithFormat(width, height, format.code()));
+    }
+
+    /**
+     * DirectByteBuffer의 픽셀로 이미지를 생성한다 (복사 1회).
+     */
+    public static SKIDImage fromDirectBuffer(ByteBuffer data, int width, int height, int rowStride,
+                                             SKIDPixelFormat format, SKIDChannelOrder order) {
+        return new SKIDImage(SKIDNative.createFromDirectBuffer(data, width, height, rowStride, format.code(), order.code()));
+    }
+
+    /**
+     * 픽셀 메모리를 잠그고 직접 읽고 쓸 수 있는 버퍼를 반환한다.
+     * 사용이 끝나면 반드시 {@link #unlock()}을 호출할 것.
+     */
+    public ByteBuffer lock() {
+        ensureValid();
+        ByteBuffer buffer = SKIDNative.lockDirect(handle);
+        if (buffer == null) {
+            throw new IllegalStateException("Failed to lock SKIDImage");
+        }
+        return buffer.order(ByteOrder.LITTLE_ENDIAN);
+    }
+
+    /**
+     * {@link #lock()}으로 잠근 픽셀 메모리를 해제한다. 이후 버퍼는 사용할 수 없다.
+     */
+    public void unlock() {
+        ensureValid();
+        SKIDNative.unlock(handle);
+    }
+
+    /**
+     * 이미지 데이터를 DirectByteBuffer에 복사한다.
+     */
+    public void copyTo(ByteBuffer out) {
+        ensureValid();
+        int result = SKIDNative.copyToDirectBuffer(handle, out);
+        if (result != 0) {
+            throw new IllegalArgumentException("copyToDirectBuffer failed: " + result);
+        }
+    }
+
     /**
      * 이미지를 리사이즈한다 (GPU 가속).
      * 원본은 변경되지 않으며, 새 SKIDImage가 반환된다.
@@ -96,6 +203,114 @@ public class SKIDImage implements AutoCloseable {
         return new SKIDImage(SKIDNative.generateNormalMap(handle, xFactor, yFactor));
     }
 
+    /**
+     * 이미지의 색 공간을 반환한다.
+     */
+    public SKIDColorSpace getColorSpace() {
+        ensureValid();
+        return SKIDColorSpace.fromCode(SKIDNative.getColorSpace(handle));
+    }
+
+    /**
+     * 픽셀 값은 그대로 두고 색 공간 태그만 변경한다.
+     */
+    public void setColorSpace(SKIDColorSpace colorSpace) {
+        ensureValid();
+        SKIDNative.setColorSpace(handle, colorSpace.code());
+    }
+
+    /**
+     * 이미지를 다른 색 공간으로 변환한다 (GPU 가속).
+     * 원본은 변경되지 않으며, 새 SKIDImage가 반환된다.
+     */
+    public SKIDImage convertColorSpace(SKIDColorSpace target) {
+        ensureValid();
+        return new SKIDImage(SKIDNative.convertColorSpace(handle, target.code()));
+    }
+
+    /**
+     * RGB에 알파를 곱한 프리멀티플라이드 이미지를 반환한다 (GPU 가속).
+     */
+    public SKIDImage premultiply() {
+        ensureValid();
+        return new SKIDImage(SKIDNative.premultiply(handle));
+    }
+
+    /**
+     * straight 알파로 되돌린 이미지를 반환한다 (GPU 가속).
+     */
+    public SKIDImage unpremultiply() {
+        ensureValid();
+        return new SKIDImage(SKIDNative.unpremultiply(handle));
+    }
+
+    /**
+     * overlay를 이 이미지 위에 합성한다 (GPU 가속).
+     *
+     * @param mode 0 = normal, 1 = add, 2 = subtract, 3 = multiply, 4 = divide
+     */
+    public SKIDImage blend(SKIDImage overlay, int mode, float opacity) {
+        ensureValid();
+        return new SKIDImage(SKIDNative.blend(handle, overlay.getHandle(), mode, opacity));
+    }
+
+    /**
+     * 가우시안 블러를 적용한다 (GPU 가속, 가장자리 clamp).
+     */
+    public SKIDImage gaussianBlur(float sigma) {
+        return gaussianBlur(sigma, false, SKIDEdgeMode.CLAMP);
+    }
+
+    /**
+     * 가우시안 블러를 적용한다.
+     *
+     * @param approximate true이면 박스 블러 3회로 근사한다 (큰 반경에서 빠름)
+     */
+    public SKIDImage gaussianBlur(float sigma, boolean approximate, SKIDEdgeMode edgeMode) {
+        ensureValid();
+        return new SKIDImage(SKIDNative.gaussianBlur(handle, sigma, approximate ? 1 : 0, edgeMode.code(), ALL_CHANNELS));
+    }
+
+    /**
+     * (2 * radius + 1) 크기의 박스 블러를 적용한다.
+     */
+    public SKIDImage boxBlur(int radius, SKIDEdgeMode edgeMode) {
+        ensureValid();
+        return new SKIDImage(SKIDNative.boxBlur(handle, radius, edgeMode.code(), ALL_CHANNELS));
+    }
+
+    /**
+     * 가장자리를 보존하는 바이리터럴 필터를 적용한다.
+     */
+    public SKIDImage bilateralBlur(float spatialSigma, float rangeSigma, SKIDEdgeMode edgeMode) {
+        ensureValid();
+        return new SKIDImage(SKIDNative.bilateralBlur(handle, spatialSigma, rangeSigma, edgeMode.code(), ALL_CHANNELS));
+    }
+
+    /**
+     * 채널별 최솟값/최댓값/평균/표준편차를 계산한다 (GPU 가속).
+     */
+    public SKIDImageStatistics statistics() {
+        ensureValid();
+        float[] packed = SKIDNative.computeStatistics(handle);
+        if (packed == null) {
+            throw new IllegalStateException("Failed to compute statistics");
+        }
+        return new SKIDImageStatistics(packed);
+    }
+
+    /**
+     * 채널별 히스토그램을 계산한다. 결과는 채널 순서로 이어 붙인 channelCount * bins 개의 개수이다.
+     */
+    public int[] histogram(int bins, float rangeMin, float rangeMax) {
+        ensureValid();
+        int[] counts = SKIDNative.computeHistogram(handle, bins, rangeMin, rangeMax);
+        if (counts == null) {
+            throw new IllegalArgumentException("Invalid histogram parameters");
+        }
+        return counts;
+    }
+
     /**
      * 네이티브 핸들을 반환한다 (고급 사용 시).
      */
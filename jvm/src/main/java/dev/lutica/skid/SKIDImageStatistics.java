@@ -0,0 +1,21 @@
+package dev.lutica.skid;
+
+/**
+ * 채널별 이미지 통계 (저장된 값 기준, 색 공간 변환 없음).
+ *
+ * 각 배열은 RGBA 순서의 4개 항목이며, 이미지 채널 수를 넘는 항목은 0이다.
+ */
+public final class SKIDImageStatistics {
+    public final float[] min;
+    public final float[] max;
+    public final float[] mean;
+    /** 모표준편차 (n으로 나눔) */
+    public final float[] stdDev;
+
+    SKIDImageStatistics(float[] packed) {
+        this.min = java.util.Arrays.copyOfRange(packed, 0, 4);
+        this.max = java.util.Arrays.copyOfRange(packed, 4, 8);
+        this.mean = java.util.Arrays.copyOfRange(packed, 8, 12);
+        this.stdDev = java.util.Arrays.copyOfRange(packed, 12, 16);
+    }
+}
@@ -42,6 +42,45 @@ public class SKIDNative {
      */
     public static native long createFromF32Array(float[] data, int width, int height);
 
+    /**
+     * 네이티브 픽셀 형식의 byte[] 배열로부터 이미지를 생성한다.
+     * float로 넓히지 않고 지정한 형식 그대로 저장된다.
+     *
+     * @param data   픽셀 바이트 (길이 = width * height * format.bytesPerPixel())
+     * @param width  이미지 너비
+     * @param height 이미지 높이
+     * @param format {@link SKIDPixelFormat#code()} 값
+     * @return 이미지 핸들 (0이면 실패)
+     */
+    public static native long createFromBytes(byte[] data, int width, int height, int format);
+
+    /**
+     * byte 배열로부터 행 간격과 채널 순서를 지정해 이미지를 생성한다.
+     *
+     * @param data         픽셀 바이트
+     * @param width        이미지 너비
+     * @param height       이미지 높이
+     * @param rowStride    한 행의 바이트 수 (0이면 여백 없음)
+     * @param format       {@link SKIDPixelFormat#code()} 값
+     * @param channelOrder {@link SKIDChannelOrder#code()} 값
+     * @return 이미지 핸들 (0이면 실패)
+     */
+    public static native long createFromU8Buffer(byte[] data, int width, int height, int rowStride, int format, int channelOrder);
+
+    /**
+     * 16비트 short 배열(R16, RGBA16, RGBA16F)로부터 이미지를 생성한다.
+     * short 값은 부호 없는 16비트로 해석된다.
+     *
+     * @param data         픽셀 값
+     * @param width        이미지 너비
+     * @param height       이미지 높이
+     * @param rowStride    한 행의 바이트 수 (0이면 여백 없음)
+     * @param format       {@link SKIDPixelFormat#code()} 값 (채널당 2바이트 형식)
+     * @param channelOrder {@link SKIDChannelOrder#code()} 값
+     * @return 이미지 핸들 (0이면 실패)
+     */
+    public static native long createFromU16Buffer(short[] data, int width, int height, int rowStride, int format, int channelOrder);
+
     /**
      * 이미지 핸들을 해제한다. 사용 후 반드시 호출할 것.
      *
@@ -84,6 +123,95 @@ public class SKIDNative {
      */
     public static native float[] getDataAsF32Array(long handle);
 
+    /**
+     * 이미지의 픽셀 형식 코드를 반환한다.
+     *
+     * @param handle 이미지 핸들
+     * @return {@link SKIDPixelFormat#code()} 값 (-1이면 유효하지 않은 핸들)
+     */
+    public static native int getPixelFormat(long handle);
+
+    /**
+     * 이미지 데이터를 네이티브 픽셀 형식의 byte[] 배열로 반환한다.
+     *
+     * @param handle 이미지 핸들
+     * @return 픽셀 바이트, 또는 null (유효하지 않은 핸들)
+     */
+    public static native byte[] getDataAsBytes(long handle);
+
+    /**
+     * 이미지를 지정한 픽셀 형식으로 디더링 양자화한 byte[] 배열을 반환한다. 원본은 바뀌지 않는다.
+     *
+     * @param handle 이미지 핸들
+     * @param format {@link SKIDPixelFormat#code()} 값
+     * @param method {@link SKIDDitherMethod#code()} 값
+     * @return 픽셀 바이트, 또는 null (유효하지 않은 핸들/인자)
+     */
+    public static native byte[] getDataAsBytesDithered(long handle, int format, int method);
+
+    /**
+     * 다른 픽셀 형식으로 변환한 이미지를 생성한다.
+     *
+     * @param handle 원본 이미지 핸들
+     * @param format {@link SKIDPixelFormat#code()} 값
+     * @return 변환된 이미지 핸들 (0이면 실패)
+     */
+    public static native long convertFormat(long handle, int format);
+
+    // ─── 직접 메모리 접근 ───
+
+    /**
+     * 지정한 픽셀 형식으로 0으로 채워진 이미지를 생성한다.
+     * {@link #lockDirect(long)}로 받은 버퍼에 직접 쓰면 복사 없이 픽셀을 채울 수 있다.
+     *
+     * @param width  이미지 너비
+     * @param height 이미지 높이
+     * @param format {@link SKIDPixelFormat#code()} 값
+     * @return 이미지 핸들 (0이면 실패)
+     */
+    public static native long createWithFormat(int width, int height, int format);
+
+    /**
+     * 이미지의 픽셀 메모리를 잠그고 이를 가리키는 DirectByteBuffer를 반환한다 (복사 없음).
+     * 버퍼는 {@link #unlock(long)} 호출 전까지만 유효하다. 잠금 중 {@link #free(long)}는 unlock 시점까지 미뤄진다.
+     * 잠금 중에는 다른 처리 함수가 이 핸들을 유효하지 않은 핸들로 보고 실패한다.
+     *
+     * @param handle 이미지 핸들
+     * @return 네이티브 픽셀 형식의 버퍼 (리틀 엔디언), 또는 null (유효하지 않은 핸들)
+     */
+    public static native java.nio.ByteBuffer lockDirect(long handle);
+
+    /**
+     * {@link #lockDirect(long)}로 잠근 이미지를 해제한다.
+     *
+     * @param handle 이미지 핸들
+     * @return 0 성공, -2 잠겨 있지 않은 핸들
+     */
+    public static native int unlock(long handle);
+
+    /**
+     * DirectByteBuffer의 픽셀을 한 번만 복사해 이미지를 생성한다.
+     *
+     * @param data         direct 버퍼
+     * @param width        이미지 너비
+     * @param height       이미지 높이
+     * @param rowStride    한 행의 바이트 수 (0이면 여백 없음)
+     * @param format       {@link SKIDPixelFormat#code()} 값
+     * @param channelOrder {@link SKIDChannelOrder#code()} 값
+     * @return 이미지 핸들 (0이면 실패)
+     */
+    public static native long createFromDirectBuffer(java.nio.ByteBuffer data, int width, int height,
+                                                     int rowStride, int format, int channelOrder);
+
+    /**
+     * 이미지 데이터를 네이티브 픽셀 형식 그대로 DirectByteBuffer에 복사한다.
+     *
+     * @param handle 이미지 핸들
+     * @param out    direct 버퍼
+     * @return 0 성공, -1 direct 버퍼가 아님, -2 버퍼가 작음, -3 유효하지 않은 핸들
+     */
+    public static native int copyToDirectBuffer(long handle, java.nio.ByteBuffer out);
+
     // ─── GPU 이미지 처리 ───
 
     /**
@@ -107,4 +235,138 @@ public class SKIDNative {
      * @return 노멀맵 이미지 핸들 (0이면 실패)
      */
     public static native long generateNormalMap(long handle, float xFactor, float yFactor);
+
+    // ─── 색 공간 ───
+
+    /**
+     * 이미지의 색 공간 코드를 반환한다.
+     *
+     * @param handle 이미지 핸들
+     * @return {@link SKIDColorSpace#code()} 값 (-1이면 유효하지 않은 핸들)
+     */
+    public static native int getColorSpace(long handle);
+
+    /**
+     * 픽셀 값은 그대로 두고 색 공간 태그만 변경한다.
+     *
+     * @param handle     이미지 핸들
+     * @param colorSpace {@link SKIDColorSpace#code()} 값
+     * @return 성공 여부
+     */
+    public static native boolean setColorSpace(long handle, int colorSpace);
+
+    /**
+     * 이미지를 다른 색 공간으로 변환한다 (GPU 가속).
+     * 원본 이미지는 변경되지 않으며, 새 핸들이 반환된다.
+     *
+     * @param handle 원본 이미지 핸들
+     * @param target {@link SKIDColorSpace#code()} 값
+     * @return 변환된 이미지 핸들 (0이면 실패)
+     */
+    public static native long convertColorSpace(long handle, int target);
+
+    // ─── 알파 / 합성 ───
+
+    /**
+     * 이미지의 알파 모드 코드를 반환한다.
+     *
+     * @param handle 이미지 핸들
+     * @return 0 = straight, 1 = premultiplied (-1이면 유효하지 않은 핸들)
+     */
+    public static native int getAlphaMode(long handle);
+
+    /**
+     * 픽셀 값은 그대로 두고 알파 모드 태그만 변경한다.
+     *
+     * @param handle    이미지 핸들
+     * @param alphaMode 0 = straight, 1 = premultiplied
+     * @return 성공 여부
+     */
+    public static native boolean setAlphaMode(long handle, int alphaMode);
+
+    /**
+     * RGB에 알파를 곱한 프리멀티플라이드 이미지를 생성한다 (GPU 가속).
+     *
+     * @param handle 원본 이미지 핸들
+     * @return 새 이미지 핸들 (0이면 실패)
+     */
+    public static native long premultiply(long handle);
+
+    /**
+     * 프리멀티플라이드 이미지를 straight 알파로 되돌린다 (GPU 가속).
+     *
+     * @param handle 원본 이미지 핸들
+     * @return 새 이미지 핸들 (0이면 실패)
+     */
+    public static native long unpremultiply(long handle);
+
+    /**
+     * overlay를 base 위에 합성한다 (linear + 프리멀티플라이드 공간, GPU 가속).
+     * 두 이미지는 크기가 같아야 한다.
+     *
+     * @param baseHandle    바탕 이미지 핸들
+     * @param overlayHandle 덮을 이미지 핸들
+     * @param mode          0 = normal, 1 = add, 2 = subtract, 3 = multiply, 4 = divide
+     * @param opacity       overlay 불투명도 (0.0~1.0)
+     * @return 합성된 이미지 핸들 (0이면 실패)
+     */
+    public static native long blend(long baseHandle, long overlayHandle, int mode, float opacity);
+
+    // ─── 블러 ───
+
+    /**
+     * 가우시안 블러를 적용한다 (GPU 가속).
+     *
+     * @param handle      원본 이미지 핸들
+     * @param sigma       표준편차 (픽셀)
+     * @param mode        0 = exact (분리 가능 커널), 1 = approximate (박스 블러 3회, 큰 반경용)
+     * @param edgeMode    {@link SKIDEdgeMode#code()} 값
+     * @param channelMask 비트 i가 켜진 채널만 블러 (0b1111 = RGBA 전체)
+     * @return 블러된 이미지 핸들 (0이면 실패)
+     */
+    public static native long gaussianBlur(long handle, float sigma, int mode, int edgeMode, int channelMask);
+
+    /**
+     * (2 * radius + 1) 크기의 박스 블러를 적용한다. 반경과 무관하게 비용이 일정하다.
+     *
+     * @param handle      원본 이미지 핸들
+     * @param radius      반경 (픽셀)
+     * @param edgeMode    {@link SKIDEdgeMode#code()} 값
+     * @param channelMask 비트 i가 켜진 채널만 블러
+     * @return 블러된 이미지 핸들 (0이면 실패)
+     */
+    public static native long boxBlur(long handle, int radius, int edgeMode, int channelMask);
+
+    /**
+     * 가장자리를 보존하는 바이리터럴 필터를 적용한다.
+     *
+     * @param handle       원본 이미지 핸들
+     * @param spatialSigma 거리 가중치의 표준편차 (픽셀)
+     * @param rangeSigma   값 차이 가중치의 표준편차 (0.0~1.0 값 기준)
+     * @param edgeMode     {@link SKIDEdgeMode#code()} 값
+     * @param channelMask  비트 i가 켜진 채널만 필터링
+     * @return 필터링된 이미지 핸들 (0이면 실패)
+     */
+    public static native long bilateralBlur(long handle, float spatialSigma, float rangeSigma, int edgeMode, int channelMask);
+
+    // ─── 통계 ───
+
+    /**
+     * 채널별 통계를 GPU 리덕션으로 계산한다.
+     *
+     * @param handle 이미지 핸들
+     * @return [min×4, max×4, mean×4, stdDev×4] 순서의 float[16] (유효하지 않은 핸들이면 null)
+     */
+    public static native float[] computeStatistics(long handle);
+
+    /**
+     * 채널별 히스토그램을 계산한다. 범위 밖의 값은 양 끝 칸에 센다.
+     *
+     * @param handle   이미지 핸들
+     * @param bins     칸 수
+     * @param rangeMin 첫 칸의 시작 값
+     * @param rangeMax 마지막 칸의 끝 값
+     * @return 채널 순서로 이어 붙인 channelCount * bins 개의 개수 (실패 시 null)
+     */
+    public static native int[] computeHistogram(long handle, int bins, float rangeMin, float rangeMax);
 }
@@ -0,0 +1,61 @@
+package dev.lutica.skid;
+
+/**
+ * 네이티브 이미지의 픽셀 저장 형식.
+ *
+ * 네이티브 측 {@code SKIDPixelFormat}의 판별값과 {@link #code()}가 일치해야 한다.
+ * 다중 바이트 채널은 리틀 엔디언으로 저장된다.
+ */
+public enum SKIDPixelFormat {
+    R8(0, 1, 1),
+    RG8(1, 2, 1),
+    RGBA8(2, 4, 1),
+    R16(3, 1, 2),
+    RGBA16(4, 4, 2),
+    R32F(5, 1, 4),
+    RGBA16F(6, 4, 2),
+    RGBA32F(7, 4, 4);
+
+    private final int code;
+    private final int channels;
+    private final int bytesPerChannel;
+
+    SKIDPixelFormat(int code, int channels, int bytesPerChannel) {
+        this.code = code;
+        this.channels = channels;
+        this.bytesPerChannel = bytesPerChannel;
+    }
+
+    /**
+     * 네이티브 코드 값을 반환한다.
+     */
+    public int code() {
+        return code;
+    }
+
+    /**
+     * 채널 수를 반환한다.
+     */
+    public int channels() {
+        return channels;
+    }
+
+    /**
+     * 픽셀 하나의 바이트 수를 반환한다.
+     */
+    public int bytesPerPixel() {
+        return channels * bytesPerChannel;
+    }
+
+    /**
+     * 네이티브 코드 값으로부터 픽셀 형식을 찾는다.
+     */
+    public static SKIDPixelFormat fromCode(int code) {
+        for (SKIDPixelFormat f : values()) {
+            if (f.code == code) {
+                return f;
+            }
+        }
+        throw new IllegalArgumentException("Unknown pixel format code: " + code);
+    }
+}
@@ -1,7 +1,7 @@
-#[cfg(feature = "use_cuda")]
-use cubecl::Runtime;
+// C/C# 에서 호출하는 진입점은 넘어온 포인터를 직접 검사하므로 unsafe fn 으로 선언하지 않는다.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use crate::model::{skid_image::{SKIDImage, SKIDSizeVector2}, skid_color::SKIDColor};
+use crate::model::{skid_image::{SKIDImage, SKIDSizeVector2}, skid_color_space::SKIDColorSpace, skid_alpha_mode::SKIDAlphaMode, skid_pixel_format::{SKIDChannelOrder, SKIDPixelFormat}};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::{Mutex, RwLock};
@@ -42,13 +42,16 @@ struct NormalMapOptions {
     pub(crate) gpu_option: Option<CalcDevice>,
 }
 #[no_mangle]
+#[allow(improper_ctypes_definitions)]
+// 켜진 GPU 기능에 따라 마지막 블록이 달라지므로 각 블록은 명시적으로 return 한다.
+#[allow(clippy::needless_return)]
 extern "C" fn skid_generate_normal_map(
     input_image: SKIDImage,
     options: NormalMapOptions,
 ) -> SKIDImage {
     let x_factor = options.x_factor;
     let y_factor: Option<f32> = options.y_factor;
-    let make_by_gpu = options.make_by_gpu;
+    let _make_by_gpu = options.make_by_gpu;
 
     if let Some(gpu) = options.gpu_option {
         // Use GPU processing if available and requested
@@ -63,7 +66,7 @@ extern "C" fn skid_generate_normal_map(
                 y_factor,
             )
         }
-        #[cfg(feature = "use_cuda")]
+        #[cfg(all(feature = "use_cuda", not(feature = "use_wgpu")))]
         {
             use cubecl::cuda::{CudaDevice, CudaRuntime};
             let gpu_device = CudaDevice::new(gpu.device_id as usize);
@@ -74,7 +77,7 @@ extern "C" fn skid_generate_normal_map(
                 y_factor,
             )
         }
-        #[cfg(feature = "use_hip")]
+        #[cfg(all(feature = "use_hip", not(any(feature = "use_wgpu", feature = "use_cuda"))))]
         {
             use cubecl::hip::{HipDevice, HipRuntime};
             let gpu_device = HipDevice::new(gpu.device_id as usize);
@@ -91,7 +94,7 @@ extern "C" fn skid_generate_normal_map(
 
         // Default to CPU processing if no GPU is specified
         let cpu_device = WgpuDevice::Cpu; // Assuming 0 is the CPU device ID
-        return crate::processor::make_normal_map::make_normal_map_base::<WgpuRuntime>(
+        crate::processor::make_normal_map::make_normal_map_base::<WgpuRuntime>(
             cpu_device,
             &input_image,
             x_factor,
@@ -118,6 +121,70 @@ pub fn new_handle_id() -> u64 {
     *id
 }
 
+/// 핸들의 이미지를 복제해 `f`로 처리한 뒤 결과를 새 핸들로 등록한다. (JNI 모듈과 공유)
+///
+/// `skid_image_resize`와 같은 락 점유 최소화 패턴을 따른다:
+/// read lock → clone → drop, 락 없이 처리, write lock → insert → drop.
+/// 핸들이 유효하지 않으면 0을 반환한다.
+pub fn process_image_handle<F: FnOnce(&SKIDImage) -> SKIDImage>(handle: u64, f: F) -> u64 {
+    let image_clone = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => image.clone(),
+            None => return 0,
+        }
+    };
+
+    let result = f(&image_clone);
+
+    let new_handle = new_handle_id();
+    IMAGE_HANDLES.write().unwrap().insert(new_handle, Box::new(result));
+    new_handle
+}
+
+/// 실패할 수 있는 처리용 `process_image_handle`. 핸들이 없거나 `f`가 `Err`를 반환하면 0 을 반환한다.
+pub fn try_process_image_handle<F: FnOnce(&SKIDImage) -> Result<SKIDImage, String>>(handle: u64, f: F) -> u64 {
+    let image_clone = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => image.clone(),
+            None => return 0,
+        }
+    };
+
+    match f(&image_clone) {
+        Ok(result) => {
+            let new_handle = new_handle_id();
+            IMAGE_HANDLES.write().unwrap().insert(new_handle, Box::new(result));
+            new_handle
+        }
+        Err(_) => 0,
+    }
+}
+
+/// 원본을 복제하지 않고 읽기 락을 잡은 채 처리하는 `try_process_image_handle`.
+///
+/// GPU 메모리보다 큰 이미지를 타일로 처리할 때처럼 원본 복제 자체가 부담스러운 경우에 쓴다.
+/// 처리하는 동안 다른 읽기는 막지 않지만 같은 핸들 맵에 대한 쓰기는 기다린다.
+pub fn try_process_image_handle_shared<F: FnOnce(&SKIDImage) -> Result<SKIDImage, String>>(handle: u64, f: F) -> u64 {
+    let result = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => f(image),
+            None => return 0,
+        }
+    };
+
+    match result {
+        Ok(result) => {
+            let new_handle = new_handle_id();
+            IMAGE_HANDLES.write().unwrap().insert(new_handle, Box::new(result));
+            new_handle
+        }
+        Err(_) => 0,
+    }
+}
+
 /// C#에서 float[] 배열을 받아 SKIDImage를 생성하고 핸들을 반환합니다.
 #[no_mangle]
 pub extern "C" fn skid_image_create_from_f32_array(
@@ -127,22 +194,115 @@ pub extern "C" fn skid_image_create_from_f32_array(
 ) -> u64 {
     if bytes.is_null() { return 0; }
     let data_slice = unsafe { std::slice::from_raw_parts(bytes, width * height * 4) };
-    let colors: Vec<SKIDColor> = data_slice.chunks_exact(4)
-        .map(|chunk| SKIDColor::from_f32_array(chunk.try_into().unwrap()))
-        .collect();
-    let image = SKIDImage::from_1d_data(SKIDSizeVector2 { width, height }, colors);
+    // RGBA32F 는 f32 배열과 바이트 배치가 같으므로 SKIDColor 를 거치지 않고 그대로 복사한다.
+    let image = SKIDImage::from_native_bytes(
+        SKIDSizeVector2 { width, height },
+        SKIDPixelFormat::RGBA32F,
+        bytemuck::cast_slice(data_slice).to_vec(),
+    );
+
+    let handle_id = new_handle_id();
+    IMAGE_HANDLES.write().unwrap().insert(handle_id, Box::new(image));
+    handle_id
+}
+
+/// 네이티브 픽셀 형식의 바이트 배열로 SKIDImage 를 생성하고 핸들을 반환합니다.
+/// f32 로 넓히지 않고 `format` 그대로 저장합니다. (format: SKIDPixelFormat 코드)
+/// 행 사이 여백이 없는 RGBA 순서 버퍼용이며, 그 외에는 `skid_image_create_from_u8_buffer`를 사용하세요.
+#[no_mangle]
+pub extern "C" fn skid_image_create_from_bytes(
+    bytes: *const u8,
+    bytes_len: usize,
+    width: usize,
+    height: usize,
+    format: u32,
+) -> u64 {
+    skid_image_create_from_u8_buffer(bytes, bytes_len, width, height, 0, format, SKIDChannelOrder::Rgba.to_u32())
+}
+
+/// 바이트 버퍼 → SKIDImage 핸들 공통 처리. 실패 시 0.
+fn create_handle_from_raw_bytes(
+    bytes: &[u8],
+    width: usize,
+    height: usize,
+    row_stride: usize,
+    format: u32,
+    channel_order: u32,
+) -> u64 {
+    let (format, channel_order) = match (SKIDPixelFormat::from_u32(format), SKIDChannelOrder::from_u32(channel_order)) {
+        (Some(f), Some(o)) => (f, o),
+        _ => return 0,
+    };
+    let image = match SKIDImage::from_raw_bytes(bytes, SKIDSizeVector2 { width, height }, format, row_stride, channel_order) {
+        Ok(image) => image,
+        Err(_) => return 0,
+    };
 
     let handle_id = new_handle_id();
     IMAGE_HANDLES.write().unwrap().insert(handle_id, Box::new(image));
     handle_id
 }
 
+/// u8 버퍼(8비트 형식 또는 임의 형식의 원시 바이트)로 SKIDImage 를 생성하고 핸들을 반환합니다.
+///
+/// - row_stride   : 한 행의 바이트 수 (0 이면 여백 없음)
+/// - format       : SKIDPixelFormat 코드
+/// - channel_order: 0 RGBA, 1 BGRA, 2 ARGB (4채널 형식에만 의미가 있음)
+///
+/// 버퍼 크기나 인자가 맞지 않으면 0을 반환합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_create_from_u8_buffer(
+    bytes: *const u8,
+    bytes_len: usize,
+    width: usize,
+    height: usize,
+    row_stride: usize,
+    format: u32,
+    channel_order: u32,
+) -> u64 {
+    if bytes.is_null() { return 0; }
+    let data_slice = unsafe { std::slice::from_raw_parts(bytes, bytes_len) };
+    create_handle_from_raw_bytes(data_slice, width, height, row_stride, format, channel_order)
+}
+
+/// u16 버퍼(R16, RGBA16, RGBA16F)로 SKIDImage 를 생성하고 핸들을 반환합니다.
+///
+/// - data_len     : 버퍼의 u16 요소 개수
+/// - row_stride   : 한 행의 **바이트** 수 (0 이면 여백 없음)
+/// - format       : SKIDPixelFormat 코드 (채널당 2바이트 형식만 허용)
+/// - channel_order: 0 RGBA, 1 BGRA, 2 ARGB
+#[no_mangle]
+pub extern "C" fn skid_image_create_from_u16_buffer(
+    data: *const u16,
+    data_len: usize,
+    width: usize,
+    height: usize,
+    row_stride: usize,
+    format: u32,
+    channel_order: u32,
+) -> u64 {
+    if data.is_null() { return 0; }
+    match SKIDPixelFormat::from_u32(format) {
+        Some(f) if f.bytes_per_channel() == 2 => {}
+        _ => return 0,
+    }
+    let data_slice = unsafe { std::slice::from_raw_parts(data, data_len) };
+    // 내부 버퍼는 리틀 엔디언이므로 호스트 바이트 순서에 기대지 않고 요소마다 변환한다.
+    let bytes: Vec<u8> = data_slice.iter().flat_map(|v| v.to_le_bytes()).collect();
+    create_handle_from_raw_bytes(&bytes, width, height, row_stride, format, channel_order)
+}
+
 /// 핸들을 사용하여 이미지의 메모리를 해제합니다. (매우 중요!)
+/// 잠금(`skid_image_lock`) 중인 핸들은 마지막 `skid_image_unlock` 시점까지 해제가 미뤄집니다.
 #[no_mangle]
 pub extern "C" fn skid_image_free(handle: u64) {
-    if handle != 0 {
-        IMAGE_HANDLES.write().unwrap().remove(&handle);
+    if handle == 0 { return; }
+    let mut locks = IMAGE_LOCKS.lock().unwrap();
+    if let Some(state) = locks.get_mut(&handle) {
+        state.free_pending = true;
+        return;
     }
+    IMAGE_HANDLES.write().unwrap().remove(&handle);
 }
 
 /// 핸들을 사용하여 이미지 크기를 가져옵니다.
@@ -168,12 +328,57 @@ pub extern "C" fn skid_image_get_data_as_f32_array(
     if out_bytes.is_null() { return -1; }
     let handles = IMAGE_HANDLES.read().unwrap();
     if let Some(image) = handles.get(&handle) {
-        let image_data = image.get_1d_data_as_f32();
-        if image_data.len() > buffer_len {
+        let pixel_count = image.get_size().width * image.get_size().height;
+        if pixel_count * 4 > buffer_len {
+            return -2; // Buffer too small
+        }
+        let out = unsafe { std::slice::from_raw_parts_mut(out_bytes, pixel_count * 4) };
+        if image.get_pixel_format() == SKIDPixelFormat::RGBA32F {
+            // 저장 형식과 바이트 배치가 같으므로 중간 버퍼 없이 한 번만 복사한다.
+            bytemuck::cast_slice_mut::<f32, u8>(out).copy_from_slice(image.get_native_data());
+        } else {
+            let format = image.get_pixel_format();
+            let bpp = format.bytes_per_pixel();
+            for (pixel, dst) in image.get_native_data().chunks_exact(bpp).zip(out.chunks_exact_mut(4)) {
+                dst.copy_from_slice(&format.decode_pixel(pixel).to_f32_array());
+            }
+        }
+        0 // Success
+    } else {
+        -3 // Invalid handle
+    }
+}
+
+/// 핸들을 사용하여 이미지의 픽셀 형식 코드를 가져옵니다.
+#[no_mangle]
+pub extern "C" fn skid_image_get_pixel_format(handle: u64, out_format: *mut u32) -> i32 {
+    if out_format.is_null() { return -1; }
+    let handles = IMAGE_HANDLES.read().unwrap();
+    if let Some(image) = handles.get(&handle) {
+        unsafe { *out_format = image.get_pixel_format().to_u32(); }
+        0
+    } else {
+        -2 // Invalid handle
+    }
+}
+
+/// 핸들을 사용하여 이미지 데이터를 네이티브 픽셀 형식의 바이트 배열로 복사합니다.
+/// 필요한 크기는 width * height * bytes_per_pixel 입니다.
+#[no_mangle]
+pub extern "C" fn skid_image_get_data_as_bytes(
+    handle: u64,
+    out_bytes: *mut u8,
+    buffer_len: usize,
+) -> i32 {
+    if out_bytes.is_null() { return -1; }
+    let handles = IMAGE_HANDLES.read().unwrap();
+    if let Some(image) = handles.get(&handle) {
+        let data = image.get_native_data();
+        if data.len() > buffer_len {
             return -2; // Buffer too small
         }
         unsafe {
-            std::ptr::copy_nonoverlapping(image_data.as_ptr(), out_bytes, image_data.len());
+            std::ptr::copy_nonoverlapping(data.as_ptr(), out_bytes, data.len());
         }
         0 // Success
     } else {
@@ -181,6 +386,165 @@ pub extern "C" fn skid_image_get_data_as_f32_array(
     }
 }
 
+/// 이미지를 `format` 형식으로 디더링 양자화한 바이트를 복사합니다. (원본 이미지는 바뀌지 않음)
+/// 필요한 크기는 width * height * bytes_per_pixel(format) 입니다.
+/// (0: 성공, -1: null 포인터, -2: 버퍼 부족, -3: 유효하지 않은 핸들, -4: 잘못된 형식/방식)
+#[no_mangle]
+pub extern "C" fn skid_image_get_data_as_bytes_dithered(
+    handle: u64,
+    format: u32,
+    method: u32,
+    out_bytes: *mut u8,
+    buffer_len: usize,
+) -> i32 {
+    use crate::utils::dither::SKIDDitherMethod;
+
+    if out_bytes.is_null() { return -1; }
+    let (Some(format), Some(method)) = (SKIDPixelFormat::from_u32(format), SKIDDitherMethod::from_u32(method)) else {
+        return -4;
+    };
+    let quantized = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => image.convert_format_dithered(format, method),
+            None => return -3, // Invalid handle
+        }
+    };
+    let data = quantized.get_native_data();
+    if data.len() > buffer_len {
+        return -2; // Buffer too small
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(data.as_ptr(), out_bytes, data.len());
+    }
+    0
+}
+
+/// 지정한 픽셀 형식으로 0 으로 채워진 이미지를 만들고 핸들을 반환합니다.
+/// `skid_image_lock`으로 받은 포인터에 직접 쓰면 호스트 → SKID 복사가 한 번으로 끝납니다.
+#[no_mangle]
+pub extern "C" fn skid_image_create_with_format(width: usize, height: usize, format: u32) -> u64 {
+    let format = match SKIDPixelFormat::from_u32(format) {
+        Some(f) => f,
+        None => return 0,
+    };
+    let image = SKIDImage::new_with_format(width, height, format);
+
+    let handle_id = new_handle_id();
+    IMAGE_HANDLES.write().unwrap().insert(handle_id, Box::new(image));
+    handle_id
+}
+
+/// `skid_image_lock`이 돌려주는 픽셀 메모리 뷰.
+///
+/// `data`는 네이티브 픽셀 형식(행 우선, 리틀 엔디언)의 첫 바이트를 가리키며,
+/// `skid_image_unlock`을 호출하기 전까지 유효합니다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SKIDImageLock {
+    pub data: *mut u8,
+    pub len: usize,
+    pub row_bytes: usize,
+    pub width: usize,
+    pub height: usize,
+    pub format: u32,
+}
+
+struct ImageLockState {
+    image: Box<SKIDImage>,
+    count: u32,
+    free_pending: bool,
+}
+
+// 잠금 중인 이미지. 잠그는 동안 이미지를 `IMAGE_HANDLES`에서 꺼내 여기에 보관하므로,
+// 다른 FFI 함수는 잠긴 핸들을 찾지 못해 실패하고 포인터로 쓰는 중인 픽셀을 동시에 읽거나 바꿀 수 없다.
+static IMAGE_LOCKS: Lazy<Mutex<HashMap<u64, ImageLockState>>> = Lazy::new(Default::default);
+
+/// 이미지의 픽셀 메모리를 잠그고 복사 없이 읽고 쓸 수 있는 포인터를 돌려줍니다.
+///
+/// - 같은 핸들을 여러 번 잠글 수 있으며(같은 포인터), 잠근 횟수만큼 `skid_image_unlock`을 호출해야 합니다.
+/// - 잠금 중에는 다른 함수(resize, get_size 등)가 이 핸들을 유효하지 않은 핸들로 보고 실패합니다.
+/// - 잠금 중 `skid_image_free`는 마지막 unlock 까지 미뤄집니다.
+///
+/// 반환값: 0 성공, -1 null 포인터, -2 유효하지 않은(또는 해제 예정인) 핸들
+#[no_mangle]
+pub extern "C" fn skid_image_lock(handle: u64, out_lock: *mut SKIDImageLock) -> i32 {
+    use std::collections::hash_map::Entry;
+
+    if out_lock.is_null() { return -1; }
+    let mut locks = IMAGE_LOCKS.lock().unwrap();
+    let state = match locks.entry(handle) {
+        Entry::Occupied(entry) => {
+            if entry.get().free_pending {
+                return -2; // 해제 예정인 핸들
+            }
+            entry.into_mut()
+        }
+        Entry::Vacant(entry) => {
+            let Some(image) = IMAGE_HANDLES.write().unwrap().remove(&handle) else {
+                return -2; // Invalid handle
+            };
+            entry.insert(ImageLockState { image, count: 0, free_pending: false })
+        }
+    };
+    // Box 안의 SKIDImage 는 움직이지 않고, 픽셀 Vec 은 잠금 동안 아무도 접근할 수 없으므로 포인터가 유지된다.
+    let image = &mut state.image;
+    let lock = SKIDImageLock {
+        len: image.get_u8_byte_len(),
+        row_bytes: image.get_row_bytes(),
+        width: image.get_size().width,
+        height: image.get_size().height,
+        format: image.get_pixel_format().to_u32(),
+        data: image.get_native_data_mut().as_mut_ptr(),
+    };
+    state.count += 1;
+    unsafe { *out_lock = lock; }
+    0
+}
+
+/// `skid_image_lock`으로 잠근 이미지를 해제합니다. 마지막 unlock 이후 포인터는 더 이상 사용할 수 없고,
+/// 핸들은 다시 다른 함수에서 쓸 수 있습니다.
+///
+/// 반환값: 0 성공, -2 잠겨 있지 않은 핸들
+#[no_mangle]
+pub extern "C" fn skid_image_unlock(handle: u64) -> i32 {
+    let mut locks = IMAGE_LOCKS.lock().unwrap();
+    let state = match locks.get_mut(&handle) {
+        Some(state) => state,
+        None => return -2, // Not locked
+    };
+    state.count -= 1;
+    if state.count == 0 {
+        let state = locks.remove(&handle).unwrap();
+        if !state.free_pending {
+            IMAGE_HANDLES.write().unwrap().insert(handle, state.image);
+        }
+    }
+    0
+}
+
+/// 이미지를 다른 픽셀 형식으로 변환하고 새 핸들을 반환합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_convert_format(handle: u64, format: u32) -> u64 {
+    let format = match SKIDPixelFormat::from_u32(format) {
+        Some(f) => f,
+        None => return 0,
+    };
+    process_image_handle(handle, |image| image.convert_format(format))
+}
+
+/// 이미지를 다른 픽셀 형식으로 변환하고 새 핸들을 반환합니다.
+/// 정수 형식으로 줄일 때 method(0 반올림, 1 Bayer, 2 블루 노이즈, 3 Floyd-Steinberg)로 디더링합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_convert_format_dithered(handle: u64, format: u32, method: u32) -> u64 {
+    use crate::utils::dither::SKIDDitherMethod;
+
+    let (Some(format), Some(method)) = (SKIDPixelFormat::from_u32(format), SKIDDitherMethod::from_u32(method)) else {
+        return 0;
+    };
+    process_image_handle(handle, |image| image.convert_format_dithered(format, method))
+}
+
 /// 이미지 리사이즈 함수 (핸들 기반)
 ///
 /// 락 점유 최소화 패턴:
@@ -209,15 +573,1228 @@ pub extern "C" fn skid_image_resize(
 
     // 2) 락 없이 GPU 작업 수행
     let new_size = SKIDSizeVector2 { width: new_width, height: new_height };
-    let resized_image = processor::resize_image::resize_image::<WgpuRuntime>(
+    let resized_image = processor::resize_image::resize_image_with_sampling::<WgpuRuntime>(
         device,
         &image_clone,
         new_size,
-        None,
+        processor::sampling::SKIDSampling::Bilinear,
     );
 
     // 3) 쓰기 락: 결과 저장 후 즉시 해제
     let new_handle = new_handle_id();
     IMAGE_HANDLES.write().unwrap().insert(new_handle, Box::new(resized_image));
     new_handle
-}
\ No newline at end of file
+}
+
+/// 이미지의 색 공간 코드를 가져옵니다. (0: sRGB, 1: Linear, 2: HSV, 3: HSL, 4: YCbCr, 5: Lab)
+#[no_mangle]
+pub extern "C" fn skid_image_get_color_space(handle: u64, out_color_space: *mut u32) -> i32 {
+    if out_color_space.is_null() { return -1; }
+    let handles = IMAGE_HANDLES.read().unwrap();
+    if let Some(image) = handles.get(&handle) {
+        unsafe { *out_color_space = image.get_color_space().to_u32(); }
+        0
+    } else {
+        -2 // Invalid handle
+    }
+}
+
+/// 픽셀 값은 그대로 두고 색 공간 태그만 변경합니다. (외부에서 linear 데이터를 넘긴 경우 등)
+#[no_mangle]
+pub extern "C" fn skid_image_set_color_space(handle: u64, color_space: u32) -> i32 {
+    let color_space = match SKIDColorSpace::from_u32(color_space) {
+        Some(cs) => cs,
+        None => return -1, // Invalid color space
+    };
+    let mut handles = IMAGE_HANDLES.write().unwrap();
+    if let Some(image) = handles.get_mut(&handle) {
+        image.set_color_space(color_space);
+        0
+    } else {
+        -2 // Invalid handle
+    }
+}
+
+/// 이미지를 다른 색 공간으로 변환하고 새 핸들을 반환합니다. (GPU 커널)
+#[no_mangle]
+pub extern "C" fn skid_image_convert_color_space(handle: u64, target: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+
+    let target = match SKIDColorSpace::from_u32(target) {
+        Some(cs) => cs,
+        None => return 0,
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| {
+        processor::color_space::convert_color_space::<WgpuRuntime>(device, image, target)
+    })
+}
+
+/// 이미지의 알파 모드를 가져옵니다. (0: Straight, 1: Premultiplied)
+#[no_mangle]
+pub extern "C" fn skid_image_get_alpha_mode(handle: u64, out_alpha_mode: *mut u32) -> i32 {
+    if out_alpha_mode.is_null() { return -1; }
+    let handles = IMAGE_HANDLES.read().unwrap();
+    if let Some(image) = handles.get(&handle) {
+        unsafe { *out_alpha_mode = image.get_alpha_mode().to_u32(); }
+        0
+    } else {
+        -2 // Invalid handle
+    }
+}
+
+/// 픽셀 값은 그대로 두고 알파 모드 태그만 변경합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_set_alpha_mode(handle: u64, alpha_mode: u32) -> i32 {
+    let alpha_mode = match SKIDAlphaMode::from_u32(alpha_mode) {
+        Some(mode) => mode,
+        None => return -1, // Invalid alpha mode
+    };
+    let mut handles = IMAGE_HANDLES.write().unwrap();
+    if let Some(image) = handles.get_mut(&handle) {
+        image.set_alpha_mode(alpha_mode);
+        0
+    } else {
+        -2 // Invalid handle
+    }
+}
+
+/// RGB 에 알파를 곱한 프리멀티플라이드 이미지를 새 핸들로 반환합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_premultiply(handle: u64) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| {
+        processor::alpha::premultiply::<WgpuRuntime>(device, image)
+    })
+}
+
+/// 프리멀티플라이드 이미지를 straight 알파로 되돌려 새 핸들로 반환합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_unpremultiply(handle: u64) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| {
+        processor::alpha::unpremultiply::<WgpuRuntime>(device, image)
+    })
+}
+
+/// overlay 를 base 위에 합성하고 새 핸들을 반환합니다.
+/// mode: 0 Normal, 1 Add, 2 Subtract, 3 Multiply, 4 Divide
+#[no_mangle]
+pub extern "C" fn skid_image_blend(
+    base_handle: u64,
+    overlay_handle: u64,
+    mode: u32,
+    opacity: f32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::image_synthesis_action::{blend_images, SKIDBlendMode};
+
+    let mode = match SKIDBlendMode::from_u32(mode) {
+        Some(m) => m,
+        None => return 0,
+    };
+    let overlay = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&overlay_handle) {
+            Some(image) => image.clone(),
+            None => return 0,
+        }
+    };
+    if let Some(base_size) = IMAGE_HANDLES.read().unwrap().get(&base_handle).map(|img| img.get_size()) {
+        if base_size != overlay.get_size() {
+            return 0; // Size mismatch
+        }
+    }
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(base_handle, |base| {
+        blend_images::<WgpuRuntime>(device, base, &overlay, mode, opacity)
+    })
+}
+
+/// 이미지에 kernel_width x kernel_height 컨볼루션 커널(행 우선 가중치)을 적용하고 새 핸들을 반환합니다.
+///
+/// - edge_mode    : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant
+/// - constant     : Constant 모드에서 이미지 밖을 채우는 값
+/// - channel_mask : 비트 i 가 켜진 채널만 필터링 (0b1111 = RGBA 전체)
+///
+/// 분리 가능한 커널은 자동으로 두 번의 1D 패스로 실행됩니다.
+#[no_mangle]
+pub extern "C" fn skid_image_convolve(
+    handle: u64,
+    weights: *const f32,
+    kernel_width: usize,
+    kernel_height: usize,
+    edge_mode: u32,
+    constant: crate::model::skid_color::SKIDColor,
+    channel_mask: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::convolution::{convolve, SKIDConvolutionKernel, SKIDConvolutionOptions, SKIDEdgeMode};
+
+    if weights.is_null() { return 0; }
+    let edge_mode = match SKIDEdgeMode::from_u32(edge_mode) {
+        Some(m) => m,
+        None => return 0,
+    };
+    let weights = unsafe { std::slice::from_raw_parts(weights, kernel_width * kernel_height) }.to_vec();
+    let kernel = match SKIDConvolutionKernel::new(kernel_width, kernel_height, weights) {
+        Ok(k) => k,
+        Err(_) => return 0,
+    };
+    let options = SKIDConvolutionOptions { edge_mode, constant_color: constant, channel_mask };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| convolve::<WgpuRuntime>(device, image, &kernel, &options))
+}
+
+/// 블러 FFI 공통 옵션. edge_mode 가 잘못되면 None.
+fn blur_options(edge_mode: u32, channel_mask: u32) -> Option<crate::processor::convolution::SKIDConvolutionOptions> {
+    use crate::processor::convolution::{SKIDConvolutionOptions, SKIDEdgeMode};
+    Some(SKIDConvolutionOptions {
+        edge_mode: SKIDEdgeMode::from_u32(edge_mode)?,
+        channel_mask,
+        ..Default::default()
+    })
+}
+
+/// 가우시안 블러를 적용하고 새 핸들을 반환합니다.
+///
+/// - mode        : 0 Exact (분리 가능 커널), 1 Approximate (박스 블러 3회, 큰 반경용)
+/// - edge_mode   : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant(투명 검정)
+/// - channel_mask: 비트 i 가 켜진 채널만 블러 (0b1111 = RGBA 전체)
+#[no_mangle]
+pub extern "C" fn skid_image_gaussian_blur(
+    handle: u64,
+    sigma: f32,
+    mode: u32,
+    edge_mode: u32,
+    channel_mask: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::blur::{gaussian_blur, SKIDGaussianMode};
+
+    let (Some(mode), Some(options)) = (SKIDGaussianMode::from_u32(mode), blur_options(edge_mode, channel_mask)) else {
+        return 0;
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| gaussian_blur::<WgpuRuntime>(device, image, sigma, mode, &options))
+}
+
+/// (2 * radius + 1) 크기의 박스 블러를 적용하고 새 핸들을 반환합니다. 반경과 무관하게 일정한 비용이 듭니다.
+#[no_mangle]
+pub extern "C" fn skid_image_box_blur(
+    handle: u64,
+    radius: u32,
+    edge_mode: u32,
+    channel_mask: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+
+    let Some(options) = blur_options(edge_mode, channel_mask) else {
+        return 0;
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| {
+        processor::blur::box_blur::<WgpuRuntime>(device, image, radius, &options)
+    })
+}
+
+/// 가장자리를 보존하는 바이리터럴 필터를 적용하고 새 핸들을 반환합니다.
+///
+/// - spatial_sigma: 거리 가중치의 표준편차 (픽셀)
+/// - range_sigma  : 값 차이 가중치의 표준편차 (0~1 값 기준)
+#[no_mangle]
+pub extern "C" fn skid_image_bilateral_blur(
+    handle: u64,
+    spatial_sigma: f32,
+    range_sigma: f32,
+    edge_mode: u32,
+    channel_mask: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+
+    let Some(options) = blur_options(edge_mode, channel_mask) else {
+        return 0;
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| {
+        processor::blur::bilateral_blur::<WgpuRuntime>(device, image, spatial_sigma, range_sigma, &options)
+    })
+}
+
+/// 하이패스 필터(0.5 + (원본 - 블러) * amount)를 적용하고 새 핸들을 반환합니다.
+///
+/// - radius   : 블러 표준편차 (픽셀)
+/// - threshold: |원본 - 블러| 가 이보다 작으면 0.5
+/// - mode     : 0 채널별, 1 휘도
+#[no_mangle]
+pub extern "C" fn skid_image_high_pass(
+    handle: u64,
+    radius: f32,
+    amount: f32,
+    threshold: f32,
+    mode: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::sharpen::{high_pass, SKIDDetailMode};
+
+    let Some(mode) = SKIDDetailMode::from_u32(mode) else {
+        return 0;
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| high_pass::<WgpuRuntime>(device, image, radius, amount, threshold, mode))
+}
+
+/// 언샤프 마스크(원본 + (원본 - 블러) * amount)를 적용하고 새 핸들을 반환합니다.
+///
+/// - radius   : 블러 표준편차 (픽셀)
+/// - threshold: |원본 - 블러| 가 이보다 작은 픽셀은 그대로 둠
+/// - mode     : 0 채널별, 1 휘도
+#[no_mangle]
+pub extern "C" fn skid_image_unsharp_mask(
+    handle: u64,
+    radius: f32,
+    amount: f32,
+    threshold: f32,
+    mode: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::sharpen::{unsharp_mask, SKIDDetailMode};
+
+    let Some(mode) = SKIDDetailMode::from_u32(mode) else {
+        return 0;
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| unsharp_mask::<WgpuRuntime>(device, image, radius, amount, threshold, mode))
+}
+
+/// 높이맵에서 단일 채널 AO 맵을 생성하고 새 핸들을 반환합니다. (1.0 = 가려지지 않음)
+///
+/// - ray_count / step_count: 방향 수 / 방향당 샘플 수
+/// - radius      : 탐색 반경 (픽셀)
+/// - height_scale: 높이 1.0 에 해당하는 픽셀 높이
+/// - strength    : 차폐량 배율
+/// - tileable    : 가장자리를 반대편으로 이어서 샘플링 (타일링 텍스처)
+#[no_mangle]
+pub extern "C" fn skid_image_make_ao_map(
+    handle: u64,
+    ray_count: u32,
+    step_count: u32,
+    radius: f32,
+    height_scale: f32,
+    strength: f32,
+    tileable: bool,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::make_ao_map::{make_ao_map, SKIDAOMapOptions};
+
+    let options = SKIDAOMapOptions { ray_count, step_count, radius, height_scale, strength, tileable };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| make_ao_map::<WgpuRuntime>(device, image, &options))
+}
+
+/// 높이맵 또는 노멀맵에서 곡률 맵을 생성하고 새 핸들을 반환합니다.
+///
+/// - source      : 0 높이맵, 1 노멀맵 (`skid_generate_normal_map` 결과)
+/// - output      : 0 부호 있는 1채널 (0.5 = 평면), 1 RGBA 분리 (R 볼록, G 오목)
+/// - radius      : 가장 작은 스케일의 샘플 거리 (픽셀), scale_count 개 스케일을 2배씩 늘려 평균
+/// - height_scale: 높이 1.0 에 해당하는 픽셀 높이 (높이맵 입력)
+#[no_mangle]
+pub extern "C" fn skid_image_make_curvature_map(
+    handle: u64,
+    source: u32,
+    output: u32,
+    radius: f32,
+    scale_count: u32,
+    height_scale: f32,
+    strength: f32,
+    tileable: bool,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::make_curvature_map::{make_curvature_map, SKIDCurvatureOptions, SKIDCurvatureOutput, SKIDCurvatureSource};
+
+    let (Some(source), Some(output)) = (SKIDCurvatureSource::from_u32(source), SKIDCurvatureOutput::from_u32(output)) else {
+        return 0;
+    };
+    let options = SKIDCurvatureOptions { source, output, radius, scale_count, height_scale, strength, tileable };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| make_curvature_map::<WgpuRuntime>(device, image, &options))
+}
+
+/// 최대 4개 이미지의 채널을 골라 하나의 이미지로 패킹하고 새 핸들을 반환합니다. (ORM/MRA 텍스처 등)
+///
+/// - handles : 입력 이미지 핸들 배열 (1~4개, 크기가 모두 같아야 함)
+/// - mapping : 출력 R, G, B, A 순서의 `SKIDChannelSource` 4개
+/// - format  : 결과 픽셀 형식 (`SKIDPixelFormat` 값)
+#[no_mangle]
+pub extern "C" fn skid_image_pack_channels(
+    handles: *const u64,
+    handle_count: usize,
+    mapping: *const crate::processor::channel_pack::SKIDChannelSource,
+    format: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::channel_pack::pack_channels;
+
+    if handles.is_null() || mapping.is_null() || handle_count == 0 || handle_count > 4 { return 0; }
+    let Some(format) = SKIDPixelFormat::from_u32(format) else { return 0; };
+    let handle_ids = unsafe { std::slice::from_raw_parts(handles, handle_count) };
+    let mut mapping_array = [crate::processor::channel_pack::SKIDChannelSource::constant(0.0); 4];
+    mapping_array.copy_from_slice(unsafe { std::slice::from_raw_parts(mapping, 4) });
+
+    let sources: Vec<SKIDImage> = {
+        let images = IMAGE_HANDLES.read().unwrap();
+        let Some(sources) = handle_ids.iter().map(|id| images.get(id).map(|img| (**img).clone())).collect() else {
+            return 0;
+        };
+        sources
+    };
+    let source_refs: Vec<&SKIDImage> = sources.iter().collect();
+    let device = &*DEFAULT_WGPU_DEVICE;
+    match pack_channels::<WgpuRuntime>(device, &source_refs, &mapping_array, format) {
+        Ok(result) => {
+            let new_handle = new_handle_id();
+            IMAGE_HANDLES.write().unwrap().insert(new_handle, Box::new(result));
+            new_handle
+        }
+        Err(_) => 0,
+    }
+}
+
+/// 스위즐 문자열(UTF-8, 예: "BGRA", "RRR1")에 따라 채널을 재배치하고 새 핸들을 반환합니다.
+/// R/G/B/A(X/Y/Z/W)는 입력 채널, 0/1 은 상수입니다.
+#[no_mangle]
+pub extern "C" fn skid_image_swizzle(handle: u64, pattern: *const u8, pattern_len: usize) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::channel_pack::swizzle;
+
+    if pattern.is_null() { return 0; }
+    let bytes = unsafe { std::slice::from_raw_parts(pattern, pattern_len) };
+    let Ok(pattern) = std::str::from_utf8(bytes) else { return 0; };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| swizzle::<WgpuRuntime>(device, image, pattern))
+}
+
+/// 타일링 시 이음매가 보이지 않도록 이미지를 가공하고 새 핸들을 반환합니다.
+///
+/// - mode        : 0 OffsetBlend (크기 유지), 1 CrossFade (가로/세로가 blend_width 만큼 줄어듦)
+/// - blend_width : 섞는 폭 (픽셀)
+#[no_mangle]
+pub extern "C" fn skid_image_make_seamless(handle: u64, mode: u32, blend_width: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::seamless::{make_seamless, SKIDSeamlessMode};
+
+    let Some(mode) = SKIDSeamlessMode::from_u32(mode) else { return 0; };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| make_seamless::<WgpuRuntime>(device, image, mode, blend_width))
+}
+
+/// 이음매 확인용으로 이미지를 columns x rows 번 반복해 붙인 미리보기 이미지의 새 핸들을 반환합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_tile_preview(handle: u64, columns: u32, rows: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::seamless::tile_preview;
+
+    if columns == 0 || rows == 0 { return 0; }
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| tile_preview::<WgpuRuntime>(device, image, columns, rows))
+}
+
+/// 절차적 노이즈(0~1) 이미지를 GPU 에서 생성하고 핸들을 반환합니다.
+///
+/// - noise_type     : 0 Value, 1 Perlin, 2 Simplex, 3 Worley
+/// - fractal        : 0 None, 1 fBm, 2 Ridged, 3 Turbulence
+/// - frequency      : 가로 방향 셀 수 (첫 옥타브)
+/// - warp_strength  : 도메인 워핑 세기 (셀 단위, 0 = 워핑 없음), warp_frequency 는 워핑 필드 주파수 배율
+/// - tileable       : true 이면 이음매 없이 반복되는 결과
+#[no_mangle]
+pub extern "C" fn skid_image_generate_noise(
+    width: usize,
+    height: usize,
+    format: u32,
+    noise_type: u32,
+    fractal: u32,
+    seed: u32,
+    frequency: f32,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+    warp_strength: f32,
+    warp_frequency: f32,
+    tileable: bool,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::noise::{generate_noise, SKIDFractalType, SKIDNoiseOptions, SKIDNoiseType};
+
+    let (Some(format), Some(noise_type), Some(fractal)) = (
+        SKIDPixelFormat::from_u32(format),
+        SKIDNoiseType::from_u32(noise_type),
+        SKIDFractalType::from_u32(fractal),
+    ) else {
+        return 0;
+    };
+    if width == 0 || height == 0 { return 0; }
+    let options = SKIDNoiseOptions {
+        noise_type, fractal, seed, frequency, octaves, lacunarity, gain, warp_strength, warp_frequency, tileable,
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    let image = generate_noise::<WgpuRuntime>(device, SKIDSizeVector2::new(width, height), format, &options);
+
+    let handle_id = new_handle_id();
+    IMAGE_HANDLES.write().unwrap().insert(handle_id, Box::new(image));
+    handle_id
+}
+
+/// 테스트 패턴 이미지를 GPU 에서 생성하고 핸들을 반환합니다.
+///
+/// - pattern   : 0 체커보드, 1 선형 그라디언트, 2 원형 그라디언트, 3 SMPTE 컬러 바, 4 UV 격자, 5 단색, 6 견본
+/// - cell_size : 체커보드 칸 / UV 격자 간격 (픽셀)
+/// - angle     : 선형 그라디언트 방향 (라디안)
+/// - color_a, color_b : 패턴 색 (컬러 바/견본은 사용하지 않음, UV 격자는 color_a 로 선을 그림)
+#[no_mangle]
+pub extern "C" fn skid_image_generate_test_pattern(
+    width: usize,
+    height: usize,
+    format: u32,
+    pattern: u32,
+    cell_size: u32,
+    angle: f32,
+    color_a: crate::model::skid_color::SKIDColor,
+    color_b: crate::model::skid_color::SKIDColor,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::test_pattern::{generate_test_pattern, SKIDTestPattern, SKIDTestPatternOptions};
+
+    let (Some(format), Some(pattern)) = (SKIDPixelFormat::from_u32(format), SKIDTestPattern::from_u32(pattern)) else {
+        return 0;
+    };
+    if width == 0 || height == 0 { return 0; }
+    let options = SKIDTestPatternOptions { pattern, cell_size, angle, color_a, color_b };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    let image = generate_test_pattern::<WgpuRuntime>(device, SKIDSizeVector2::new(width, height), format, &options);
+
+    let handle_id = new_handle_id();
+    IMAGE_HANDLES.write().unwrap().insert(handle_id, Box::new(image));
+    handle_id
+}
+
+/// 채널별 최솟값/최댓값/평균/표준편차를 GPU 에서 계산해 `out_stats`에 씁니다.
+/// (0: 성공, -1: null 포인터, -2: 유효하지 않은 핸들)
+#[no_mangle]
+pub extern "C" fn skid_image_compute_statistics(
+    handle: u64,
+    out_stats: *mut crate::processor::statistics::SKIDImageStatistics,
+) -> i32 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::statistics::compute_statistics;
+
+    if out_stats.is_null() { return -1; }
+    let image = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => image.clone(),
+            None => return -2, // Invalid handle
+        }
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    let stats = compute_statistics::<WgpuRuntime>(device, &image);
+    unsafe { *out_stats = stats; }
+    0
+}
+
+/// 채널별 bins 칸 히스토그램을 GPU 에서 계산해 `out_counts`에 씁니다.
+/// 결과는 채널 순서로 이어 붙인 channel_count * bins 개이며, [range_min, range_max] 밖의 값은 양 끝 칸에 셉니다.
+/// (0: 성공, -1: null 포인터, -2: 버퍼가 작음, -3: 유효하지 않은 핸들, -4: 잘못된 bins/범위)
+#[no_mangle]
+pub extern "C" fn skid_image_compute_histogram(
+    handle: u64,
+    bins: u32,
+    range_min: f32,
+    range_max: f32,
+    out_counts: *mut u32,
+    buffer_len: usize,
+) -> i32 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::statistics::compute_histogram;
+
+    if out_counts.is_null() { return -1; }
+    let image = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => image.clone(),
+            None => return -3, // Invalid handle
+        }
+    };
+    if image.get_channel_count() * bins as usize > buffer_len {
+        return -2; // Buffer too small
+    }
+    let device = &*DEFAULT_WGPU_DEVICE;
+    match compute_histogram::<WgpuRuntime>(device, &image, bins, range_min, range_max) {
+        Ok(counts) => {
+            unsafe { std::ptr::copy_nonoverlapping(counts.as_ptr(), out_counts, counts.len()); }
+            0
+        }
+        Err(_) => -4,
+    }
+}
+
+/// 레벨 조정(입력 검정/흰색 점, 감마, 출력 범위)을 적용하고 새 핸들을 반환합니다.
+/// channel_mask 의 비트 i 가 켜진 채널만 바뀝니다 (0b0111 = RGB, 알파 유지).
+#[no_mangle]
+pub extern "C" fn skid_image_levels(
+    handle: u64,
+    in_black: f32,
+    in_white: f32,
+    gamma: f32,
+    out_black: f32,
+    out_white: f32,
+    channel_mask: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::levels::{levels, SKIDLevels};
+
+    let params = SKIDLevels { in_black, in_white, gamma, out_black, out_white };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| levels::<WgpuRuntime>(device, image, &params, channel_mask))
+}
+
+/// 톤 커브를 적용하고 새 핸들을 반환합니다.
+///
+/// - points       : 모든 커브의 제어점 (x, y) 쌍을 rgb, R, G, B, A 커브 순서로 이어 붙인 배열
+/// - point_counts : 커브 5개 각각의 제어점 수 (0 이면 항등 커브)
+#[no_mangle]
+pub extern "C" fn skid_image_curves(handle: u64, points: *const f32, point_counts: *const u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::levels::{curves, SKIDCurve, SKIDCurves};
+
+    if point_counts.is_null() { return 0; }
+    let counts = unsafe { std::slice::from_raw_parts(point_counts, 5) };
+    let total: usize = counts.iter().map(|&c| c as usize).sum();
+    if total > 0 && points.is_null() { return 0; }
+    let values = if total > 0 { unsafe { std::slice::from_raw_parts(points, total * 2) } } else { &[][..] };
+
+    let mut built = Vec::with_capacity(5);
+    let mut offset = 0;
+    for &count in counts {
+        let count = count as usize;
+        if count == 0 {
+            built.push(SKIDCurve::identity());
+            continue;
+        }
+        let pairs = values[offset * 2..(offset + count) * 2].chunks_exact(2).map(|p| (p[0], p[1])).collect();
+        offset += count;
+        match SKIDCurve::new(pairs) {
+            Ok(curve) => built.push(curve),
+            Err(_) => return 0,
+        }
+    }
+    let mut built = built.into_iter();
+    let rgb = built.next().unwrap_or_default();
+    let set = SKIDCurves { rgb, channels: std::array::from_fn(|_| built.next().unwrap_or_default()) };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| curves::<WgpuRuntime>(device, image, &set))
+}
+
+/// 백분위 기반 자동 대비를 적용하고 새 핸들을 반환합니다.
+///
+/// - low_percentile, high_percentile : 검정/흰색으로 자를 누적 비율 (예: 0.005, 0.995)
+/// - per_channel : true 이면 채널마다 따로 범위를 정함 (색 캐스트 보정)
+#[no_mangle]
+pub extern "C" fn skid_image_auto_contrast(
+    handle: u64,
+    low_percentile: f32,
+    high_percentile: f32,
+    per_channel: bool,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::levels::{auto_contrast, SKIDAutoContrastOptions};
+
+    let options = SKIDAutoContrastOptions { low_percentile, high_percentile, per_channel };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| auto_contrast::<WgpuRuntime>(device, image, &options))
+}
+
+/// 3D LUT 를 적용하고 새 핸들을 반환합니다.
+///
+/// - lut_data      : R 이 가장 빠르게 변하는 순서의 RGB 항목 lut_size³ 개 (float 3 * lut_size³ 개)
+/// - interpolation : 0 Trilinear, 1 Tetrahedral
+#[no_mangle]
+pub extern "C" fn skid_image_apply_lut3d(handle: u64, lut_data: *const f32, lut_size: u32, interpolation: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::lut3d::{apply_lut3d, SKIDLut3D, SKIDLutInterpolation};
+
+    if lut_data.is_null() || lut_size < 2 { return 0; }
+    let Some(interpolation) = SKIDLutInterpolation::from_u32(interpolation) else { return 0; };
+    let n = lut_size as usize;
+    let values = unsafe { std::slice::from_raw_parts(lut_data, n * n * n * 3) };
+    let Ok(lut) = SKIDLut3D::from_data(n, values.chunks_exact(3).map(|v| [v[0], v[1], v[2]]).collect()) else {
+        return 0;
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| apply_lut3d::<WgpuRuntime>(device, image, &lut, interpolation))
+}
+
+/// .cube 파일(경로는 UTF-8)을 읽어 3D LUT 를 적용하고 새 핸들을 반환합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_apply_cube_file(handle: u64, path: *const u8, path_len: usize, interpolation: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::lut3d::{apply_lut3d, SKIDLut3D, SKIDLutInterpolation};
+
+    if path.is_null() { return 0; }
+    let bytes = unsafe { std::slice::from_raw_parts(path, path_len) };
+    let Ok(path) = std::str::from_utf8(bytes) else { return 0; };
+    let Some(interpolation) = SKIDLutInterpolation::from_u32(interpolation) else { return 0; };
+    let Ok(lut) = SKIDLut3D::load_cube_file(path) else { return 0; };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| apply_lut3d::<WgpuRuntime>(device, image, &lut, interpolation))
+}
+
+/// 항등 LUT 스트립 이미지((size*size) x size)를 만들고 핸들을 반환합니다.
+/// 이 이미지를 외부 툴에서 그레이딩한 뒤 `skid_image_strip_to_cube_file`로 .cube 로 저장할 수 있습니다.
+#[no_mangle]
+pub extern "C" fn skid_image_create_identity_lut(size: u32, format: u32) -> u64 {
+    use crate::processor::lut3d::identity_lut_image;
+
+    let Some(format) = SKIDPixelFormat::from_u32(format) else { return 0; };
+    let Ok(image) = identity_lut_image(size as usize, format) else { return 0; };
+    let handle_id = new_handle_id();
+    IMAGE_HANDLES.write().unwrap().insert(handle_id, Box::new(image));
+    handle_id
+}
+
+/// 그레이딩된 LUT 스트립 이미지를 .cube 파일로 저장합니다.
+/// (0: 성공, -1: null 포인터, -2: 유효하지 않은 핸들, -4: 잘못된 스트립 크기 또는 쓰기 실패)
+#[no_mangle]
+pub extern "C" fn skid_image_strip_to_cube_file(handle: u64, path: *const u8, path_len: usize) -> i32 {
+    use crate::processor::lut3d::SKIDLut3D;
+
+    if path.is_null() { return -1; }
+    let bytes = unsafe { std::slice::from_raw_parts(path, path_len) };
+    let Ok(path) = std::str::from_utf8(bytes) else { return -4; };
+    let lut = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => SKIDLut3D::from_strip_image(image),
+            None => return -2, // Invalid handle
+        }
+    };
+    match lut.and_then(|lut| lut.save_cube_file(path)) {
+        Ok(()) => 0,
+        Err(_) => -4,
+    }
+}
+
+/// HDR 이미지를 0~1 범위의 sRGB 로 톤 매핑하고 새 핸들을 반환합니다.
+///
+/// - operator    : 0 Reinhard, 1 확장 Reinhard, 2 ACES 근사, 3 AgX, 4 Uncharted2
+/// - exposure    : 노출 보정 (EV)
+/// - white_point : 확장 Reinhard / Uncharted2 의 흰색 점 (0 이하이면 연산자 기본값)
+#[no_mangle]
+pub extern "C" fn skid_image_tone_map(handle: u64, operator: u32, exposure: f32, white_point: f32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::tone_map::{tone_map, SKIDToneMapOperator, SKIDToneMapOptions};
+
+    let Some(operator) = SKIDToneMapOperator::from_u32(operator) else { return 0; };
+    let white_point = (white_point > 0.0).then_some(white_point);
+    let options = SKIDToneMapOptions { operator, exposure, white_point };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| tone_map::<WgpuRuntime>(device, image, &options))
+}
+
+/// 이미지에서 최대 color_count 색 팔레트를 만들어 `out_colors`에 씁니다.
+///
+/// - method : 0 중앙값 분할, 1 Lab k-means
+/// - 반환값 : 실제 색 수 (투명 픽셀이 있으면 0번이 투명 항목), 또는 -1 null 포인터, -2 유효하지 않은 핸들,
+///   -3 버퍼 부족, -4 잘못된 인자
+#[no_mangle]
+pub extern "C" fn skid_image_generate_palette(
+    handle: u64,
+    color_count: u32,
+    method: u32,
+    out_colors: *mut crate::model::skid_color::SKIDColor,
+    capacity: usize,
+) -> i32 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::palette::{generate_palette, SKIDPaletteMethod};
+
+    if out_colors.is_null() { return -1; }
+    let Some(method) = SKIDPaletteMethod::from_u32(method) else { return -4; };
+    let image = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => image.clone(),
+            None => return -2, // Invalid handle
+        }
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    let Ok(palette) = generate_palette::<WgpuRuntime>(device, &image, color_count as usize, method) else {
+        return -4;
+    };
+    if palette.len() > capacity {
+        return -3; // Buffer too small
+    }
+    let out = unsafe { std::slice::from_raw_parts_mut(out_colors, palette.len()) };
+    out.copy_from_slice(palette.get_colors());
+    palette.len() as i32
+}
+
+/// 팔레트 파일(.gpl, JASC .pal, .hex — 경로는 UTF-8)을 읽어 `out_colors`에 씁니다.
+/// (반환값: 색 수, 또는 -1 null 포인터, -3 버퍼 부족, -4 읽기/파싱 실패)
+#[no_mangle]
+pub extern "C" fn skid_palette_load(
+    path: *const u8,
+    path_len: usize,
+    out_colors: *mut crate::model::skid_color::SKIDColor,
+    capacity: usize,
+) -> i32 {
+    use crate::processor::palette::SKIDPalette;
+
+    if path.is_null() || out_colors.is_null() { return -1; }
+    let bytes = unsafe { std::slice::from_raw_parts(path, path_len) };
+    let Ok(path) = std::str::from_utf8(bytes) else { return -4; };
+    let Ok(palette) = SKIDPalette::load(path) else { return -4; };
+    if palette.len() > capacity {
+        return -3; // Buffer too small
+    }
+    let out = unsafe { std::slice::from_raw_parts_mut(out_colors, palette.len()) };
+    out.copy_from_slice(palette.get_colors());
+    palette.len() as i32
+}
+
+/// 이미지를 주어진 팔레트 색으로만 표현한 새 핸들을 반환합니다.
+/// dither : 0 없음, 1 Bayer, 2 블루 노이즈, 3 Floyd-Steinberg
+#[no_mangle]
+pub extern "C" fn skid_image_map_to_palette(
+    handle: u64,
+    colors: *const crate::model::skid_color::SKIDColor,
+    color_count: usize,
+    dither: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::palette::{map_to_palette, SKIDPalette};
+    use crate::utils::dither::SKIDDitherMethod;
+
+    if colors.is_null() { return 0; }
+    let Some(dither) = SKIDDitherMethod::from_u32(dither) else { return 0; };
+    let colors = unsafe { std::slice::from_raw_parts(colors, color_count) };
+    let Ok(palette) = SKIDPalette::new(colors.to_vec()) else { return 0; };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| map_to_palette::<WgpuRuntime>(device, image, &palette, dither))
+}
+
+/// 이미지를 팔레트로 매핑해 인덱스 컬러 PNG 로 저장합니다. (경로는 UTF-8)
+/// (0: 성공, -1: null 포인터, -2: 유효하지 않은 핸들, -4: 잘못된 인자 또는 저장 실패)
+#[cfg(feature = "use_image")]
+#[no_mangle]
+pub extern "C" fn skid_image_export_indexed_png(
+    handle: u64,
+    colors: *const crate::model::skid_color::SKIDColor,
+    color_count: usize,
+    dither: u32,
+    path: *const u8,
+    path_len: usize,
+) -> i32 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::palette::{export_indexed_png, SKIDPalette};
+    use crate::utils::dither::SKIDDitherMethod;
+
+    if colors.is_null() || path.is_null() { return -1; }
+    let bytes = unsafe { std::slice::from_raw_parts(path, path_len) };
+    let Ok(path) = std::str::from_utf8(bytes) else { return -4; };
+    let Some(dither) = SKIDDitherMethod::from_u32(dither) else { return -4; };
+    let colors = unsafe { std::slice::from_raw_parts(colors, color_count) };
+    let Ok(palette) = SKIDPalette::new(colors.to_vec()) else { return -4; };
+    let image = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&handle) {
+            Some(image) => image.clone(),
+            None => return -2, // Invalid handle
+        }
+    };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    match export_indexed_png::<WgpuRuntime>(device, &image, &palette, dither, path) {
+        Ok(()) => 0,
+        Err(_) => -4,
+    }
+}
+
+/// 지정한 보간 방식으로 리사이즈하고 새 핸들을 반환합니다. (sampling: 0 최근접, 1 바이리니어, 2 바이큐빅)
+#[no_mangle]
+pub extern "C" fn skid_image_resize_with_sampling(handle: u64, new_width: usize, new_height: usize, sampling: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::{resize_image::resize_image_with_sampling, sampling::SKIDSampling};
+
+    let Some(sampling) = SKIDSampling::from_u32(sampling) else { return 0; };
+    let new_size = SKIDSizeVector2::new(new_width, new_height);
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| resize_image_with_sampling::<WgpuRuntime>(device, image, new_size, sampling))
+}
+
+/// (x, y)에서 width x height 영역을 잘라낸 새 핸들을 반환합니다. 영역이 이미지를 벗어나면 0.
+#[no_mangle]
+pub extern "C" fn skid_image_crop(handle: u64, x: usize, y: usize, width: usize, height: usize) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::model::skid_image::SKIDRect;
+
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| {
+        processor::geometry::crop::<WgpuRuntime>(device, image, SKIDRect::new(x, y, width, height))
+    })
+}
+
+/// 이미지 둘레에 픽셀을 덧붙인 새 핸들을 반환합니다.
+///
+/// - edge_mode : 0 Clamp (가장자리 연장), 1 Wrap, 2 Mirror, 3 Constant (`fill`로 채움)
+/// - fill      : 이미지와 같은 색 공간/알파 모드의 채움 색
+#[no_mangle]
+pub extern "C" fn skid_image_pad(
+    handle: u64,
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+    edge_mode: u32,
+    fill: crate::model::skid_color::SKIDColor,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::{convolution::SKIDEdgeMode, geometry::{pad, SKIDPadding}};
+
+    let Some(edge_mode) = SKIDEdgeMode::from_u32(edge_mode) else { return 0; };
+    let padding = SKIDPadding::new(left, top, right, bottom);
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| pad::<WgpuRuntime>(device, image, padding, edge_mode, fill))
+}
+
+/// 이미지를 뒤집은 새 핸들을 반환합니다. (axis: 0 좌우, 1 상하)
+#[no_mangle]
+pub extern "C" fn skid_image_flip(handle: u64, axis: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::geometry::{flip_horizontal, flip_vertical};
+
+    let device = &*DEFAULT_WGPU_DEVICE;
+    match axis {
+        0 => process_image_handle(handle, |image| flip_horizontal::<WgpuRuntime>(device, image)),
+        1 => process_image_handle(handle, |image| flip_vertical::<WgpuRuntime>(device, image)),
+        _ => 0,
+    }
+}
+
+/// 행과 열을 바꾼 새 핸들을 반환합니다.
+#[no_mangle]
+pub extern "C" fn skid_image_transpose(handle: u64) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| processor::geometry::transpose::<WgpuRuntime>(device, image))
+}
+
+/// 2x3 아핀 변환(원본 → 출력 좌표, 행 우선 [a, b, tx, c, d, ty])을 적용한 out_width x out_height 새 핸들을 반환합니다.
+///
+/// - sampling  : 0 최근접, 1 바이리니어, 2 바이큐빅
+/// - edge_mode : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant
+/// - constant  : Constant 모드에서 원본 밖을 채우는 값 (linear + 프리멀티플라이드)
+///
+/// 역변환이 없는 행렬이면 0.
+#[no_mangle]
+pub extern "C" fn skid_image_affine_warp(
+    handle: u64,
+    matrix: *const f32,
+    out_width: usize,
+    out_height: usize,
+    sampling: u32,
+    edge_mode: u32,
+    constant: crate::model::skid_color::SKIDColor,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::model::skid_matrix2::SKIDMatrix2x3;
+    use crate::processor::{convolution::SKIDEdgeMode, geometry::{affine_warp, SKIDWarpOptions}, sampling::SKIDSampling};
+
+    if matrix.is_null() { return 0; }
+    let (Some(sampling), Some(edge_mode)) = (SKIDSampling::from_u32(sampling), SKIDEdgeMode::from_u32(edge_mode)) else {
+        return 0;
+    };
+    let m = unsafe { std::slice::from_raw_parts(matrix, 6) };
+    let matrix = SKIDMatrix2x3::from_rows([[m[0], m[1], m[2]], [m[3], m[4], m[5]]]);
+    let options = SKIDWarpOptions { sampling, edge_mode, constant_color: constant };
+    let out_size = SKIDSizeVector2::new(out_width, out_height);
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| affine_warp::<WgpuRuntime>(device, image, &matrix, out_size, &options))
+}
+
+/// 중심 기준으로 radians 만큼(시계 방향) 회전한 새 핸들을 반환합니다.
+/// expand 가 true 이면 회전한 이미지 전체가 들어가도록 크기를 키우며, 밖은 투명 검정으로 채웁니다.
+#[no_mangle]
+pub extern "C" fn skid_image_rotate(handle: u64, radians: f32, expand: bool, sampling: u32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::{geometry::SKIDWarpOptions, image_rotation_action::rotate_image, sampling::SKIDSampling};
+
+    let Some(sampling) = SKIDSampling::from_u32(sampling) else { return 0; };
+    let options = SKIDWarpOptions { sampling, ..Default::default() };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| rotate_image::<WgpuRuntime>(device, image, radians, expand, &options))
+}
+
+/// 90도 단위로 시계 방향 turns 번 회전한 새 핸들을 반환합니다. (음수는 반시계 방향)
+#[no_mangle]
+pub extern "C" fn skid_image_rotate_quarter_turns(handle: u64, turns: i32) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+
+    let device = &*DEFAULT_WGPU_DEVICE;
+    process_image_handle(handle, |image| {
+        processor::image_rotation_action::rotate_quarter_turns::<WgpuRuntime>(device, image, turns)
+    })
+}
+
+/// 호모그래피(원본 → 출력 좌표)로 원근 변환한 out_width x out_height 새 핸들을 반환합니다.
+///
+/// - sampling  : 0 최근접, 1 바이리니어, 2 바이큐빅
+/// - edge_mode : 0 Clamp, 1 Wrap, 2 Mirror, 3 Constant (투명 검정)
+///
+/// 역변환이 없는 행렬이면 0.
+#[no_mangle]
+pub extern "C" fn skid_image_perspective_warp(
+    handle: u64,
+    matrix: crate::model::skid_matrix3::SKIDMatrix3,
+    out_width: usize,
+    out_height: usize,
+    sampling: u32,
+    edge_mode: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::{convolution::SKIDEdgeMode, geometry::SKIDWarpOptions, perspective::perspective_warp, sampling::SKIDSampling};
+
+    let (Some(sampling), Some(edge_mode)) = (SKIDSampling::from_u32(sampling), SKIDEdgeMode::from_u32(edge_mode)) else {
+        return 0;
+    };
+    let options = SKIDWarpOptions { sampling, edge_mode, ..Default::default() };
+    let out_size = SKIDSizeVector2::new(out_width, out_height);
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| perspective_warp::<WgpuRuntime>(device, image, &matrix, out_size, &options))
+}
+
+/// 비스듬히 찍힌 사각형(corners: 왼쪽 위, 오른쪽 위, 오른쪽 아래, 왼쪽 아래 4개)을 정면 직사각형으로 편 새 핸들을 반환합니다.
+/// out_width 나 out_height 가 0 이면 변 길이로 크기를 정합니다. (sampling: 0 최근접, 1 바이리니어, 2 바이큐빅)
+#[no_mangle]
+pub extern "C" fn skid_image_correct_perspective(
+    handle: u64,
+    corners: *const crate::model::skid_vector2::SKIDVector2,
+    out_width: usize,
+    out_height: usize,
+    sampling: u32,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::model::skid_vector2::SKIDVector2;
+    use crate::processor::{convolution::SKIDEdgeMode, geometry::SKIDWarpOptions, perspective::correct_perspective, sampling::SKIDSampling};
+
+    if corners.is_null() { return 0; }
+    let Some(sampling) = SKIDSampling::from_u32(sampling) else { return 0; };
+    let corners: [SKIDVector2; 4] = unsafe { std::ptr::read(corners as *const [SKIDVector2; 4]) };
+    let out_size = (out_width > 0 && out_height > 0).then(|| SKIDSizeVector2::new(out_width, out_height));
+    // 사진 가장자리 바깥은 보통 쓸모가 없으므로 가장자리 픽셀을 늘려 검은 테두리를 피한다.
+    let options = SKIDWarpOptions { sampling, edge_mode: SKIDEdgeMode::Clamp, ..Default::default() };
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle(handle, |image| {
+        correct_perspective::<WgpuRuntime>(device, image, &corners, out_size, &options)
+    })
+}
+
+/// 높이맵(height_handle)의 roi 영역만 노멀맵을 다시 계산해 normal_handle 이미지에 제자리로 씁니다.
+/// roi 의 width 나 height 가 0 이면 전체를 다시 계산합니다. 두 이미지는 크기가 같아야 합니다.
+/// (0: 성공, -2: 유효하지 않은 핸들, -4: 잘못된 인자)
+#[no_mangle]
+pub extern "C" fn skid_image_make_normal_map_region(
+    height_handle: u64,
+    normal_handle: u64,
+    roi: crate::model::skid_image::SKIDRect,
+    x_factor: f32,
+    y_factor: f32,
+) -> i32 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::model::skid_image::SKIDRect;
+    use crate::processor::{convolution::SKIDEdgeMode, region};
+
+    const HALO: usize = 1;
+    // 1) 읽기 락: 높이맵 전체가 아니라 테두리를 붙인 영역만 복사한다.
+    let (rect, input) = {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        let (Some(height_map), Some(normal_map)) = (handles.get(&height_handle), handles.get(&normal_handle)) else {
+            return -2; // Invalid handle
+        };
+        let size = height_map.get_size();
+        if size != normal_map.get_size() {
+            return -4;
+        }
+        let rect = if roi.is_empty() { SKIDRect::new(0, 0, size.width, size.height) } else { roi };
+        match region::extract_with_halo(height_map, rect, HALO, SKIDEdgeMode::Wrap) {
+            Ok(input) => (rect, input),
+            Err(_) => return -4,
+        }
+    };
+
+    // 2) 락 없이 GPU 작업 수행
+    let device = &*DEFAULT_WGPU_DEVICE;
+    let normals = processor::make_normal_map::make_normal_map_base::<WgpuRuntime>(
+        device.clone(),
+        &input,
+        Some(x_factor),
+        Some(y_factor),
+    );
+    let Ok(inner) = normals.view(SKIDRect::new(HALO, HALO, rect.width, rect.height)).map(|v| v.to_image()) else {
+        return -4;
+    };
+
+    // 3) 쓰기 락: 노멀맵의 해당 영역만 덮어쓴다.
+    let mut handles = IMAGE_HANDLES.write().unwrap();
+    let Some(normal_map) = handles.get_mut(&normal_handle) else {
+        return -2; // Invalid handle
+    };
+    match region::write_region::<WgpuRuntime>(device, normal_map, rect, &inner) {
+        Ok(()) => 0,
+        Err(_) => -4,
+    }
+}
+
+/// GPU 메모리에 한 번에 올리지 않고 타일 단위로 노멀맵을 만들어 새 핸들을 반환합니다.
+///
+/// - tile_size      : 타일 한 변의 픽셀 수 (0 이면 기본값 2048)
+/// - parallel_tiles : 동시에 처리할 타일 수 (0 또는 1 이면 순서대로)
+#[no_mangle]
+pub extern "C" fn skid_image_make_normal_map_tiled(
+    handle: u64,
+    x_factor: f32,
+    y_factor: f32,
+    tile_size: usize,
+    parallel_tiles: usize,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::tiling::make_normal_map_tiled;
+
+    let options = tiling_options(tile_size, parallel_tiles);
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle_shared(handle, |image| {
+        make_normal_map_tiled::<WgpuRuntime>(device.clone(), image, Some(x_factor), Some(y_factor), &options)
+    })
+}
+
+/// 타일 단위로 리사이즈해 새 핸들을 반환합니다. 인자는 `skid_image_resize_with_sampling`,
+/// `skid_image_make_normal_map_tiled`와 같습니다.
+#[no_mangle]
+pub extern "C" fn skid_image_resize_tiled(
+    handle: u64,
+    new_width: usize,
+    new_height: usize,
+    sampling: u32,
+    tile_size: usize,
+    parallel_tiles: usize,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::{sampling::SKIDSampling, tiling::resize_image_tiled};
+
+    let Some(sampling) = SKIDSampling::from_u32(sampling) else { return 0; };
+    let new_size = SKIDSizeVector2::new(new_width, new_height);
+    let options = tiling_options(tile_size, parallel_tiles);
+    let device = &*DEFAULT_WGPU_DEVICE;
+    try_process_image_handle_shared(handle, |image| {
+        resize_image_tiled::<WgpuRuntime>(device, image, new_size, sampling, &options)
+    })
+}
+
+/// FFI 인자의 0 을 기본값으로 바꾼 타일 옵션.
+fn tiling_options(tile_size: usize, parallel_tiles: usize) -> crate::processor::tiling::SKIDTilingOptions {
+    let default = crate::processor::tiling::SKIDTilingOptions::default();
+    crate::processor::tiling::SKIDTilingOptions::new(
+        if tile_size == 0 { default.tile_size } else { tile_size },
+        parallel_tiles.max(1),
+    )
+}
+
+/// 여러 이미지를 하나의 텍스처 아틀라스로 패킹하고 아틀라스 핸들을 반환합니다. (실패하면 0)
+///
+/// - handles        : 입력 이미지 핸들 배열
+/// - max_width/max_height : 아틀라스 최대 크기
+/// - padding        : 이미지 둘레에 가장자리 픽셀을 늘여 붙일 폭 (번짐 방지)
+/// - allow_rotation : 90도 회전 배치 허용
+/// - power_of_two   : 아틀라스 가로/세로를 2의 거듭제곱으로
+/// - format         : 아틀라스 픽셀 형식 (`SKIDPixelFormat` 값, 4채널)
+/// - out_entries    : 입력 순서대로 각 이미지의 위치/UV 를 받을 배열 (handle_count 개 이상)
+#[no_mangle]
+pub extern "C" fn skid_image_build_atlas(
+    handles: *const u64,
+    handle_count: usize,
+    max_width: usize,
+    max_height: usize,
+    padding: usize,
+    allow_rotation: bool,
+    power_of_two: bool,
+    format: u32,
+    out_entries: *mut crate::model::skid_atlas::SKIDAtlasEntry,
+    entries_capacity: usize,
+) -> u64 {
+    use cubecl::wgpu::WgpuRuntime;
+    use crate::processor::atlas::{build_atlas, SKIDAtlasOptions};
+
+    if handles.is_null() || out_entries.is_null() || handle_count == 0 || entries_capacity < handle_count {
+        return 0;
+    }
+    let Some(format) = SKIDPixelFormat::from_u32(format) else { return 0; };
+    let handle_ids = unsafe { std::slice::from_raw_parts(handles, handle_count) };
+    let options = SKIDAtlasOptions {
+        max_size: SKIDSizeVector2::new(max_width, max_height),
+        padding,
+        allow_rotation,
+        power_of_two,
+        format,
+    };
+
+    let sources: Vec<SKIDImage> = {
+        let images = IMAGE_HANDLES.read().unwrap();
+        let Some(sources) = handle_ids.iter().map(|id| images.get(id).map(|img| (**img).clone())).collect() else {
+            return 0;
+        };
+        sources
+    };
+    let source_refs: Vec<&SKIDImage> = sources.iter().collect();
+    let device = &*DEFAULT_WGPU_DEVICE;
+    match build_atlas::<WgpuRuntime>(device, &source_refs, &options) {
+        Ok(atlas) => {
+            let out = unsafe { std::slice::from_raw_parts_mut(out_entries, handle_count) };
+            out.copy_from_slice(&atlas.manifest.entries);
+            let new_handle = new_handle_id();
+            IMAGE_HANDLES.write().unwrap().insert(new_handle, Box::new(atlas.image));
+            new_handle
+        }
+        Err(_) => 0,
+    }
+}
+
+/// `skid_image_build_atlas`의 결과를 JSON 매니페스트(UTF-8)로 만들어 `out_bytes`에 씁니다.
+///
+/// - 반환값 : 쓴 바이트 수 (NUL 종료 없음), 또는 -1 null 포인터, -3 버퍼 부족
+#[no_mangle]
+pub extern "C" fn skid_atlas_manifest_to_json(
+    atlas_width: usize,
+    atlas_height: usize,
+    padding: usize,
+    entries: *const crate::model::skid_atlas::SKIDAtlasEntry,
+    entry_count: usize,
+    out_bytes: *mut u8,
+    buffer_len: usize,
+) -> i32 {
+    use crate::model::skid_atlas::SKIDAtlasManifest;
+
+    if entries.is_null() || out_bytes.is_null() { return -1; }
+    let manifest = SKIDAtlasManifest {
+        size: SKIDSizeVector2::new(atlas_width, atlas_height),
+        padding,
+        entries: unsafe { std::slice::from_raw_parts(entries, entry_count) }.to_vec(),
+    };
+    let json = manifest.to_json(None);
+    if json.len() > buffer_len {
+        return -3; // Buffer too small
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(json.as_ptr(), out_bytes, json.len());
+    }
+    json.len() as i32
+}
@@ -13,4 +13,7 @@ pub enum ImageOpts{
     BlendSubtract(SKIDImage,SKIDImage, f32),
     BlendMultiply(SKIDImage,SKIDImage, f32),
     BlendDivide(SKIDImage,SKIDImage, f32),
+    GaussianBlur(SKIDImage, f32),
+    BoxBlur(SKIDImage, u32),
+    BilateralBlur(SKIDImage, f32, f32),
 }
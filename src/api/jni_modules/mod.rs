@@ -1,30 +1,32 @@
-/// JNI(JVM Native Interface) 호환 FFI 모듈.
-///
-/// `jni` 크레이트를 사용하여 타입 안전한 JNI 상호작용을 제공한다.
-/// 기존 C# FFI와 동일한 핸들 기반 IMAGE_HANDLES를 공유하여,
-/// C#과 JVM이 동일 프로세스 내에서 같은 이미지 핸들에 접근할 수 있다.
-///
-/// JNI 함수 명명 규칙: Java_<패키지>_<클래스>_<메서드>
-/// 패키지: dev.lutica.skid → dev_lutica_skid
-/// 클래스: SKIDNative
+//! JNI(JVM Native Interface) 호환 FFI 모듈.
+//!
+//! `jni` 크레이트를 사용하여 타입 안전한 JNI 상호작용을 제공한다.
+//! 기존 C# FFI와 동일한 핸들 기반 IMAGE_HANDLES를 공유하여,
+//! C#과 JVM이 동일 프로세스 내에서 같은 이미지 핸들에 접근할 수 있다.
+//!
+//! JNI 함수 명명 규칙: Java_<패키지>_<클래스>_<메서드>
+//! 패키지: dev.lutica.skid → dev_lutica_skid
+//! 클래스: SKIDNative
 
 #[cfg(feature = "use_jni")]
 mod impl_jni {
     use jni::JNIEnv;
-    use jni::objects::{JClass, JFloatArray};
-    use jni::sys::{jlong, jint, jfloat, jfloatArray};
+    use jni::objects::{JByteArray, JByteBuffer, JClass, JFloatArray, JShortArray};
+    use jni::sys::{jlong, jint, jfloat, jfloatArray, jintArray, jbyteArray, jboolean, jobject, JNI_TRUE, JNI_FALSE};
 
-    use crate::model::skid_color::SKIDColor;
+    use crate::model::skid_pixel_format::SKIDPixelFormat;
+    use crate::model::skid_color_space::SKIDColorSpace;
+    use crate::model::skid_alpha_mode::SKIDAlphaMode;
     use crate::model::skid_image::{SKIDImage, SKIDSizeVector2};
     use crate::processor;
-    use crate::api::ffi_modules::{IMAGE_HANDLES, new_handle_id};
+    use crate::api::ffi_modules::{IMAGE_HANDLES, new_handle_id, process_image_handle};
 
     /// float[] 배열로부터 SKIDImage를 생성하고 핸들(long)을 반환한다.
     ///
     /// Java 시그니처: `native long createFromF32Array(float[] data, int width, int height);`
     #[no_mangle]
     pub extern "system" fn Java_dev_lutica_skid_SKIDNative_createFromF32Array(
-        mut env: JNIEnv,
+        env: JNIEnv,
         _class: JClass,
         data: JFloatArray,
         width: jint,
@@ -45,17 +47,55 @@ mod impl_jni {
             return 0;
         }
 
-        let colors: Vec<SKIDColor> = buf
-            .chunks_exact(4)
-            .map(|c| SKIDColor::from_f32_array([c[0], c[1], c[2], c[3]]))
-            .collect();
+        // RGBA32F 는 f32 배열과 바이트 배치가 같으므로 SKIDColor 를 거치지 않는다.
+        let image = SKIDImage::from_native_bytes(
+            SKIDSizeVector2 {
+                width: width as usize,
+                height: height as usize,
+            },
+            SKIDPixelFormat::RGBA32F,
+            bytemuck::cast_slice(&buf).to_vec(),
+        );
+
+        let handle = new_handle_id();
+        IMAGE_HANDLES.write().unwrap().insert(handle, Box::new(image));
+        handle as jlong
+    }
 
-        let image = SKIDImage::from_1d_data(
+    /// 네이티브 픽셀 형식의 byte[] 로부터 SKIDImage를 생성하고 핸들을 반환한다.
+    /// f32 로 넓히지 않고 `format` 그대로 저장한다.
+    ///
+    /// Java 시그니처: `native long createFromBytes(byte[] data, int width, int height, int format);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_createFromBytes(
+        env: JNIEnv,
+        _class: JClass,
+        data: JByteArray,
+        width: jint,
+        height: jint,
+        format: jint,
+    ) -> jlong {
+        let Some(format) = SKIDPixelFormat::from_u32(format as u32) else {
+            return 0;
+        };
+        let expected = (width as usize) * (height as usize) * format.bytes_per_pixel();
+        let bytes = match env.convert_byte_array(&data) {
+            Ok(b) => b,
+            Err(_) => return 0,
+        };
+        if bytes.len() < expected {
+            return 0;
+        }
+
+        let mut bytes = bytes;
+        bytes.truncate(expected);
+        let image = SKIDImage::from_native_bytes(
             SKIDSizeVector2 {
                 width: width as usize,
                 height: height as usize,
             },
-            colors,
+            format,
+            bytes,
         );
 
         let handle = new_handle_id();
@@ -63,6 +103,77 @@ mod impl_jni {
         handle as jlong
     }
 
+    /// byte[] 버퍼를 행 간격/채널 순서를 지정해 가져옵니다.
+    /// rowStride 는 한 행의 바이트 수 (0 이면 여백 없음), channelOrder 는 `SKIDChannelOrder` 코드입니다.
+    ///
+    /// Java 시그니처: `native long createFromU8Buffer(byte[] data, int width, int height, int rowStride, int format, int channelOrder);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_createFromU8Buffer(
+        env: JNIEnv,
+        _class: JClass,
+        data: JByteArray,
+        width: jint,
+        height: jint,
+        row_stride: jint,
+        format: jint,
+        channel_order: jint,
+    ) -> jlong {
+        if width < 0 || height < 0 || row_stride < 0 {
+            return 0;
+        }
+        let bytes = match env.convert_byte_array(&data) {
+            Ok(b) => b,
+            Err(_) => return 0,
+        };
+        crate::api::ffi_modules::skid_image_create_from_u8_buffer(
+            bytes.as_ptr(),
+            bytes.len(),
+            width as usize,
+            height as usize,
+            row_stride as usize,
+            format as u32,
+            channel_order as u32,
+        ) as jlong
+    }
+
+    /// short[] 버퍼(R16, RGBA16, RGBA16F)를 가져옵니다. short 값은 부호 없는 16비트로 해석합니다.
+    /// rowStride 는 한 행의 **바이트** 수 (0 이면 여백 없음)입니다.
+    ///
+    /// Java 시그니처: `native long createFromU16Buffer(short[] data, int width, int height, int rowStride, int format, int channelOrder);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_createFromU16Buffer(
+        env: JNIEnv,
+        _class: JClass,
+        data: JShortArray,
+        width: jint,
+        height: jint,
+        row_stride: jint,
+        format: jint,
+        channel_order: jint,
+    ) -> jlong {
+        if width < 0 || height < 0 || row_stride < 0 {
+            return 0;
+        }
+        let len = match env.get_array_length(&data) {
+            Ok(l) => l as usize,
+            Err(_) => return 0,
+        };
+        let mut buf = vec![0i16; len];
+        if env.get_short_array_region(&data, 0, &mut buf).is_err() {
+            return 0;
+        }
+        let values: &[u16] = bytemuck::cast_slice(&buf);
+        crate::api::ffi_modules::skid_image_create_from_u16_buffer(
+            values.as_ptr(),
+            values.len(),
+            width as usize,
+            height as usize,
+            row_stride as usize,
+            format as u32,
+            channel_order as u32,
+        ) as jlong
+    }
+
     /// 핸들 해제 (메모리 반환).
     ///
     /// Java 시그니처: `native void free(long handle);`
@@ -73,7 +184,8 @@ mod impl_jni {
         handle: jlong,
     ) {
         if handle > 0 {
-            IMAGE_HANDLES.write().unwrap().remove(&(handle as u64));
+            // 잠금 중인 핸들의 해제 지연 처리를 공유하기 위해 FFI 구현에 위임한다.
+            crate::api::ffi_modules::skid_image_free(handle as u64);
         }
     }
 
@@ -101,7 +213,7 @@ mod impl_jni {
     /// Java 시그니처: `native float[] getDataAsF32Array(long handle);`
     #[no_mangle]
     pub extern "system" fn Java_dev_lutica_skid_SKIDNative_getDataAsF32Array(
-        mut env: JNIEnv,
+        env: JNIEnv,
         _class: JClass,
         handle: jlong,
     ) -> jfloatArray {
@@ -121,6 +233,199 @@ mod impl_jni {
         }
     }
 
+    /// 픽셀 형식 코드를 반환한다. (-1: 유효하지 않은 핸들)
+    ///
+    /// Java 시그니처: `native int getPixelFormat(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_getPixelFormat(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jint {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&(handle as u64)) {
+            Some(image) => image.get_pixel_format().to_u32() as jint,
+            None => -1,
+        }
+    }
+
+    /// 이미지 데이터를 네이티브 픽셀 형식의 byte[] 로 반환한다.
+    ///
+    /// Java 시그니처: `native byte[] getDataAsBytes(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_getDataAsBytes(
+        env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jbyteArray {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        if let Some(image) = handles.get(&(handle as u64)) {
+            match env.byte_array_from_slice(image.get_native_data()) {
+                Ok(arr) => arr.into_raw(),
+                Err(_) => std::ptr::null_mut(),
+            }
+        } else {
+            std::ptr::null_mut()
+        }
+    }
+
+    /// 이미지를 `format` 형식으로 디더링 양자화한 byte[] 를 반환한다. 실패 시 null.
+    ///
+    /// Java 시그니처: `native byte[] getDataAsBytesDithered(long handle, int format, int method);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_getDataAsBytesDithered(
+        env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        format: jint,
+        method: jint,
+    ) -> jbyteArray {
+        use crate::utils::dither::SKIDDitherMethod;
+
+        let (Some(format), Some(method)) =
+            (SKIDPixelFormat::from_u32(format as u32), SKIDDitherMethod::from_u32(method as u32))
+        else {
+            return std::ptr::null_mut();
+        };
+        let quantized = {
+            let handles = IMAGE_HANDLES.read().unwrap();
+            match handles.get(&(handle as u64)) {
+                Some(image) => image.convert_format_dithered(format, method),
+                None => return std::ptr::null_mut(),
+            }
+        };
+        match env.byte_array_from_slice(quantized.get_native_data()) {
+            Ok(arr) => arr.into_raw(),
+            Err(_) => std::ptr::null_mut(),
+        }
+    }
+
+    /// 지정한 픽셀 형식으로 0 으로 채워진 이미지를 만든다. `lockDirect`와 함께 사용한다.
+    ///
+    /// Java 시그니처: `native long createWithFormat(int width, int height, int format);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_createWithFormat(
+        _env: JNIEnv,
+        _class: JClass,
+        width: jint,
+        height: jint,
+        format: jint,
+    ) -> jlong {
+        if width < 0 || height < 0 {
+            return 0;
+        }
+        crate::api::ffi_modules::skid_image_create_with_format(width as usize, height as usize, format as u32) as jlong
+    }
+
+    /// 이미지의 픽셀 메모리를 잠그고 이를 가리키는 DirectByteBuffer 를 반환한다. (복사 없음)
+    /// 버퍼는 `unlock` 호출 전까지만 유효하며, 바이트 순서는 리틀 엔디언이다. 실패 시 null.
+    /// 잠금 중에는 다른 처리 함수가 이 핸들로 실패한다.
+    ///
+    /// Java 시그니처: `native java.nio.ByteBuffer lockDirect(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_lockDirect(
+        mut env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jobject {
+        use crate::api::ffi_modules::{skid_image_lock, skid_image_unlock, SKIDImageLock};
+
+        let mut lock = SKIDImageLock {
+            data: std::ptr::null_mut(),
+            len: 0,
+            row_bytes: 0,
+            width: 0,
+            height: 0,
+            format: 0,
+        };
+        if skid_image_lock(handle as u64, &mut lock) != 0 {
+            return std::ptr::null_mut();
+        }
+        match unsafe { env.new_direct_byte_buffer(lock.data, lock.len) } {
+            Ok(buffer) => buffer.into_raw(),
+            Err(_) => {
+                skid_image_unlock(handle as u64);
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    /// `lockDirect`로 잠근 이미지를 해제한다. (0: 성공, -2: 잠겨 있지 않은 핸들)
+    ///
+    /// Java 시그니처: `native int unlock(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_unlock(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jint {
+        crate::api::ffi_modules::skid_image_unlock(handle as u64)
+    }
+
+    /// DirectByteBuffer 의 내용을 한 번만 복사해 이미지를 만든다.
+    /// rowStride 는 한 행의 바이트 수 (0 이면 여백 없음), channelOrder 는 `SKIDChannelOrder` 코드이다.
+    ///
+    /// Java 시그니처: `native long createFromDirectBuffer(java.nio.ByteBuffer data, int width, int height, int rowStride, int format, int channelOrder);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_createFromDirectBuffer(
+        env: JNIEnv,
+        _class: JClass,
+        data: JByteBuffer,
+        width: jint,
+        height: jint,
+        row_stride: jint,
+        format: jint,
+        channel_order: jint,
+    ) -> jlong {
+        if width < 0 || height < 0 || row_stride < 0 {
+            return 0;
+        }
+        let (ptr, len) = match (env.get_direct_buffer_address(&data), env.get_direct_buffer_capacity(&data)) {
+            (Ok(p), Ok(l)) => (p, l),
+            _ => return 0, // direct 버퍼가 아님
+        };
+        crate::api::ffi_modules::skid_image_create_from_u8_buffer(
+            ptr,
+            len,
+            width as usize,
+            height as usize,
+            row_stride as usize,
+            format as u32,
+            channel_order as u32,
+        ) as jlong
+    }
+
+    /// 이미지 데이터를 네이티브 픽셀 형식 그대로 DirectByteBuffer 에 복사한다.
+    /// (0: 성공, -1: direct 버퍼가 아님, -2: 버퍼가 작음, -3: 유효하지 않은 핸들)
+    ///
+    /// Java 시그니처: `native int copyToDirectBuffer(long handle, java.nio.ByteBuffer out);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_copyToDirectBuffer(
+        env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        out: JByteBuffer,
+    ) -> jint {
+        let (ptr, len) = match (env.get_direct_buffer_address(&out), env.get_direct_buffer_capacity(&out)) {
+            (Ok(p), Ok(l)) => (p, l),
+            _ => return -1,
+        };
+        crate::api::ffi_modules::skid_image_get_data_as_bytes(handle as u64, ptr, len)
+    }
+
+    /// 다른 픽셀 형식으로 변환한 새 핸들을 반환한다.
+    ///
+    /// Java 시그니처: `native long convertFormat(long handle, int format);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_convertFormat(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        format: jint,
+    ) -> jlong {
+        crate::api::ffi_modules::skid_image_convert_format(handle as u64, format as u32) as jlong
+    }
+
     /// 이미지를 리사이즈하고 새 핸들을 반환한다.
     ///
     /// Java 시그니처: `native long resize(long handle, int newWidth, int newHeight);`
@@ -151,11 +456,11 @@ mod impl_jni {
             width: new_width as usize,
             height: new_height as usize,
         };
-        let resized = processor::resize_image::resize_image::<WgpuRuntime>(
+        let resized = processor::resize_image::resize_image_with_sampling::<WgpuRuntime>(
             device,
             &image_clone,
             new_size,
-            None,
+            processor::sampling::SKIDSampling::Bilinear,
         );
 
         // 3) 쓰기 락: 결과 저장
@@ -202,4 +507,294 @@ mod impl_jni {
         IMAGE_HANDLES.write().unwrap().insert(new_handle, Box::new(result));
         new_handle as jlong
     }
+
+    /// 색 공간 코드를 반환한다. (0: sRGB, 1: Linear, 2: HSV, 3: HSL, 4: YCbCr, 5: Lab, -1: 유효하지 않은 핸들)
+    ///
+    /// Java 시그니처: `native int getColorSpace(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_getColorSpace(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jint {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&(handle as u64)) {
+            Some(image) => image.get_color_space().to_u32() as jint,
+            None => -1,
+        }
+    }
+
+    /// 픽셀 값은 그대로 두고 색 공간 태그만 변경한다.
+    ///
+    /// Java 시그니처: `native boolean setColorSpace(long handle, int colorSpace);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_setColorSpace(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        color_space: jint,
+    ) -> jboolean {
+        let Some(color_space) = SKIDColorSpace::from_u32(color_space as u32) else {
+            return JNI_FALSE;
+        };
+        let mut handles = IMAGE_HANDLES.write().unwrap();
+        match handles.get_mut(&(handle as u64)) {
+            Some(image) => {
+                image.set_color_space(color_space);
+                JNI_TRUE
+            }
+            None => JNI_FALSE,
+        }
+    }
+
+    /// 이미지를 다른 색 공간으로 변환하고 새 핸들을 반환한다.
+    ///
+    /// Java 시그니처: `native long convertColorSpace(long handle, int target);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_convertColorSpace(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        target: jint,
+    ) -> jlong {
+        use cubecl::wgpu::WgpuRuntime;
+        use crate::api::ffi_modules::DEFAULT_WGPU_DEVICE;
+
+        let Some(target) = SKIDColorSpace::from_u32(target as u32) else {
+            return 0;
+        };
+        let device = &*DEFAULT_WGPU_DEVICE;
+        process_image_handle(handle as u64, |image| {
+            processor::color_space::convert_color_space::<WgpuRuntime>(device, image, target)
+        }) as jlong
+    }
+
+    /// 알파 모드 코드를 반환한다. (0: Straight, 1: Premultiplied, -1: 유효하지 않은 핸들)
+    ///
+    /// Java 시그니처: `native int getAlphaMode(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_getAlphaMode(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jint {
+        let handles = IMAGE_HANDLES.read().unwrap();
+        match handles.get(&(handle as u64)) {
+            Some(image) => image.get_alpha_mode().to_u32() as jint,
+            None => -1,
+        }
+    }
+
+    /// 픽셀 값은 그대로 두고 알파 모드 태그만 변경한다.
+    ///
+    /// Java 시그니처: `native boolean setAlphaMode(long handle, int alphaMode);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_setAlphaMode(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        alpha_mode: jint,
+    ) -> jboolean {
+        let Some(alpha_mode) = SKIDAlphaMode::from_u32(alpha_mode as u32) else {
+            return JNI_FALSE;
+        };
+        let mut handles = IMAGE_HANDLES.write().unwrap();
+        match handles.get_mut(&(handle as u64)) {
+            Some(image) => {
+                image.set_alpha_mode(alpha_mode);
+                JNI_TRUE
+            }
+            None => JNI_FALSE,
+        }
+    }
+
+    /// 프리멀티플라이드 알파로 변환한 새 핸들을 반환한다.
+    ///
+    /// Java 시그니처: `native long premultiply(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_premultiply(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jlong {
+        use cubecl::wgpu::WgpuRuntime;
+        use crate::api::ffi_modules::DEFAULT_WGPU_DEVICE;
+
+        let device = &*DEFAULT_WGPU_DEVICE;
+        process_image_handle(handle as u64, |image| {
+            processor::alpha::premultiply::<WgpuRuntime>(device, image)
+        }) as jlong
+    }
+
+    /// straight 알파로 되돌린 새 핸들을 반환한다.
+    ///
+    /// Java 시그니처: `native long unpremultiply(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_unpremultiply(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jlong {
+        use cubecl::wgpu::WgpuRuntime;
+        use crate::api::ffi_modules::DEFAULT_WGPU_DEVICE;
+
+        let device = &*DEFAULT_WGPU_DEVICE;
+        process_image_handle(handle as u64, |image| {
+            processor::alpha::unpremultiply::<WgpuRuntime>(device, image)
+        }) as jlong
+    }
+
+    /// overlay 를 base 위에 합성하고 새 핸들을 반환한다.
+    ///
+    /// Java 시그니처: `native long blend(long baseHandle, long overlayHandle, int mode, float opacity);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_blend(
+        _env: JNIEnv,
+        _class: JClass,
+        base_handle: jlong,
+        overlay_handle: jlong,
+        mode: jint,
+        opacity: jfloat,
+    ) -> jlong {
+        crate::api::ffi_modules::skid_image_blend(
+            base_handle as u64,
+            overlay_handle as u64,
+            mode as u32,
+            opacity,
+        ) as jlong
+    }
+
+    /// 가우시안 블러를 적용한 새 핸들을 반환한다. (mode: 0 Exact, 1 Approximate)
+    ///
+    /// Java 시그니처: `native long gaussianBlur(long handle, float sigma, int mode, int edgeMode, int channelMask);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_gaussianBlur(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        sigma: jfloat,
+        mode: jint,
+        edge_mode: jint,
+        channel_mask: jint,
+    ) -> jlong {
+        crate::api::ffi_modules::skid_image_gaussian_blur(
+            handle as u64,
+            sigma,
+            mode as u32,
+            edge_mode as u32,
+            channel_mask as u32,
+        ) as jlong
+    }
+
+    /// 박스 블러를 적용한 새 핸들을 반환한다.
+    ///
+    /// Java 시그니처: `native long boxBlur(long handle, int radius, int edgeMode, int channelMask);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_boxBlur(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        radius: jint,
+        edge_mode: jint,
+        channel_mask: jint,
+    ) -> jlong {
+        if radius < 0 {
+            return 0;
+        }
+        crate::api::ffi_modules::skid_image_box_blur(
+            handle as u64,
+            radius as u32,
+            edge_mode as u32,
+            channel_mask as u32,
+        ) as jlong
+    }
+
+    /// 바이리터럴 필터를 적용한 새 핸들을 반환한다.
+    ///
+    /// Java 시그니처: `native long bilateralBlur(long handle, float spatialSigma, float rangeSigma, int edgeMode, int channelMask);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_bilateralBlur(
+        _env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        spatial_sigma: jfloat,
+        range_sigma: jfloat,
+        edge_mode: jint,
+        channel_mask: jint,
+    ) -> jlong {
+        crate::api::ffi_modules::skid_image_bilateral_blur(
+            handle as u64,
+            spatial_sigma,
+            range_sigma,
+            edge_mode as u32,
+            channel_mask as u32,
+        ) as jlong
+    }
+
+    /// 채널별 통계를 [min×4, max×4, mean×4, stdDev×4] 순서의 float[16] 으로 반환한다.
+    /// 이미지 채널 수를 넘는 항목은 0 이다. (유효하지 않은 핸들이면 null)
+    ///
+    /// Java 시그니처: `native float[] computeStatistics(long handle);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_computeStatistics(
+        env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+    ) -> jfloatArray {
+        let mut stats = crate::processor::statistics::SKIDImageStatistics::default();
+        if crate::api::ffi_modules::skid_image_compute_statistics(handle as u64, &mut stats) != 0 {
+            return std::ptr::null_mut();
+        }
+        let data: Vec<f32> = [stats.min, stats.max, stats.mean, stats.std_dev].concat();
+        let arr = match env.new_float_array(data.len() as i32) {
+            Ok(a) => a,
+            Err(_) => return std::ptr::null_mut(),
+        };
+        if env.set_float_array_region(&arr, 0, &data).is_err() {
+            return std::ptr::null_mut();
+        }
+        arr.into_raw()
+    }
+
+    /// 채널별 bins 칸 히스토그램을 채널 순서로 이어 붙인 int[] 로 반환한다. (실패 시 null)
+    ///
+    /// Java 시그니처: `native int[] computeHistogram(long handle, int bins, float rangeMin, float rangeMax);`
+    #[no_mangle]
+    pub extern "system" fn Java_dev_lutica_skid_SKIDNative_computeHistogram(
+        env: JNIEnv,
+        _class: JClass,
+        handle: jlong,
+        bins: jint,
+        range_min: jfloat,
+        range_max: jfloat,
+    ) -> jintArray {
+        if bins <= 0 {
+            return std::ptr::null_mut();
+        }
+        let channels = match IMAGE_HANDLES.read().unwrap().get(&(handle as u64)) {
+            Some(image) => image.get_channel_count(),
+            None => return std::ptr::null_mut(),
+        };
+        let mut counts = vec![0u32; channels * bins as usize];
+        let status = crate::api::ffi_modules::skid_image_compute_histogram(
+            handle as u64,
+            bins as u32,
+            range_min,
+            range_max,
+            counts.as_mut_ptr(),
+            counts.len(),
+        );
+        if status != 0 {
+            return std::ptr::null_mut();
+        }
+        let data: Vec<i32> = counts.iter().map(|&c| c as i32).collect();
+        let arr = match env.new_int_array(data.len() as i32) {
+            Ok(a) => a,
+            Err(_) => return std::ptr::null_mut(),
+        };
+        if env.set_int_array_region(&arr, 0, &data).is_err() {
+            return std::ptr::null_mut();
+        }
+        arr.into_raw()
+    }
 }
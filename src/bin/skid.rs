@@ -0,0 +1,106 @@
+//! SKID 명령행 도구.
+//!
+//! ```text
+//! skid pattern <이름> <너비> <높이> <출력.png> [--cell N] [--angle 도] [--color-a r,g,b,a] [--color-b r,g,b,a]
+//!              [--bit-depth 8|16] [--dither round|bayer|blue-noise|floyd-steinberg]
+//! ```
+//! 패턴 이름: checkerboard, linear-gradient, radial-gradient, color-bars, uv-grid, solid, swatches
+//!
+//! `--bit-depth`를 생략하면 `--dither round`(기본값)는 8비트, 그 밖의 디더링은 16비트로 저장한다.
+
+use std::process::ExitCode;
+
+use cubecl::wgpu::{WgpuDevice, WgpuRuntime};
+use skid_rust_backend::{
+    model::{skid_color::SKIDColor, skid_image::SKIDSizeVector2, skid_pixel_format::SKIDPixelFormat},
+    processor::test_pattern::{generate_test_pattern, SKIDTestPattern, SKIDTestPatternOptions},
+    utils::{dither::SKIDDitherMethod, file_io::{self, SKIDPngBitDepth}},
+};
+
+const USAGE: &str = "usage: skid pattern <name> <width> <height> <output.png> \
+[--cell N] [--angle DEGREES] [--color-a r,g,b,a] [--color-b r,g,b,a] \
+[--bit-depth 8|16] [--dither round|bayer|blue-noise|floyd-steinberg]\n\
+patterns: checkerboard, linear-gradient, radial-gradient, color-bars, uv-grid, solid, swatches";
+
+fn parse_color(text: &str) -> Result<SKIDColor, String> {
+    let values: Vec<f32> = text
+        .split(',')
+        .map(|v| v.trim().parse::<f32>().map_err(|e| format!("Invalid color component {:?}: {}", v, e)))
+        .collect::<Result<_, _>>()?;
+    match values.as_slice() {
+        [r, g, b] => Ok(SKIDColor::new(*r, *g, *b, 1.0)),
+        [r, g, b, a] => Ok(SKIDColor::new(*r, *g, *b, *a)),
+        _ => Err(format!("Color must have 3 or 4 components, got {:?}", text)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, text: &str) -> Result<T, String> {
+    text.parse::<T>().map_err(|_| format!("Invalid {}: {:?}", name, text))
+}
+
+fn run_pattern(args: &[String]) -> Result<(), String> {
+    let [name, width, height, output, rest @ ..] = args else {
+        return Err(USAGE.to_string());
+    };
+    let pattern = SKIDTestPattern::from_name(name).ok_or_else(|| format!("Unknown pattern {:?}\n{}", name, USAGE))?;
+    let size = SKIDSizeVector2::new(parse_number("width", width)?, parse_number("height", height)?);
+    if size.is_empty() {
+        return Err("Width and height must be positive".to_string());
+    }
+
+    let mut options = SKIDTestPatternOptions { pattern, ..Default::default() };
+    let mut bit_depth = None;
+    let mut dither = SKIDDitherMethod::Round;
+    let mut flags = rest.iter();
+    while let Some(flag) = flags.next() {
+        let value = flags.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+        match flag.as_str() {
+            "--cell" => options.cell_size = parse_number("cell size", value)?,
+            "--angle" => options.angle = parse_number::<f32>("angle", value)?.to_radians(),
+            "--color-a" => options.color_a = parse_color(value)?,
+            "--color-b" => options.color_b = parse_color(value)?,
+            "--bit-depth" => {
+                bit_depth = Some(match value.as_str() {
+                    "8" => SKIDPngBitDepth::Eight,
+                    "16" => SKIDPngBitDepth::Sixteen,
+                    _ => return Err(format!("Bit depth must be 8 or 16, got {:?}", value)),
+                })
+            }
+            "--dither" => {
+                dither = SKIDDitherMethod::from_name(value).ok_or_else(|| format!("Unknown dither method {:?}", value))?
+            }
+            other => return Err(format!("Unknown option {:?}\n{}", other, USAGE)),
+        }
+    }
+
+    let device = WgpuDevice::default();
+    let bit_depth = bit_depth.unwrap_or(if dither == SKIDDitherMethod::Round {
+        SKIDPngBitDepth::Eight
+    } else {
+        SKIDPngBitDepth::Sixteen
+    });
+    // 디더링은 출력보다 정밀한 값이 있어야 의미가 있으므로 그때만 f32 로 생성하고,
+    // 반올림은 출력 비트 깊이와 같은 정밀도로 생성한다.
+    let format = match (dither, bit_depth) {
+        (SKIDDitherMethod::Round, SKIDPngBitDepth::Eight) => SKIDPixelFormat::RGBA8,
+        (SKIDDitherMethod::Round, SKIDPngBitDepth::Sixteen) => SKIDPixelFormat::RGBA16,
+        _ => SKIDPixelFormat::RGBA32F,
+    };
+    let image = generate_test_pattern::<WgpuRuntime>(&device, size, format, &options);
+    file_io::export_to_png_dithered(&image, output, None, bit_depth, dither)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("pattern") => run_pattern(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::model::{skid_image::SKIDImage,skid_color::SKIDColor, skid_vector3::SKIDVector3};
+// C/C# 에서 호출하는 진입점은 넘어온 포인터를 직접 검사하므로 unsafe fn 으로 선언하지 않는다.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use crate::model::{skid_image::SKIDImage,skid_color::SKIDColor, skid_vector2::SKIDVector2, skid_vector3::SKIDVector3, skid_matrix3::SKIDMatrix3};
 pub mod skid_image_ffi;
 
 #[repr(C)]
@@ -13,6 +16,9 @@ pub enum ImageOptsTag {
     BlendSubtract,
     BlendMultiply,
     BlendDivide,
+    GaussianBlur,
+    BoxBlur,
+    BilateralBlur,
 }
 
 #[repr(C)]
@@ -28,11 +34,19 @@ pub struct ImageOptArgs {
     pub value: f32,
 }
 
+#[repr(C)]
+pub struct BilateralArgs {
+    pub img: *mut SKIDImage,
+    pub spatial_sigma: f32,
+    pub range_sigma: f32,
+}
+
 #[repr(C)]
 pub union ImageOptsData {
     pub img: *mut SKIDImage,
     pub blend: std::mem::ManuallyDrop<BlendArgs>,
     pub img_with_value: std::mem::ManuallyDrop<ImageOptArgs>,
+    pub bilateral: std::mem::ManuallyDrop<BilateralArgs>,
 }
 
 #[repr(C)]
@@ -131,3 +145,91 @@ pub extern "C" fn skid_vector3_neg(v: SKIDVector3) -> SKIDVector3 {
     -v // Rust 내부의 Neg 트레잇 사용
 }
 
+#[no_mangle]
+pub extern "C" fn skid_matrix3_identity() -> SKIDMatrix3 {
+    SKIDMatrix3::IDENTITY
+}
+
+/// 행 우선 9개 원소로 행렬을 만든다.
+#[no_mangle]
+pub extern "C" fn skid_matrix3_from_rows(values: *const f32) -> SKIDMatrix3 {
+    assert!(!values.is_null());
+    let v = unsafe { std::slice::from_raw_parts(values, 9) };
+    SKIDMatrix3::from_rows([[v[0], v[1], v[2]], [v[3], v[4], v[5]], [v[6], v[7], v[8]]])
+}
+
+/// 행 우선 9개 원소를 `out_values`에 쓴다.
+#[no_mangle]
+pub extern "C" fn skid_matrix3_to_rows(m: SKIDMatrix3, out_values: *mut f32) {
+    assert!(!out_values.is_null());
+    let rows = m.to_rows();
+    unsafe {
+        std::ptr::copy_nonoverlapping(rows.as_flattened().as_ptr(), out_values, 9);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn skid_matrix3_mul(m1: SKIDMatrix3, m2: SKIDMatrix3) -> SKIDMatrix3 {
+    m1 * m2 // Rust 내부의 Mul 트레잇 사용
+}
+
+#[no_mangle]
+pub extern "C" fn skid_matrix3_mul_vector3(m: SKIDMatrix3, v: SKIDVector3) -> SKIDVector3 {
+    m * v // Rust 내부의 Mul<SKIDVector3> 트레잇 사용
+}
+
+#[no_mangle]
+pub extern "C" fn skid_matrix3_determinant(m: SKIDMatrix3) -> f32 {
+    m.determinant()
+}
+
+#[no_mangle]
+pub extern "C" fn skid_matrix3_transpose(m: SKIDMatrix3) -> SKIDMatrix3 {
+    m.transpose()
+}
+
+/// 역행렬을 `out`에 쓴다. 특이 행렬이면 false 를 반환하고 `out`은 건드리지 않는다.
+#[no_mangle]
+pub extern "C" fn skid_matrix3_inverse(m: SKIDMatrix3, out: *mut SKIDMatrix3) -> bool {
+    assert!(!out.is_null());
+    match m.inverse() {
+        Some(inverse) => {
+            unsafe { *out = inverse; }
+            true
+        }
+        None => false,
+    }
+}
+
+/// 2D 점을 원근 변환한다. w 가 0 이면 false.
+#[no_mangle]
+pub extern "C" fn skid_matrix3_transform_point(m: SKIDMatrix3, p: SKIDVector2, out: *mut SKIDVector2) -> bool {
+    assert!(!out.is_null());
+    match m.transform_point(p) {
+        Some(point) => {
+            unsafe { *out = point; }
+            true
+        }
+        None => false,
+    }
+}
+
+/// 원본 네 꼭짓점(`src`)을 대상 네 꼭짓점(`dst`)으로 보내는 호모그래피를 `out`에 쓴다.
+/// 사각형이 퇴화(세 점이 한 직선 위)했으면 false.
+#[no_mangle]
+pub extern "C" fn skid_matrix3_homography_from_corners(
+    src: *const SKIDVector2,
+    dst: *const SKIDVector2,
+    out: *mut SKIDMatrix3,
+) -> bool {
+    assert!(!src.is_null() && !dst.is_null() && !out.is_null());
+    let src: &[SKIDVector2; 4] = unsafe { &*(src as *const [SKIDVector2; 4]) };
+    let dst: &[SKIDVector2; 4] = unsafe { &*(dst as *const [SKIDVector2; 4]) };
+    match crate::processor::perspective::homography_from_corners(src, dst) {
+        Ok(matrix) => {
+            unsafe { *out = matrix; }
+            true
+        }
+        Err(_) => false,
+    }
+}
@@ -1,11 +1,9 @@
-use crate::model::skid_image::{SKIDImage, SKIDSizeVector2};
+use crate::model::skid_image::SKIDImage;
+/// 빈 이미지를 만들어 소유권을 호출 측에 넘깁니다.
+///
+/// # Safety
+/// 반환된 이미지는 이 라이브러리의 할당자로 만든 것이므로 다른 할당자로 해제하면 안 됩니다.
 #[no_mangle]
 pub unsafe extern "C" fn skid_image_new(width: usize, height: usize) -> Box<SKIDImage> {
     Box::new(SKIDImage::new(width, height))
 }
-
-
-#[no_mangle]
-pub extern "C" fn skid_image_get_size(handle: Box<SKIDImage> ) -> SKIDSizeVector2 {
-    handle.get_size()
-}
\ No newline at end of file
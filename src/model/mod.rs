@@ -1,5 +1,12 @@
 pub mod skid_color;
+pub mod skid_color_space;
+pub mod skid_alpha_mode;
+pub mod skid_pixel_format;
 pub mod skid_vector3;
+pub mod skid_matrix3;
 pub mod skid_vector2;
+pub mod skid_matrix2;
 pub mod skid_image;
+pub mod skid_image_view;
+pub mod skid_atlas;
 pub mod ffi_modules;
\ No newline at end of file
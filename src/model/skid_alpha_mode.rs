@@ -0,0 +1,38 @@
+use crate::model::skid_color::SKIDColor;
+
+/// 이미지의 RGB 채널이 알파와 어떻게 결합되어 있는지 나타내는 태그.
+///
+/// - `Straight`      : RGB 와 A 가 독립적이다. (PNG 등 일반적인 저장 형식)
+/// - `Premultiplied` : RGB 에 A 가 이미 곱해져 있다. 필터링/블렌딩은 이 형식에서 수행해야
+///   투명 픽셀의 색이 가장자리로 번지지 않는다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SKIDAlphaMode {
+    #[default]
+    Straight = 0,
+    Premultiplied = 1,
+}
+
+impl SKIDAlphaMode {
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDAlphaMode::Straight),
+            1 => Some(SKIDAlphaMode::Premultiplied),
+            _ => None,
+        }
+    }
+}
+
+/// CPU 측 프리멀티플라이 (`processor::alpha`의 커널과 같은 계산).
+pub fn premultiply_color(color: SKIDColor) -> SKIDColor {
+    SKIDColor::new(color.r * color.a, color.g * color.a, color.b * color.a, color.a)
+}
+
+/// CPU 측 언프리멀티플라이. 완전 투명 픽셀은 색 정보가 없으므로 검정으로 둔다.
+pub fn unpremultiply_color(color: SKIDColor) -> SKIDColor {
+    let factor = if color.a > 0.0 { 1.0 / color.a } else { 0.0 };
+    SKIDColor::new(color.r * factor, color.g * factor, color.b * factor, color.a)
+}
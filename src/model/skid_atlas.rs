@@ -0,0 +1,105 @@
+use crate::model::{
+    skid_image::{SKIDImage, SKIDRect, SKIDSizeVector2},
+    skid_vector2::SKIDVector2,
+};
+
+/// 아틀라스 안에 놓인 이미지 하나의 위치.
+///
+/// `rect`는 여백(padding)을 뺀 내용 영역이며, `rotated`이면 원본을 시계 방향으로 90도 돌려 넣었으므로
+/// `rect`의 가로/세로가 원본과 바뀌어 있다. UV 는 아틀라스 크기로 나눈 값이고 v 는 이미지 행 방향(아래로 증가)이다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDAtlasEntry {
+    pub rect: SKIDRect,
+    pub rotated: bool,
+    pub uv_min: SKIDVector2,
+    pub uv_max: SKIDVector2,
+}
+
+impl SKIDAtlasEntry {
+    pub fn new(rect: SKIDRect, rotated: bool, atlas_size: SKIDSizeVector2) -> Self {
+        let (w, h) = (atlas_size.width as f32, atlas_size.height as f32);
+        SKIDAtlasEntry {
+            rect,
+            rotated,
+            uv_min: SKIDVector2::new(rect.x as f32 / w, rect.y as f32 / h),
+            uv_max: SKIDVector2::new((rect.x + rect.width) as f32 / w, (rect.y + rect.height) as f32 / h),
+        }
+    }
+}
+
+/// 아틀라스 매니페스트. `entries`는 입력 이미지 순서와 같다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SKIDAtlasManifest {
+    pub size: SKIDSizeVector2,
+    /// 각 이미지 둘레에 가장자리 픽셀을 늘여 붙인 폭 (픽셀)
+    pub padding: usize,
+    pub entries: Vec<SKIDAtlasEntry>,
+}
+
+impl SKIDAtlasManifest {
+    /// JSON 문자열로 만든다. `names`를 주면 각 항목에 "name"을 붙인다. (길이가 모자란 항목은 생략)
+    ///
+    /// ```json
+    /// {"width":512,"height":256,"padding":2,"entries":[
+    ///   {"index":0,"name":"icon","x":2,"y":2,"width":32,"height":32,"rotated":false,"uv":[0.0039,0.0078,0.0664,0.1328]}]}
+    /// ```
+    pub fn to_json(&self, names: Option<&[&str]>) -> String {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let name = names
+                    .and_then(|names| names.get(index))
+                    .map(|name| format!("\"name\":\"{}\",", escape_json(name)))
+                    .unwrap_or_default();
+                let r = entry.rect;
+                format!(
+                    "{{\"index\":{},{}\"x\":{},\"y\":{},\"width\":{},\"height\":{},\"rotated\":{},\"uv\":[{},{},{},{}]}}",
+                    index,
+                    name,
+                    r.x,
+                    r.y,
+                    r.width,
+                    r.height,
+                    entry.rotated,
+                    entry.uv_min.x,
+                    entry.uv_min.y,
+                    entry.uv_max.x,
+                    entry.uv_max.y
+                )
+            })
+            .collect();
+        format!(
+            "{{\"width\":{},\"height\":{},\"padding\":{},\"entries\":[{}]}}",
+            self.size.width,
+            self.size.height,
+            self.padding,
+            entries.join(",")
+        )
+    }
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 완성된 아틀라스 이미지와 매니페스트.
+#[derive(Clone)]
+pub struct SKIDAtlas {
+    pub image: SKIDImage,
+    pub manifest: SKIDAtlasManifest,
+}
@@ -32,13 +32,10 @@ impl SKIDColor {
         }
     }
 
+    /// 채널을 0~1 로 클램프한 뒤 가장 가까운 8비트 값으로 반올림한다.
+    /// 이미지 전체를 디더링하며 줄이려면 `SKIDImage::convert_format_dithered`를 사용할 것.
     pub fn to_u8_array(&self) -> [u8; Self::SKID_U8_ARRAY_BYTE_SIZE_TOTAL] {
-        [
-            (self.r * 255.0) as u8,
-            (self.g * 255.0) as u8,
-            (self.b * 255.0) as u8,
-            (self.a * 255.0) as u8,
-        ]
+        self.to_f32_array().map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
     }
     pub fn from_u8_array(arr: [u8; Self::SKID_U8_ARRAY_BYTE_SIZE_TOTAL]) -> Self {
         SKIDColor {
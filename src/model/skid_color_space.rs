@@ -0,0 +1,67 @@
+/// 이미지 픽셀 값이 어떤 색 공간으로 인코딩되어 있는지 나타내는 태그.
+///
+/// GPU 커널(`processor::color_space`)과 FFI에서 `u32` 코드로 주고받으므로
+/// 판별값(discriminant)은 고정되어 있다.
+///
+/// 채널 배치 (모든 값은 0.0~1.0 범위로 정규화되어 저장된다):
+/// - `Srgb`, `Linear` : R, G, B
+/// - `Hsv`            : H(0~1 = 0°~360°), S, V
+/// - `Hsl`            : H(0~1 = 0°~360°), S, L
+/// - `YCbCr`          : Y, Cb(+0.5 오프셋), Cr(+0.5 오프셋) — BT.709 full range
+/// - `Lab`            : L/100, a/256 + 0.5, b/256 + 0.5 — CIE L*a*b* (D65)
+///
+/// 알파 채널은 어떤 색 공간에서도 변환되지 않는다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SKIDColorSpace {
+    #[default]
+    Srgb = 0,
+    Linear = 1,
+    Hsv = 2,
+    Hsl = 3,
+    YCbCr = 4,
+    Lab = 5,
+}
+
+impl SKIDColorSpace {
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDColorSpace::Srgb),
+            1 => Some(SKIDColorSpace::Linear),
+            2 => Some(SKIDColorSpace::Hsv),
+            3 => Some(SKIDColorSpace::Hsl),
+            4 => Some(SKIDColorSpace::YCbCr),
+            5 => Some(SKIDColorSpace::Lab),
+            _ => None,
+        }
+    }
+    /// 필터링/블렌딩 같은 선형 연산을 그대로 수행해도 되는 공간인지 여부.
+    pub fn is_linear(self) -> bool {
+        self == SKIDColorSpace::Linear
+    }
+    /// R, G, B 채널을 가진 공간인지 여부 (HSV/HSL/YCbCr/Lab 은 false).
+    pub fn is_rgb(self) -> bool {
+        matches!(self, SKIDColorSpace::Srgb | SKIDColorSpace::Linear)
+    }
+}
+
+/// CPU 측 sRGB → linear 전달 함수 (IEC 61966-2-1).
+pub fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// CPU 측 linear → sRGB 전달 함수 (IEC 61966-2-1).
+pub fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
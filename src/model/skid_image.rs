@@ -1,4 +1,5 @@
-use crate::model::{skid_color::SKIDColor};
+use crate::utils::dither::{self, SKIDDitherMethod};
+use crate::model::{skid_alpha_mode::SKIDAlphaMode, skid_color::SKIDColor, skid_color_space::SKIDColorSpace, skid_pixel_format::{SKIDChannelOrder, SKIDPixelFormat}};
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SKIDSizeVector2 {
@@ -16,34 +17,109 @@ impl SKIDSizeVector2 {
         SKIDSizeVector2 { width: tuple.0, height: tuple.1 }
     }
 }
+
+/// 이미지 안의 사각 영역 (픽셀 단위, 왼쪽 위 기준).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SKIDRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+impl SKIDRect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        SKIDRect { x, y, width, height }
+    }
+    pub fn get_size(&self) -> SKIDSizeVector2 {
+        SKIDSizeVector2::new(self.width, self.height)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+    /// 영역이 `size` 크기의 이미지 안에 완전히 들어가는지.
+    pub fn fits_in(&self, size: SKIDSizeVector2) -> bool {
+        self.x.checked_add(self.width).is_some_and(|r| r <= size.width)
+            && self.y.checked_add(self.height).is_some_and(|b| b <= size.height)
+    }
+}
+
+/// 2D 이미지 컨테이너.
+///
+/// 픽셀은 `format`이 정한 네이티브 형식으로, 행 우선(row-major)·행 사이 여백 없이
+/// 하나의 바이트 버퍼에 저장된다. `SKIDColor`를 주고받는 API(`get_pixel`, `get_1d_data` 등)는
+/// 읽을 때 디코딩하고 쓸 때 인코딩한다.
 #[derive(Clone)]
 pub struct SKIDImage {
     width: usize,
     height: usize,
-    data: Vec<Vec<SKIDColor>>,
+    data: Vec<u8>,
     len: usize,
     size:SKIDSizeVector2,
+    format: SKIDPixelFormat,
+    color_space: SKIDColorSpace,
+    alpha_mode: SKIDAlphaMode,
 }
 
 impl SKIDImage {
     /// Creates a new SKIDImage with the specified width and height, initializing all pixels to transparent black.
     pub fn new(width: usize, height: usize) -> Self {
-        let data = vec![
-            vec![SKIDColor::new(0.0, 0.0, 0.0, 0.0); width as usize];
-            height as usize
-        ];
-        SKIDImage { width, height, data, len: (width * height) as usize, size:
-            SKIDSizeVector2 { width, height }
-        }
+        SKIDImage::new_with_format(width, height, SKIDPixelFormat::RGBA32F)
+    }
+    /// 지정한 픽셀 형식으로 모든 채널이 0인 이미지를 만든다.
+    pub fn new_with_format(width: usize, height: usize, format: SKIDPixelFormat) -> Self {
+        SKIDImage::from_native_bytes(
+            SKIDSizeVector2 { width, height },
+            format,
+            vec![0u8; width * height * format.bytes_per_pixel()],
+        )
     }
     pub fn new_with_color(width: usize, height: usize, color: SKIDColor) -> Self {
-        let data = vec![
-            vec![color; width as usize];
-            height as usize
-        ];
-        SKIDImage { width, height, data, len: (width * height) as usize, size:
-            SKIDSizeVector2 { width, height }
+        let mut image = SKIDImage::new(width, height);
+        image.fill(color);
+        image
+    }
+    /// 네이티브 형식 바이트 버퍼를 그대로 소유하는 이미지를 만든다. (복사/변환 없음)
+    pub fn from_native_bytes(size: SKIDSizeVector2, format: SKIDPixelFormat, data: Vec<u8>) -> Self {
+        let width = size.width;
+        let height = size.height;
+        if data.len() != width * height * format.bytes_per_pixel() {
+            panic!("Data length does not match width, height and pixel format");
+        }
+        SKIDImage {
+            width, height, data,
+            len: width * height,
+            size,
+            format,
+            color_space: SKIDColorSpace::default(),
+            alpha_mode: SKIDAlphaMode::default(),
+        }
+    }
+    /// 채널 수가 `channels`인 인터리브 f32 데이터를 `format`으로 인코딩해 이미지를 만든다.
+    /// GPU 커널 출력을 `SKIDColor`로 넓히지 않고 바로 저장할 때 사용한다.
+    pub fn from_f32_data(size: SKIDSizeVector2, format: SKIDPixelFormat, data: &[f32], channels: usize) -> Self {
+        if data.len() != size.width * size.height * channels {
+            panic!("Data length does not match width, height and channel count");
+        }
+        let bpp = format.bytes_per_pixel();
+        let step = format.bytes_per_channel();
+        let mut bytes = vec![0u8; size.width * size.height * bpp];
+        for (pixel, out) in data.chunks_exact(channels).zip(bytes.chunks_exact_mut(bpp)) {
+            if channels == format.channel_count() {
+                for (c, &v) in pixel.iter().enumerate() {
+                    format.encode_channel(v, &mut out[c * step..]);
+                }
+            } else {
+                let color = match channels {
+                    1 => SKIDColor::new(pixel[0], pixel[0], pixel[0], 1.0),
+                    2 => SKIDColor::new(pixel[0], pixel[1], 0.0, 1.0),
+                    3 => SKIDColor::new(pixel[0], pixel[1], pixel[2], 1.0),
+                    _ => SKIDColor::new(pixel[0], pixel[1], pixel[2], pixel[3]),
+                };
+                format.encode_pixel(color, out);
+            }
         }
+        SKIDImage::from_native_bytes(size, format, bytes)
     }
     pub fn from_1d_data(size:SKIDSizeVector2,  data: Vec<SKIDColor>) -> Self {
         let width = size.width;
@@ -51,34 +127,35 @@ impl SKIDImage {
         if data.len() != width * height {
             panic!("Data length does not match width and height");
         }
-        let mut data_2d = Vec::with_capacity(height);
-        for i in 0..height {
-            let start = i * width;
-            let end = start + width;
-            data_2d.push(data[start..end].to_vec());
-        }
-        SKIDImage { 
-            width, height, data: data_2d, 
-            len: (width * height) as usize, 
-            size: SKIDSizeVector2 { width, height } 
+        let format = SKIDPixelFormat::RGBA32F;
+        let mut bytes = vec![0u8; width * height * format.bytes_per_pixel()];
+        for (color, out) in data.iter().zip(bytes.chunks_exact_mut(format.bytes_per_pixel())) {
+            format.encode_pixel(*color, out);
         }
+        SKIDImage::from_native_bytes(size, format, bytes)
     }
     pub fn from_data_size(size:SKIDSizeVector2, data: Vec<Vec<SKIDColor>>) -> Self {
         SKIDImage::from_data(size.width, size.height, data)
     }
     pub fn from_data(width: usize, height: usize, data: Vec<Vec<SKIDColor>>) -> Self {
-        if data.len() != height as usize // Check if data has the correct number of rows
-        || data.iter().any(|row| row.len() != width as usize) {
+        if data.len() != height // Check if data has the correct number of rows
+        || data.iter().any(|row| row.len() != width) {
             panic!("Data length does not match width and height");
         }
-        SKIDImage { width, height, data, 
-            len: (width * height) as usize, 
-            size: SKIDSizeVector2 { width, height } }
+        SKIDImage::from_1d_data(
+            SKIDSizeVector2 { width, height },
+            data.into_iter().flatten().collect(),
+        )
     }
 
-    pub fn get_pixel(&self, x: u32, y: u32) -> Option<&SKIDColor> {
+    fn pixel_offset(&self, x: usize, y: usize) -> usize {
+        (y * self.width + x) * self.format.bytes_per_pixel()
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<SKIDColor> {
         if x < self.width as u32 && y < self.height as u32 {
-            Some(&self.data[y as usize][x as usize])
+            let offset = self.pixel_offset(x as usize, y as usize);
+            Some(self.format.decode_pixel(&self.data[offset..]))
         } else {
             None
         }
@@ -86,7 +163,8 @@ impl SKIDImage {
 
     pub fn set_pixel(&mut self, x: u32, y: u32, color: SKIDColor) -> Option<()> {
         if x < self.width as u32 && y < self.height as u32 {
-            self.data[y as usize][x as usize] = color;
+            let offset = self.pixel_offset(x as usize, y as usize);
+            self.format.encode_pixel(color, &mut self.data[offset..]);
             Some(())
         } else {
             None
@@ -94,20 +172,25 @@ impl SKIDImage {
     }
 
     pub fn fill(&mut self, color: SKIDColor) {
-        for height in &mut self.data {
-            for pixel in height.iter_mut() {
-                *pixel = color;
-            }
+        let bpp = self.format.bytes_per_pixel();
+        if bpp == 0 || self.data.is_empty() {
+            return;
+        }
+        self.format.encode_pixel(color, &mut self.data[..bpp]);
+        let (first, rest) = self.data.split_at_mut(bpp);
+        for pixel in rest.chunks_exact_mut(bpp) {
+            pixel.copy_from_slice(first);
         }
     }
     pub fn to_vec(&self) -> Vec<SKIDColor> {
-        self.data.iter().flat_map(|row| row.iter().cloned()).collect()
+        self.get_1d_data()
     }
-    pub fn len(self) -> usize {
+    pub fn len(&self) -> usize {
         self.len
     }
+    /// 네이티브 형식 기준 전체 바이트 수.
     pub fn get_u8_byte_len(&self) -> usize {
-        self.len * SKIDColor::SKID_U8_ARRAY_BYTE_SIZE_TOTAL
+        self.data.len()
     }
     pub fn get_size(&self) -> SKIDSizeVector2 {
         self.size
@@ -115,29 +198,170 @@ impl SKIDImage {
     pub fn is_empty(&self) -> bool {
         self.width == 0 || self.height == 0
     }
+    pub fn get_pixel_format(&self) -> SKIDPixelFormat {
+        self.format
+    }
+    pub fn get_channel_count(&self) -> usize {
+        self.format.channel_count()
+    }
+    /// 한 행의 바이트 수 (행 사이 여백이 없으므로 width * bytes_per_pixel).
+    pub fn get_row_bytes(&self) -> usize {
+        self.width * self.format.bytes_per_pixel()
+    }
+    /// 픽셀 값이 인코딩된 색 공간. 새로 만든 이미지와 PNG에서 읽은 이미지는 `Srgb`.
+    pub fn get_color_space(&self) -> SKIDColorSpace {
+        self.color_space
+    }
+    /// 픽셀 값은 건드리지 않고 색 공간 태그만 바꾼다. 실제 변환은 `processor::color_space`를 사용할 것.
+    pub fn set_color_space(&mut self, color_space: SKIDColorSpace) {
+        self.color_space = color_space;
+    }
+    pub fn with_color_space(mut self, color_space: SKIDColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+    /// RGB 가 알파로 미리 곱해져 있는지 여부. 새로 만든 이미지와 PNG에서 읽은 이미지는 `Straight`.
+    pub fn get_alpha_mode(&self) -> SKIDAlphaMode {
+        self.alpha_mode
+    }
+    /// 픽셀 값은 건드리지 않고 알파 모드 태그만 바꾼다. 실제 변환은 `processor::alpha`를 사용할 것.
+    pub fn set_alpha_mode(&mut self, alpha_mode: SKIDAlphaMode) {
+        self.alpha_mode = alpha_mode;
+    }
+    pub fn with_alpha_mode(mut self, alpha_mode: SKIDAlphaMode) -> Self {
+        self.alpha_mode = alpha_mode;
+        self
+    }
+    /// 다른 이미지의 색 공간/알파 모드 태그를 그대로 가져온다. (프로세서 출력에 입력 태그를 유지할 때 사용)
+    pub fn with_metadata_from(mut self, other: &SKIDImage) -> Self {
+        self.color_space = other.color_space;
+        self.alpha_mode = other.alpha_mode;
+        self
+    }
+    /// 다른 픽셀 형식으로 변환한 복사본을 만든다. 태그(색 공간/알파 모드)는 유지된다.
+    pub fn convert_format(&self, format: SKIDPixelFormat) -> SKIDImage {
+        if format == self.format {
+            return self.clone();
+        }
+        let src_bpp = self.format.bytes_per_pixel();
+        let dst_bpp = format.bytes_per_pixel();
+        let mut bytes = vec![0u8; self.len * dst_bpp];
+        for (src, dst) in self.data.chunks_exact(src_bpp).zip(bytes.chunks_exact_mut(dst_bpp)) {
+            format.encode_pixel(self.format.decode_pixel(src), dst);
+        }
+        SKIDImage::from_native_bytes(self.size, format, bytes).with_metadata_from(self)
+    }
+    /// 정수 형식으로 줄일 때 `method`로 디더링하는 `convert_format`. 부동소수 형식 대상은 `convert_format`과 같다.
+    /// 매끄러운 그라디언트를 8비트로 내보낼 때 생기는 밴딩을 줄인다.
+    pub fn convert_format_dithered(&self, format: SKIDPixelFormat, method: SKIDDitherMethod) -> SKIDImage {
+        if format.is_float() || (format == self.format && method == SKIDDitherMethod::Round) {
+            return self.convert_format(format);
+        }
+        let channels = format.channel_count();
+        let values: Vec<f32> = self
+            .data
+            .chunks_exact(self.format.bytes_per_pixel())
+            .flat_map(|pixel| {
+                let color = self.format.decode_pixel(pixel).to_f32_array();
+                color.into_iter().take(channels)
+            })
+            .collect();
+        let threads = std::thread::available_parallelism().map_or(4, |n| n.get());
+        let bytes = if format.bytes_per_channel() == 1 {
+            dither::quantize::<u8>(&values, self.width, channels, method, threads)
+        } else {
+            dither::quantize::<u16>(&values, self.width, channels, method, threads)
+                .into_iter()
+                .flat_map(u16::to_le_bytes)
+                .collect()
+        };
+        SKIDImage::from_native_bytes(self.size, format, bytes).with_metadata_from(self)
+    }
+    /// 네이티브 형식의 픽셀 바이트를 복사해 반환한다.
     pub fn to_byte_array(&self) -> Vec<u8> {
-        self.data.iter().flat_map(|row| row.iter().flat_map(|color| color.to_u8_array())).collect()
+        self.data.clone()
     }
-    pub fn get_data(&self) -> &Vec<Vec<SKIDColor>> {
+    /// 네이티브 형식의 픽셀 바이트를 빌려준다.
+    pub fn get_native_data(&self) -> &[u8] {
         &self.data
     }
-    pub fn from_raw_bytes(bytes: &[u8]) -> Self {
-        if bytes.len() % SKIDColor::SKID_U8_ARRAY_BYTE_SIZE_TOTAL != 0 {
-            panic!("Byte array length is not a multiple of SKIDColor::SKID_U8_ARRAY_BYTE_SIZE_TOTAL");
+    /// 네이티브 형식의 픽셀 바이트를 쓰기 가능하게 빌려준다. (길이는 바뀌지 않는다)
+    pub fn get_native_data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+    /// 모든 픽셀을 `SKIDColor`로 디코딩한 2차원 벡터를 반환한다.
+    pub fn get_data(&self) -> Vec<Vec<SKIDColor>> {
+        let colors = self.get_1d_data();
+        if self.width == 0 {
+            return vec![Vec::new(); self.height];
+        }
+        colors.chunks(self.width).map(|row| row.to_vec()).collect()
+    }
+    /// 외부 픽셀 버퍼로부터 이미지를 만든다.
+    ///
+    /// - `size`         : 이미지 크기
+    /// - `format`       : 버퍼의 픽셀 형식 (변환 없이 그대로 저장된다)
+    /// - `row_stride`   : 한 행의 바이트 수 (pitch). 0 이면 `width * bytes_per_pixel`로 간주한다.
+    /// - `channel_order`: 4채널 형식의 채널 순서. 1·2채널 형식에는 `Rgba`만 허용된다.
+    pub fn from_raw_bytes(
+        bytes: &[u8],
+        size: SKIDSizeVector2,
+        format: SKIDPixelFormat,
+        row_stride: usize,
+        channel_order: SKIDChannelOrder,
+    ) -> Result<Self, String> {
+        let bpp = format.bytes_per_pixel();
+        let packed_row = size.width * bpp;
+        let row_stride = if row_stride == 0 { packed_row } else { row_stride };
+        if row_stride < packed_row {
+            return Err(format!("Row stride {} is smaller than a packed row ({} bytes)", row_stride, packed_row));
+        }
+        if format.channel_count() != 4 && channel_order != SKIDChannelOrder::Rgba {
+            return Err(format!("Channel order {:?} requires a 4-channel format, got {:?}", channel_order, format));
         }
-        let pixel_count = bytes.len() / SKIDColor::SKID_U8_ARRAY_BYTE_SIZE_TOTAL;
-        let mut data = Vec::with_capacity(pixel_count);
-        for chunk in bytes.chunks(SKIDColor::SKID_U8_ARRAY_BYTE_SIZE_TOTAL) {
-            data.push(SKIDColor::from_u8_array(chunk.try_into().unwrap()));
+        // 마지막 행은 stride 여백 없이 끝나도 된다.
+        let required = if size.height == 0 { 0 } else { row_stride * (size.height - 1) + packed_row };
+        if bytes.len() < required {
+            return Err(format!("Buffer too small: {} bytes, need {}", bytes.len(), required));
         }
-        let width = (pixel_count as f64).sqrt() as usize;
-        let height = pixel_count / width;
-        SKIDImage::from_1d_data(SKIDSizeVector2 { width, height }, data)
+
+        let mut data = Vec::with_capacity(packed_row * size.height);
+        for y in 0..size.height {
+            data.extend_from_slice(&bytes[y * row_stride..y * row_stride + packed_row]);
+        }
+        if channel_order != SKIDChannelOrder::Rgba {
+            let step = format.bytes_per_channel();
+            let src_idx = channel_order.source_indices();
+            let mut src = vec![0u8; bpp];
+            for pixel in data.chunks_exact_mut(bpp) {
+                src.copy_from_slice(pixel);
+                for (dst_c, &src_c) in src_idx.iter().enumerate() {
+                    pixel[dst_c * step..(dst_c + 1) * step]
+                        .copy_from_slice(&src[src_c * step..(src_c + 1) * step]);
+                }
+            }
+        }
+        Ok(SKIDImage::from_native_bytes(size, format, data))
     }
     pub fn get_1d_data(&self) -> Vec<SKIDColor> {
-        self.data.iter().flat_map(|row| row.iter().cloned()).collect()
+        self.data
+            .chunks_exact(self.format.bytes_per_pixel())
+            .map(|pixel| self.format.decode_pixel(pixel))
+            .collect()
     }
+    /// 모든 픽셀을 RGBA 인터리브 f32 로 디코딩한다. (4채널 GPU 커널 입력용)
     pub fn get_1d_data_as_f32(&self) -> Vec<f32> {
-        self.data.iter().flat_map(|row| row.iter().flat_map(|color| color.to_f32_array())).collect()
+        self.data
+            .chunks_exact(self.format.bytes_per_pixel())
+            .flat_map(|pixel| self.format.decode_pixel(pixel).to_f32_array())
+            .collect()
+    }
+    /// 네이티브 채널 수 그대로 f32 로 디코딩한다. (채널 수에 특화된 GPU 커널 입력용)
+    pub fn get_1d_data_as_f32_channels(&self) -> Vec<f32> {
+        let step = self.format.bytes_per_channel();
+        self.data
+            .chunks_exact(step)
+            .map(|channel| self.format.decode_channel(channel))
+            .collect()
     }
-}
\ No newline at end of file
+}
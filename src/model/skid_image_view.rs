@@ -0,0 +1,269 @@
+use crate::model::{
+    skid_alpha_mode::SKIDAlphaMode,
+    skid_color::SKIDColor,
+    skid_color_space::SKIDColorSpace,
+    skid_image::{SKIDImage, SKIDRect, SKIDSizeVector2},
+    skid_pixel_format::SKIDPixelFormat,
+};
+
+/// 네이티브 바이트 버퍼의 사각 영역을 복사 없이 빌려 보는 읽기 전용 뷰.
+///
+/// `data`는 뷰의 첫 픽셀에서 시작하며, 행 y 는 `data[y * row_stride..]`에서 `width * bytes_per_pixel` 바이트이다.
+/// 원본 이미지의 한 행 전체가 `row_stride`이므로 뷰의 행 사이에는 원본의 나머지 픽셀이 끼어 있다.
+#[derive(Clone, Copy)]
+pub struct SKIDImageView<'a> {
+    data: &'a [u8],
+    size: SKIDSizeVector2,
+    row_stride: usize,
+    format: SKIDPixelFormat,
+    color_space: SKIDColorSpace,
+    alpha_mode: SKIDAlphaMode,
+}
+
+/// `SKIDImageView`의 쓰기 가능 버전. 쓴 값은 원본 버퍼에 바로 반영된다.
+pub struct SKIDImageViewMut<'a> {
+    data: &'a mut [u8],
+    size: SKIDSizeVector2,
+    row_stride: usize,
+    format: SKIDPixelFormat,
+    color_space: SKIDColorSpace,
+    alpha_mode: SKIDAlphaMode,
+}
+
+/// 행 간격 `row_stride`로 `size`개 행을 담으려면 필요한 바이트 수. 마지막 행은 여백 없이 끝나도 된다.
+fn required_bytes(size: SKIDSizeVector2, row_stride: usize, format: SKIDPixelFormat) -> usize {
+    if size.is_empty() {
+        0
+    } else {
+        row_stride * (size.height - 1) + size.width * format.bytes_per_pixel()
+    }
+}
+
+/// 버퍼 안에서 `rect`가 시작하는 바이트 위치. `rect`가 `size` 밖이면 Err.
+fn rect_offset(rect: SKIDRect, size: SKIDSizeVector2, row_stride: usize, format: SKIDPixelFormat) -> Result<usize, String> {
+    if !rect.fits_in(size) {
+        return Err(format!("View rectangle {:?} is outside of the {}x{} image", rect, size.width, size.height));
+    }
+    Ok(rect.y * row_stride + rect.x * format.bytes_per_pixel())
+}
+
+impl<'a> SKIDImageView<'a> {
+    /// 외부 버퍼를 뷰로 감싼다. `row_stride`가 0 이면 `width * bytes_per_pixel`로 간주한다.
+    pub fn from_raw(
+        data: &'a [u8],
+        size: SKIDSizeVector2,
+        format: SKIDPixelFormat,
+        row_stride: usize,
+    ) -> Result<Self, String> {
+        let packed_row = size.width * format.bytes_per_pixel();
+        let row_stride = if row_stride == 0 { packed_row } else { row_stride };
+        if row_stride < packed_row {
+            return Err(format!("Row stride {} is smaller than a packed row ({} bytes)", row_stride, packed_row));
+        }
+        let required = required_bytes(size, row_stride, format);
+        if data.len() < required {
+            return Err(format!("Buffer too small: {} bytes, need {}", data.len(), required));
+        }
+        Ok(SKIDImageView {
+            data,
+            size,
+            row_stride,
+            format,
+            color_space: SKIDColorSpace::default(),
+            alpha_mode: SKIDAlphaMode::default(),
+        })
+    }
+    pub fn get_size(&self) -> SKIDSizeVector2 {
+        self.size
+    }
+    /// 한 행에서 다음 행까지의 바이트 수 (pitch).
+    pub fn get_row_stride(&self) -> usize {
+        self.row_stride
+    }
+    /// 한 행에서 뷰에 속한 바이트 수 (`width * bytes_per_pixel`).
+    pub fn get_row_bytes(&self) -> usize {
+        self.size.width * self.format.bytes_per_pixel()
+    }
+    pub fn get_pixel_format(&self) -> SKIDPixelFormat {
+        self.format
+    }
+    pub fn get_color_space(&self) -> SKIDColorSpace {
+        self.color_space
+    }
+    pub fn get_alpha_mode(&self) -> SKIDAlphaMode {
+        self.alpha_mode
+    }
+    /// 행 y 의 픽셀 바이트.
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        let start = y * self.row_stride;
+        &self.data[start..start + self.get_row_bytes()]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.size.height).map(move |y| self.row(y))
+    }
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<SKIDColor> {
+        if x < self.size.width && y < self.size.height {
+            let bpp = self.format.bytes_per_pixel();
+            Some(self.format.decode_pixel(&self.row(y)[x * bpp..]))
+        } else {
+            None
+        }
+    }
+    /// 이 뷰 안의 `rect`(뷰 기준 좌표)를 다시 빌린다.
+    pub fn sub_view(&self, rect: SKIDRect) -> Result<SKIDImageView<'a>, String> {
+        let offset = rect_offset(rect, self.size, self.row_stride, self.format)?;
+        let size = rect.get_size();
+        let end = offset + required_bytes(size, self.row_stride, self.format);
+        Ok(SKIDImageView { data: &self.data[offset..end.max(offset)], size, ..*self })
+    }
+    /// 뷰 영역을 행 여백 없는 새 이미지로 복사한다. 태그(색 공간/알파 모드)는 유지된다.
+    pub fn to_image(&self) -> SKIDImage {
+        let mut bytes = Vec::with_capacity(self.get_row_bytes() * self.size.height);
+        for row in self.rows() {
+            bytes.extend_from_slice(row);
+        }
+        SKIDImage::from_native_bytes(self.size, self.format, bytes)
+            .with_color_space(self.color_space)
+            .with_alpha_mode(self.alpha_mode)
+    }
+}
+
+impl<'a> SKIDImageViewMut<'a> {
+    /// 외부 버퍼를 쓰기 가능한 뷰로 감싼다. `row_stride`가 0 이면 `width * bytes_per_pixel`로 간주한다.
+    pub fn from_raw(
+        data: &'a mut [u8],
+        size: SKIDSizeVector2,
+        format: SKIDPixelFormat,
+        row_stride: usize,
+    ) -> Result<Self, String> {
+        let view = SKIDImageView::from_raw(data, size, format, row_stride)?;
+        let row_stride = view.row_stride;
+        Ok(SKIDImageViewMut {
+            data,
+            size,
+            row_stride,
+            format,
+            color_space: SKIDColorSpace::default(),
+            alpha_mode: SKIDAlphaMode::default(),
+        })
+    }
+    pub fn get_size(&self) -> SKIDSizeVector2 {
+        self.size
+    }
+    pub fn get_row_stride(&self) -> usize {
+        self.row_stride
+    }
+    pub fn get_row_bytes(&self) -> usize {
+        self.size.width * self.format.bytes_per_pixel()
+    }
+    pub fn get_pixel_format(&self) -> SKIDPixelFormat {
+        self.format
+    }
+    /// 읽기 전용 뷰로 다시 빌린다.
+    pub fn as_view(&self) -> SKIDImageView<'_> {
+        SKIDImageView {
+            data: self.data,
+            size: self.size,
+            row_stride: self.row_stride,
+            format: self.format,
+            color_space: self.color_space,
+            alpha_mode: self.alpha_mode,
+        }
+    }
+    pub fn row_mut(&mut self, y: usize) -> &mut [u8] {
+        let start = y * self.row_stride;
+        let row_bytes = self.get_row_bytes();
+        &mut self.data[start..start + row_bytes]
+    }
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<SKIDColor> {
+        self.as_view().get_pixel(x, y)
+    }
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: SKIDColor) -> Option<()> {
+        if x < self.size.width && y < self.size.height {
+            let bpp = self.format.bytes_per_pixel();
+            let format = self.format;
+            format.encode_pixel(color, &mut self.row_mut(y)[x * bpp..]);
+            Some(())
+        } else {
+            None
+        }
+    }
+    pub fn fill(&mut self, color: SKIDColor) {
+        let bpp = self.format.bytes_per_pixel();
+        let mut pixel = vec![0u8; bpp];
+        self.format.encode_pixel(color, &mut pixel);
+        for y in 0..self.size.height {
+            for dst in self.row_mut(y).chunks_exact_mut(bpp) {
+                dst.copy_from_slice(&pixel);
+            }
+        }
+    }
+    /// 같은 크기의 뷰 내용을 이 영역에 쓴다. 픽셀 형식이 다르면 채널 값을 변환해 쓴다. (태그는 비교하지 않는다)
+    pub fn copy_from(&mut self, source: &SKIDImageView) -> Result<(), String> {
+        if source.get_size() != self.size {
+            return Err(format!("Size mismatch: view is {:?}, source is {:?}", self.size, source.get_size()));
+        }
+        let (src_format, dst_format) = (source.get_pixel_format(), self.format);
+        let (src_bpp, dst_bpp) = (src_format.bytes_per_pixel(), dst_format.bytes_per_pixel());
+        for y in 0..self.size.height {
+            let src_row = source.row(y);
+            let dst_row = self.row_mut(y);
+            if src_format == dst_format {
+                dst_row.copy_from_slice(src_row);
+            } else {
+                for (src, dst) in src_row.chunks_exact(src_bpp).zip(dst_row.chunks_exact_mut(dst_bpp)) {
+                    dst_format.encode_pixel(src_format.decode_pixel(src), dst);
+                }
+            }
+        }
+        Ok(())
+    }
+    pub fn to_image(&self) -> SKIDImage {
+        self.as_view().to_image()
+    }
+}
+
+impl SKIDImage {
+    /// `rect` 영역을 복사 없이 빌린다. 영역이 이미지 밖이면 Err.
+    pub fn view(&self, rect: SKIDRect) -> Result<SKIDImageView<'_>, String> {
+        let (size, format, row_stride) = (self.get_size(), self.get_pixel_format(), self.get_row_bytes());
+        let offset = rect_offset(rect, size, row_stride, format)?;
+        let view_size = rect.get_size();
+        let end = offset + required_bytes(view_size, row_stride, format);
+        Ok(SKIDImageView {
+            data: &self.get_native_data()[offset..end.max(offset)],
+            size: view_size,
+            row_stride,
+            format,
+            color_space: self.get_color_space(),
+            alpha_mode: self.get_alpha_mode(),
+        })
+    }
+    /// `rect` 영역을 쓰기 가능하게 빌린다. 영역이 이미지 밖이면 Err.
+    pub fn view_mut(&mut self, rect: SKIDRect) -> Result<SKIDImageViewMut<'_>, String> {
+        let (size, format, row_stride) = (self.get_size(), self.get_pixel_format(), self.get_row_bytes());
+        let (color_space, alpha_mode) = (self.get_color_space(), self.get_alpha_mode());
+        let offset = rect_offset(rect, size, row_stride, format)?;
+        let view_size = rect.get_size();
+        let end = offset + required_bytes(view_size, row_stride, format);
+        Ok(SKIDImageViewMut {
+            data: &mut self.get_native_data_mut()[offset..end.max(offset)],
+            size: view_size,
+            row_stride,
+            format,
+            color_space,
+            alpha_mode,
+        })
+    }
+    /// 이미지 전체를 뷰로 빌린다.
+    pub fn as_view(&self) -> SKIDImageView<'_> {
+        SKIDImageView {
+            data: self.get_native_data(),
+            size: self.get_size(),
+            row_stride: self.get_row_bytes(),
+            format: self.get_pixel_format(),
+            color_space: self.get_color_space(),
+            alpha_mode: self.get_alpha_mode(),
+        }
+    }
+}
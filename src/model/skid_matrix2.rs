@@ -0,0 +1,167 @@
+use crate::model::skid_vector2::SKIDVector2;
+
+/// 2x2 선형 변환 행렬. 두 열 벡터(`x_axis`, `y_axis`)로 저장한다.
+///
+/// `M * v = x_axis * v.x + y_axis * v.y`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDMatrix2 {
+    pub x_axis: SKIDVector2,
+    pub y_axis: SKIDVector2,
+}
+
+impl SKIDMatrix2 {
+    pub const IDENTITY: SKIDMatrix2 = SKIDMatrix2 {
+        x_axis: SKIDVector2 { x: 1.0, y: 0.0 },
+        y_axis: SKIDVector2 { x: 0.0, y: 1.0 },
+    };
+
+    pub fn new(x_axis: SKIDVector2, y_axis: SKIDVector2) -> Self {
+        SKIDMatrix2 { x_axis, y_axis }
+    }
+    /// 행 우선 원소 `[[a, b], [c, d]]`로 만든다.
+    pub fn from_rows(rows: [[f32; 2]; 2]) -> Self {
+        SKIDMatrix2 {
+            x_axis: SKIDVector2::new(rows[0][0], rows[1][0]),
+            y_axis: SKIDVector2::new(rows[0][1], rows[1][1]),
+        }
+    }
+    pub fn to_rows(&self) -> [[f32; 2]; 2] {
+        [[self.x_axis.x, self.y_axis.x], [self.x_axis.y, self.y_axis.y]]
+    }
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        SKIDMatrix2::from_rows([[sx, 0.0], [0.0, sy]])
+    }
+    /// 회전 행렬. 이미지 좌표계(y 가 아래로 증가)에서 양의 각도는 시계 방향으로 보인다.
+    pub fn rotation(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        SKIDMatrix2::from_rows([[cos, -sin], [sin, cos]])
+    }
+    /// x' = x + shear_x * y, y' = shear_y * x + y
+    pub fn shear(shear_x: f32, shear_y: f32) -> Self {
+        SKIDMatrix2::from_rows([[1.0, shear_x], [shear_y, 1.0]])
+    }
+    pub fn determinant(&self) -> f32 {
+        self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y
+    }
+    pub fn transpose(&self) -> Self {
+        let [[a, b], [c, d]] = self.to_rows();
+        SKIDMatrix2::from_rows([[a, c], [b, d]])
+    }
+    /// 역행렬. 특이 행렬(행렬식이 0 에 가까움)이면 None.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if !det.is_finite() || det.abs() <= f32::EPSILON * f32::EPSILON {
+            return None;
+        }
+        let [[a, b], [c, d]] = self.to_rows();
+        Some(SKIDMatrix2::from_rows([[d / det, -b / det], [-c / det, a / det]]))
+    }
+    pub fn transform(&self, v: SKIDVector2) -> SKIDVector2 {
+        self.x_axis * v.x + self.y_axis * v.y
+    }
+}
+
+impl Default for SKIDMatrix2 {
+    fn default() -> Self {
+        SKIDMatrix2::IDENTITY
+    }
+}
+
+impl std::ops::Mul for SKIDMatrix2 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        SKIDMatrix2 {
+            x_axis: self.transform(other.x_axis),
+            y_axis: self.transform(other.y_axis),
+        }
+    }
+}
+
+impl std::ops::Mul<SKIDVector2> for SKIDMatrix2 {
+    type Output = SKIDVector2;
+
+    fn mul(self, v: SKIDVector2) -> SKIDVector2 {
+        self.transform(v)
+    }
+}
+
+/// 2x3 아핀 변환 행렬 (선형 부분 + 이동). 점 p 를 `linear * p + translation`으로 옮긴다.
+///
+/// 이미지에 쓸 때 좌표는 픽셀 단위이며, 픽셀 (i, j)의 중심은 (i + 0.5, j + 0.5)이다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDMatrix2x3 {
+    pub linear: SKIDMatrix2,
+    pub translation: SKIDVector2,
+}
+
+impl SKIDMatrix2x3 {
+    pub const IDENTITY: SKIDMatrix2x3 = SKIDMatrix2x3 {
+        linear: SKIDMatrix2::IDENTITY,
+        translation: SKIDVector2 { x: 0.0, y: 0.0 },
+    };
+
+    pub fn new(linear: SKIDMatrix2, translation: SKIDVector2) -> Self {
+        SKIDMatrix2x3 { linear, translation }
+    }
+    /// 행 우선 원소 `[[a, b, tx], [c, d, ty]]`로 만든다.
+    pub fn from_rows(rows: [[f32; 3]; 2]) -> Self {
+        SKIDMatrix2x3 {
+            linear: SKIDMatrix2::from_rows([[rows[0][0], rows[0][1]], [rows[1][0], rows[1][1]]]),
+            translation: SKIDVector2::new(rows[0][2], rows[1][2]),
+        }
+    }
+    pub fn to_rows(&self) -> [[f32; 3]; 2] {
+        let [[a, b], [c, d]] = self.linear.to_rows();
+        [[a, b, self.translation.x], [c, d, self.translation.y]]
+    }
+    pub fn translation(offset: SKIDVector2) -> Self {
+        SKIDMatrix2x3::new(SKIDMatrix2::IDENTITY, offset)
+    }
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        SKIDMatrix2x3::new(SKIDMatrix2::scale(sx, sy), SKIDVector2::new(0.0, 0.0))
+    }
+    pub fn rotation(radians: f32) -> Self {
+        SKIDMatrix2x3::new(SKIDMatrix2::rotation(radians), SKIDVector2::new(0.0, 0.0))
+    }
+    /// `center`를 고정점으로 하는 회전.
+    pub fn rotation_about(center: SKIDVector2, radians: f32) -> Self {
+        SKIDMatrix2x3::translation(center) * SKIDMatrix2x3::rotation(radians) * SKIDMatrix2x3::translation(-center)
+    }
+    pub fn shear(shear_x: f32, shear_y: f32) -> Self {
+        SKIDMatrix2x3::new(SKIDMatrix2::shear(shear_x, shear_y), SKIDVector2::new(0.0, 0.0))
+    }
+    /// `self`를 적용한 뒤 `next`를 적용하는 변환 (`next * self`).
+    pub fn then(&self, next: &SKIDMatrix2x3) -> Self {
+        *next * *self
+    }
+    /// 역변환. 선형 부분이 특이 행렬이면 None.
+    pub fn inverse(&self) -> Option<Self> {
+        let linear = self.linear.inverse()?;
+        Some(SKIDMatrix2x3::new(linear, -linear.transform(self.translation)))
+    }
+    pub fn transform_point(&self, p: SKIDVector2) -> SKIDVector2 {
+        self.linear.transform(p) + self.translation
+    }
+    /// 방향 벡터를 옮긴다. (이동 성분은 적용하지 않는다)
+    pub fn transform_vector(&self, v: SKIDVector2) -> SKIDVector2 {
+        self.linear.transform(v)
+    }
+}
+
+impl Default for SKIDMatrix2x3 {
+    fn default() -> Self {
+        SKIDMatrix2x3::IDENTITY
+    }
+}
+
+impl std::ops::Mul for SKIDMatrix2x3 {
+    type Output = Self;
+
+    /// `self * other`: `other`를 먼저 적용한 뒤 `self`를 적용한다.
+    fn mul(self, other: Self) -> Self {
+        SKIDMatrix2x3::new(self.linear * other.linear, self.transform_point(other.translation))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::model::{skid_matrix2::SKIDMatrix2x3, skid_vector2::SKIDVector2, skid_vector3::SKIDVector3};
+
+/// 3x3 행렬. 세 열 벡터(`x_axis`, `y_axis`, `z_axis`)로 저장한다.
+///
+/// 2D 동차 좌표 (x, y, 1)에 적용하면 호모그래피(원근 변환)가 된다. `SKIDMatrix2x3`의 상위 호환이다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDMatrix3 {
+    pub x_axis: SKIDVector3,
+    pub y_axis: SKIDVector3,
+    pub z_axis: SKIDVector3,
+}
+
+impl SKIDMatrix3 {
+    pub const IDENTITY: SKIDMatrix3 = SKIDMatrix3 {
+        x_axis: SKIDVector3 { x: 1.0, y: 0.0, z: 0.0 },
+        y_axis: SKIDVector3 { x: 0.0, y: 1.0, z: 0.0 },
+        z_axis: SKIDVector3 { x: 0.0, y: 0.0, z: 1.0 },
+    };
+
+    pub fn new(x_axis: SKIDVector3, y_axis: SKIDVector3, z_axis: SKIDVector3) -> Self {
+        SKIDMatrix3 { x_axis, y_axis, z_axis }
+    }
+    /// 행 우선 원소로 만든다.
+    pub fn from_rows(rows: [[f32; 3]; 3]) -> Self {
+        SKIDMatrix3 {
+            x_axis: SKIDVector3::new(rows[0][0], rows[1][0], rows[2][0]),
+            y_axis: SKIDVector3::new(rows[0][1], rows[1][1], rows[2][1]),
+            z_axis: SKIDVector3::new(rows[0][2], rows[1][2], rows[2][2]),
+        }
+    }
+    pub fn to_rows(&self) -> [[f32; 3]; 3] {
+        let (x, y, z) = (self.x_axis, self.y_axis, self.z_axis);
+        [[x.x, y.x, z.x], [x.y, y.y, z.y], [x.z, y.z, z.z]]
+    }
+    pub fn determinant(&self) -> f32 {
+        self.x_axis.dot(&self.y_axis.cross(&self.z_axis))
+    }
+    pub fn transpose(&self) -> Self {
+        let (x, y, z) = (self.x_axis, self.y_axis, self.z_axis);
+        SKIDMatrix3::from_rows([[x.x, x.y, x.z], [y.x, y.y, y.z], [z.x, z.y, z.z]])
+    }
+    /// 역행렬. 특이 행렬이면 None.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if !det.is_finite() || det == 0.0 {
+            return None;
+        }
+        // 역행렬의 각 행은 나머지 두 열의 외적을 행렬식으로 나눈 것이다.
+        let r0 = self.y_axis.cross(&self.z_axis) / det;
+        let r1 = self.z_axis.cross(&self.x_axis) / det;
+        let r2 = self.x_axis.cross(&self.y_axis) / det;
+        Some(SKIDMatrix3::from_rows([[r0.x, r0.y, r0.z], [r1.x, r1.y, r1.z], [r2.x, r2.y, r2.z]]))
+    }
+    pub fn transform(&self, v: SKIDVector3) -> SKIDVector3 {
+        self.x_axis * v.x + self.y_axis * v.y + self.z_axis * v.z
+    }
+    /// 2D 점 (x, y, 1)을 변환한 뒤 w 로 나눈다. 점이 무한원점으로 가면(w = 0) None.
+    pub fn transform_point(&self, p: SKIDVector2) -> Option<SKIDVector2> {
+        let h = self.transform(SKIDVector3::new(p.x, p.y, 1.0));
+        if h.z == 0.0 {
+            return None;
+        }
+        Some(SKIDVector2::new(h.x / h.z, h.y / h.z))
+    }
+    /// 오른쪽 아래 원소가 1 이 되도록 전체를 나눈다. (호모그래피는 배율과 무관하다) 0 이면 그대로 둔다.
+    pub fn normalized(&self) -> Self {
+        let w = self.z_axis.z;
+        if w == 0.0 {
+            return *self;
+        }
+        SKIDMatrix3::new(self.x_axis / w, self.y_axis / w, self.z_axis / w)
+    }
+}
+
+impl Default for SKIDMatrix3 {
+    fn default() -> Self {
+        SKIDMatrix3::IDENTITY
+    }
+}
+
+impl From<SKIDMatrix2x3> for SKIDMatrix3 {
+    fn from(m: SKIDMatrix2x3) -> Self {
+        let [r0, r1] = m.to_rows();
+        SKIDMatrix3::from_rows([r0, r1, [0.0, 0.0, 1.0]])
+    }
+}
+
+impl std::ops::Mul for SKIDMatrix3 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        SKIDMatrix3 {
+            x_axis: self.transform(other.x_axis),
+            y_axis: self.transform(other.y_axis),
+            z_axis: self.transform(other.z_axis),
+        }
+    }
+}
+
+impl std::ops::Mul<SKIDVector3> for SKIDMatrix3 {
+    type Output = SKIDVector3;
+
+    fn mul(self, v: SKIDVector3) -> SKIDVector3 {
+        self.transform(v)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::model::skid_color::SKIDColor;
+
+/// `SKIDImage`가 메모리에 픽셀을 저장하는 형식.
+///
+/// 모든 다중 바이트 채널은 리틀 엔디언으로 저장된다.
+/// 정수 형식(`R8`, `R16` 등)은 0.0~1.0 으로 정규화된 unorm 값이며, 저장 시 반올림/클램프된다.
+/// 부동소수 형식(`R32F`, `RGBA16F`, `RGBA32F`)은 1.0 을 넘는 HDR 값을 그대로 보존한다.
+///
+/// `SKIDColor`로 읽을 때 채널이 부족한 형식은 다음과 같이 확장된다:
+/// - 1채널 : (v, v, v, 1)  — 높이맵/마스크를 그레이스케일로 취급
+/// - 2채널 : (r, g, 0, 1)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SKIDPixelFormat {
+    R8 = 0,
+    RG8 = 1,
+    RGBA8 = 2,
+    R16 = 3,
+    RGBA16 = 4,
+    R32F = 5,
+    RGBA16F = 6,
+    #[default]
+    RGBA32F = 7,
+}
+
+impl SKIDPixelFormat {
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDPixelFormat::R8),
+            1 => Some(SKIDPixelFormat::RG8),
+            2 => Some(SKIDPixelFormat::RGBA8),
+            3 => Some(SKIDPixelFormat::R16),
+            4 => Some(SKIDPixelFormat::RGBA16),
+            5 => Some(SKIDPixelFormat::R32F),
+            6 => Some(SKIDPixelFormat::RGBA16F),
+            7 => Some(SKIDPixelFormat::RGBA32F),
+            _ => None,
+        }
+    }
+    pub fn channel_count(self) -> usize {
+        match self {
+            SKIDPixelFormat::R8 | SKIDPixelFormat::R16 | SKIDPixelFormat::R32F => 1,
+            SKIDPixelFormat::RG8 => 2,
+            SKIDPixelFormat::RGBA8
+            | SKIDPixelFormat::RGBA16
+            | SKIDPixelFormat::RGBA16F
+            | SKIDPixelFormat::RGBA32F => 4,
+        }
+    }
+    pub fn bytes_per_channel(self) -> usize {
+        match self {
+            SKIDPixelFormat::R8 | SKIDPixelFormat::RG8 | SKIDPixelFormat::RGBA8 => 1,
+            SKIDPixelFormat::R16 | SKIDPixelFormat::RGBA16 | SKIDPixelFormat::RGBA16F => 2,
+            SKIDPixelFormat::R32F | SKIDPixelFormat::RGBA32F => 4,
+        }
+    }
+    pub fn bytes_per_pixel(self) -> usize {
+        self.channel_count() * self.bytes_per_channel()
+    }
+    pub fn is_float(self) -> bool {
+        matches!(self, SKIDPixelFormat::R32F | SKIDPixelFormat::RGBA16F | SKIDPixelFormat::RGBA32F)
+    }
+    /// 같은 정밀도를 가진 4채널 형식. (단일 채널 입력에서 RGBA 출력을 만드는 프로세서용)
+    pub fn rgba_equivalent(self) -> Self {
+        match self {
+            SKIDPixelFormat::R8 | SKIDPixelFormat::RG8 | SKIDPixelFormat::RGBA8 => SKIDPixelFormat::RGBA8,
+            SKIDPixelFormat::R16 | SKIDPixelFormat::RGBA16 => SKIDPixelFormat::RGBA16,
+            SKIDPixelFormat::RGBA16F => SKIDPixelFormat::RGBA16F,
+            SKIDPixelFormat::R32F | SKIDPixelFormat::RGBA32F => SKIDPixelFormat::RGBA32F,
+        }
+    }
+    /// 같은 정밀도를 가진 1채널 형식. (AO/마스크처럼 단일 채널 결과를 만드는 프로세서용)
+    pub fn single_channel_equivalent(self) -> Self {
+        match self {
+            SKIDPixelFormat::R8 | SKIDPixelFormat::RG8 | SKIDPixelFormat::RGBA8 => SKIDPixelFormat::R8,
+            SKIDPixelFormat::R16 | SKIDPixelFormat::RGBA16 => SKIDPixelFormat::R16,
+            SKIDPixelFormat::R32F | SKIDPixelFormat::RGBA16F | SKIDPixelFormat::RGBA32F => SKIDPixelFormat::R32F,
+        }
+    }
+
+    /// 한 채널 값을 읽어 f32 로 반환한다. `bytes`는 해당 채널의 시작 위치여야 한다.
+    pub fn decode_channel(self, bytes: &[u8]) -> f32 {
+        match self.bytes_per_channel() {
+            1 => bytes[0] as f32 / 255.0,
+            2 if self.is_float() => f16_bits_to_f32(u16::from_le_bytes([bytes[0], bytes[1]])),
+            2 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 65535.0,
+            _ => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+
+    /// f32 값을 한 채널로 기록한다. `out`은 해당 채널의 시작 위치여야 한다.
+    pub fn encode_channel(self, value: f32, out: &mut [u8]) {
+        match self.bytes_per_channel() {
+            1 => out[0] = (value.clamp(0.0, 1.0) * 255.0).round() as u8,
+            2 if self.is_float() => out[..2].copy_from_slice(&f32_to_f16_bits(value).to_le_bytes()),
+            2 => out[..2].copy_from_slice(&((value.clamp(0.0, 1.0) * 65535.0).round() as u16).to_le_bytes()),
+            _ => out[..4].copy_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    /// 픽셀 하나(`bytes_per_pixel` 바이트)를 `SKIDColor`로 읽는다.
+    pub fn decode_pixel(self, bytes: &[u8]) -> SKIDColor {
+        let step = self.bytes_per_channel();
+        match self.channel_count() {
+            1 => {
+                let v = self.decode_channel(bytes);
+                SKIDColor::new(v, v, v, 1.0)
+            }
+            2 => SKIDColor::new(
+                self.decode_channel(bytes),
+                self.decode_channel(&bytes[step..]),
+                0.0,
+                1.0,
+            ),
+            _ => SKIDColor::new(
+                self.decode_channel(bytes),
+                self.decode_channel(&bytes[step..]),
+                self.decode_channel(&bytes[step * 2..]),
+                self.decode_channel(&bytes[step * 3..]),
+            ),
+        }
+    }
+
+    /// `SKIDColor`를 픽셀 하나로 기록한다. 채널이 부족한 형식은 앞쪽 채널(R, G)만 저장한다.
+    pub fn encode_pixel(self, color: SKIDColor, out: &mut [u8]) {
+        let step = self.bytes_per_channel();
+        let values = color.to_f32_array();
+        for c in 0..self.channel_count() {
+            self.encode_channel(values[c], &mut out[c * step..]);
+        }
+    }
+}
+
+/// 외부 버퍼의 4채널 픽셀 안에서 채널이 놓인 순서.
+/// `SKIDImage` 내부는 항상 RGBA 순서로 저장되며, 가져올 때 이 순서에 따라 재배치된다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SKIDChannelOrder {
+    #[default]
+    Rgba = 0,
+    Bgra = 1,
+    Argb = 2,
+}
+
+impl SKIDChannelOrder {
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDChannelOrder::Rgba),
+            1 => Some(SKIDChannelOrder::Bgra),
+            2 => Some(SKIDChannelOrder::Argb),
+            _ => None,
+        }
+    }
+    /// RGBA 각 채널이 원본 픽셀의 몇 번째 채널에서 오는지.
+    pub fn source_indices(self) -> [usize; 4] {
+        match self {
+            SKIDChannelOrder::Rgba => [0, 1, 2, 3],
+            SKIDChannelOrder::Bgra => [2, 1, 0, 3],
+            SKIDChannelOrder::Argb => [1, 2, 3, 0],
+        }
+    }
+}
+
+/// IEEE 754 binary16 비트 패턴을 f32 로 변환한다.
+pub fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) & 0x1) as u32;
+    let exp = ((bits >> 10) & 0x1f) as u32;
+    let mant = (bits & 0x3ff) as u32;
+    let f32_bits = if exp == 0 {
+        if mant == 0 {
+            sign << 31
+        } else {
+            // 비정규수: 정규화하면서 지수를 보정
+            let mut e = 127 - 15 + 1;
+            let mut m = mant;
+            while m & 0x400 == 0 {
+                m <<= 1;
+                e -= 1;
+            }
+            (sign << 31) | ((e as u32) << 23) | ((m & 0x3ff) << 13)
+        }
+    } else if exp == 0x1f {
+        (sign << 31) | (0xff << 23) | (mant << 13) // Inf / NaN
+    } else {
+        (sign << 31) | ((exp + 127 - 15) << 23) | (mant << 13)
+    };
+    f32::from_bits(f32_bits)
+}
+
+/// f32 를 IEEE 754 binary16 비트 패턴으로 변환한다 (round-to-nearest-even).
+pub fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x7f_ffff;
+
+    if exp == 0xff {
+        // Inf / NaN
+        return sign | 0x7c00 | if mant != 0 { 0x200 } else { 0 };
+    }
+    let half_exp = exp - 127 + 15;
+    if half_exp >= 0x1f {
+        return sign | 0x7c00; // 오버플로 → Inf
+    }
+    if half_exp <= 0 {
+        if half_exp < -10 {
+            return sign; // 0 으로 언더플로
+        }
+        // 비정규수
+        let m = mant | 0x80_0000;
+        let shift = (14 - half_exp) as u32;
+        let half_mant = m >> shift;
+        let round_bit = 1 << (shift - 1);
+        let rounded = if (m & round_bit) != 0 && ((m & (round_bit - 1)) != 0 || (half_mant & 1) != 0) {
+            half_mant + 1
+        } else {
+            half_mant
+        };
+        return sign | rounded as u16;
+    }
+    let half_mant = mant >> 13;
+    let round_bits = mant & 0x1fff;
+    let mut result = ((half_exp as u32) << 10) | half_mant;
+    if round_bits > 0x1000 || (round_bits == 0x1000 && (half_mant & 1) != 0) {
+        result += 1; // 가수 올림이 지수로 넘어가도 올바른 값이 된다
+    }
+    sign | result as u16
+}
@@ -0,0 +1,137 @@
+use cubecl::{cube, prelude::*, Runtime};
+
+use crate::{
+    model::{skid_alpha_mode::SKIDAlphaMode, skid_color_space::SKIDColorSpace, skid_image::SKIDImage, skid_pixel_format::SKIDPixelFormat},
+    processor::color_space,
+    utils::gpu_opt::image_cube_config,
+};
+
+#[cube(launch_unchecked)]
+fn kernel_convert_alpha<F: Float>(
+    input: &Array<F>,
+    width: u32,
+    height: u32,
+    premultiply: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let alpha = input[idx][3];
+        let mut factor = alpha;
+        if premultiply == 0 {
+            // 완전 투명 픽셀은 색 정보가 없으므로 검정으로 둔다.
+            factor = if alpha > F::new(0.) { F::new(1.) / alpha } else { F::new(0.) };
+        }
+        output[idx * 4] = input[idx][0] * factor;
+        output[idx * 4 + 1] = input[idx][1] * factor;
+        output[idx * 4 + 2] = input[idx][2] * factor;
+        output[idx * 4 + 3] = alpha;
+    }
+}
+
+/// straight 알파 이미지를 프리멀티플라이드로 변환한다. 이미 프리멀티플라이드면 복사본을 돌려준다.
+pub fn premultiply<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    convert_alpha_mode::<R>(run_device, image, SKIDAlphaMode::Premultiplied)
+}
+
+/// 프리멀티플라이드 이미지를 straight 알파로 되돌린다. 이미 straight 면 복사본을 돌려준다.
+pub fn unpremultiply<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    convert_alpha_mode::<R>(run_device, image, SKIDAlphaMode::Straight)
+}
+
+pub fn convert_alpha_mode<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: SKIDAlphaMode,
+) -> SKIDImage {
+    if image.get_alpha_mode() == target {
+        return image.clone();
+    }
+    launch::<R>(run_device, image, target)
+}
+
+/// `prepare_filter_input`이 바꾼 입력의 원래 형식. 처리 결과를 되돌릴 때 사용한다.
+#[derive(Debug, Clone, Copy)]
+pub struct FilterInputState {
+    pub color_space: SKIDColorSpace,
+    pub alpha_mode: SKIDAlphaMode,
+    pub pixel_format: SKIDPixelFormat,
+}
+
+/// 리샘플링/블렌딩처럼 주변 픽셀을 섞는 프로세서의 입력을 준비한다.
+/// 입력을 RGBA32F + linear + 프리멀티플라이드 형식으로 만들고, 원래 형식을 함께 돌려준다.
+/// (8비트 형식 그대로 linear 로 바꾸면 어두운 영역에서 밴딩이 생기므로 f32 로 먼저 넓힌다.)
+/// 색 공간 디코딩과 프리멀티플라이는 한 번의 GPU 패스로 처리한다.
+pub fn prepare_filter_input<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    processor_name: &str,
+) -> (SKIDImage, FilterInputState) {
+    let state = FilterInputState {
+        color_space: image.get_color_space(),
+        alpha_mode: image.get_alpha_mode(),
+        pixel_format: image.get_pixel_format(),
+    };
+    if !state.color_space.is_rgb() {
+        log::warn!(
+            "[{}] input is in {:?} space, converting to linear RGB before processing",
+            processor_name, state.color_space
+        );
+    }
+    let widened = image.convert_format(SKIDPixelFormat::RGBA32F);
+    let prepared = color_space::convert_color_space_and_alpha::<R>(
+        run_device,
+        &widened,
+        SKIDColorSpace::Linear,
+        SKIDAlphaMode::Premultiplied,
+    );
+    (prepared, state)
+}
+
+/// `prepare_filter_input`으로 준비한 입력에서 나온 결과를 원래 형식으로 되돌린다.
+/// 언프리멀티플라이와 색 공간 인코딩은 한 번의 GPU 패스로 처리한다.
+pub fn restore_filter_output<R: Runtime>(
+    run_device: &R::Device,
+    image: SKIDImage,
+    state: &FilterInputState,
+) -> SKIDImage {
+    color_space::convert_color_space_and_alpha::<R>(run_device, &image, state.color_space, state.alpha_mode)
+        .convert_format(state.pixel_format)
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: SKIDAlphaMode,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let w_u32 = size.width as u32;
+    let h_u32 = size.height as u32;
+
+    let input = image.get_1d_data_as_f32();
+    let pixel_count = input.len() / 4;
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_convert_alpha::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, 4),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: (target == SKIDAlphaMode::Premultiplied) as u32 },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), 4)
+        .with_metadata_from(image)
+        .with_alpha_mode(target)
+}
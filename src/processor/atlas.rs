@@ -0,0 +1,344 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_atlas::{SKIDAtlas, SKIDAtlasEntry, SKIDAtlasManifest},
+        skid_image::{SKIDImage, SKIDRect, SKIDSizeVector2},
+        skid_pixel_format::SKIDPixelFormat,
+    },
+    processor::{
+        convolution::{load_texel, SKIDEdgeMode},
+        region::match_tags,
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 아틀라스 생성 옵션.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SKIDAtlasOptions {
+    /// 아틀라스가 커질 수 있는 최대 크기. 이 안에 모두 들어가지 않으면 Err.
+    pub max_size: SKIDSizeVector2,
+    /// 각 이미지 둘레에 가장자리 픽셀을 늘여(extrude) 붙일 폭. 밉맵/바이리니어 샘플링 시 이웃 이미지가 번지는 것을 막는다.
+    pub padding: usize,
+    /// 더 잘 들어가면 이미지를 시계 방향으로 90도 돌려 넣는다.
+    pub allow_rotation: bool,
+    /// 가로/세로를 2의 거듭제곱으로 맞춘다.
+    pub power_of_two: bool,
+    /// 결과 아틀라스의 픽셀 형식 (4채널)
+    pub format: SKIDPixelFormat,
+}
+
+impl Default for SKIDAtlasOptions {
+    fn default() -> Self {
+        SKIDAtlasOptions {
+            max_size: SKIDSizeVector2::new(4096, 4096),
+            padding: 2,
+            allow_rotation: false,
+            power_of_two: false,
+            format: SKIDPixelFormat::RGBA8,
+        }
+    }
+}
+
+/// 배치 후보의 우선순위. (짧은 남는 변, 긴 남는 변, y, x) 순으로 작을수록 좋다.
+type FitScore = (usize, usize, usize, usize);
+
+/// MaxRects 빈 패킹. 남은 공간을 서로 겹칠 수 있는 최대 빈 사각형 목록으로 관리한다.
+struct MaxRectsBin {
+    free: Vec<SKIDRect>,
+}
+
+fn overlaps(a: &SKIDRect, b: &SKIDRect) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+fn contains(outer: &SKIDRect, inner: &SKIDRect) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+impl MaxRectsBin {
+    fn new(size: SKIDSizeVector2) -> Self {
+        MaxRectsBin { free: vec![SKIDRect::new(0, 0, size.width, size.height)] }
+    }
+
+    /// Best Short Side Fit: 남는 짧은 변이 가장 작은 빈 사각형의 왼쪽 위에 넣는다. (동점이면 긴 변, 위쪽, 왼쪽 순)
+    fn insert(&mut self, width: usize, height: usize, allow_rotation: bool) -> Option<(SKIDRect, bool)> {
+        let mut best: Option<(FitScore, SKIDRect, bool)> = None;
+        for free in &self.free {
+            for (w, h, rotated) in [(width, height, false), (height, width, true)] {
+                if rotated && (!allow_rotation || width == height) {
+                    continue;
+                }
+                if w > free.width || h > free.height {
+                    continue;
+                }
+                let (left_w, left_h) = (free.width - w, free.height - h);
+                let score = (left_w.min(left_h), left_w.max(left_h), free.y, free.x);
+                if best.is_none_or(|(best_score, ..)| score < best_score) {
+                    best = Some((score, SKIDRect::new(free.x, free.y, w, h), rotated));
+                }
+            }
+        }
+        let (_, rect, rotated) = best?;
+        self.place(&rect);
+        Some((rect, rotated))
+    }
+
+    /// `used`와 겹치는 빈 사각형을 겹치지 않는 네 방향 조각으로 나누고, 다른 빈 사각형에 포함되는 것은 버린다.
+    fn place(&mut self, used: &SKIDRect) {
+        let mut split = Vec::with_capacity(self.free.len() + 4);
+        for free in self.free.drain(..) {
+            if !overlaps(&free, used) {
+                split.push(free);
+                continue;
+            }
+            let (free_right, free_bottom) = (free.x + free.width, free.y + free.height);
+            let (used_right, used_bottom) = (used.x + used.width, used.y + used.height);
+            if used.x > free.x {
+                split.push(SKIDRect::new(free.x, free.y, used.x - free.x, free.height));
+            }
+            if used_right < free_right {
+                split.push(SKIDRect::new(used_right, free.y, free_right - used_right, free.height));
+            }
+            if used.y > free.y {
+                split.push(SKIDRect::new(free.x, free.y, free.width, used.y - free.y));
+            }
+            if used_bottom < free_bottom {
+                split.push(SKIDRect::new(free.x, used_bottom, free.width, free_bottom - used_bottom));
+            }
+        }
+        // 같은 사각형이 여럿이면 처음 것만 남긴다.
+        self.free = split
+            .iter()
+            .enumerate()
+            .filter(|&(i, a)| {
+                !split.iter().enumerate().any(|(j, b)| i != j && contains(b, a) && (a != b || j < i))
+            })
+            .map(|(_, a)| *a)
+            .collect();
+    }
+}
+
+/// `sizes` 크기의 사각형들을 `max_size` 안에 겹치지 않게 배치한다.
+///
+/// 넓이로 어림한 정사각형에서 시작해 모두 들어갈 때까지 짧은 쪽 변을 늘려 가며 다시 배치하므로, 결과는 가능한 한
+/// 작고 정사각형에 가깝다. 반환값은 (아틀라스 크기, 입력 순서의 (위치, 회전 여부))이며, 들어가지 않으면 Err.
+pub fn pack_rects(
+    sizes: &[SKIDSizeVector2],
+    max_size: SKIDSizeVector2,
+    allow_rotation: bool,
+    power_of_two: bool,
+) -> Result<(SKIDSizeVector2, Vec<(SKIDRect, bool)>), String> {
+    if sizes.is_empty() {
+        return Err("No rectangles to pack".to_string());
+    }
+    for (index, size) in sizes.iter().enumerate() {
+        let fits = |w: usize, h: usize| w <= max_size.width && h <= max_size.height;
+        if size.is_empty() {
+            return Err(format!("Rectangle {} is empty", index));
+        }
+        if !(fits(size.width, size.height) || allow_rotation && fits(size.height, size.width)) {
+            return Err(format!(
+                "Rectangle {} ({}x{}) does not fit in {}x{}",
+                index, size.width, size.height, max_size.width, max_size.height
+            ));
+        }
+    }
+
+    // 긴 변이 긴 것부터 넣어야 빈 공간이 덜 쪼개진다. (동점이면 넓이, 입력 순서)
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| {
+        let s = sizes[i];
+        (std::cmp::Reverse(s.width.max(s.height)), std::cmp::Reverse(s.width * s.height))
+    });
+
+    let grow = |side: usize, limit: usize| {
+        let next = if power_of_two { side * 2 } else { side + side.div_ceil(8) };
+        next.min(limit)
+    };
+    let round = |side: usize, limit: usize| {
+        let side = if power_of_two { side.next_power_of_two() } else { side };
+        side.min(limit)
+    };
+    let area: usize = sizes.iter().map(|s| s.width * s.height).sum();
+    let side = (area as f64).sqrt().ceil() as usize;
+    // 가장 큰 사각형도 들어가야 한다. 회전할 수 있으면 짧은 변만 들어가면 된다.
+    let extent = |s: &SKIDSizeVector2| {
+        if allow_rotation {
+            let short = s.width.min(s.height);
+            (short, short)
+        } else {
+            (s.width, s.height)
+        }
+    };
+    let min_width = sizes.iter().map(|s| extent(s).0).max().unwrap_or(1);
+    let min_height = sizes.iter().map(|s| extent(s).1).max().unwrap_or(1);
+    let mut bin_size = SKIDSizeVector2::new(
+        round(side.max(min_width), max_size.width),
+        round(side.max(min_height), max_size.height),
+    );
+
+    loop {
+        let mut bin = MaxRectsBin::new(bin_size);
+        let mut placements = vec![(SKIDRect::default(), false); sizes.len()];
+        let packed = order.iter().all(|&i| match bin.insert(sizes[i].width, sizes[i].height, allow_rotation) {
+            Some(placement) => {
+                placements[i] = placement;
+                true
+            }
+            None => false,
+        });
+        if packed {
+            // 배치에 쓰인 영역만 남긴다.
+            let used_width = placements.iter().map(|(r, _)| r.x + r.width).max().unwrap_or(1);
+            let used_height = placements.iter().map(|(r, _)| r.y + r.height).max().unwrap_or(1);
+            let size = SKIDSizeVector2::new(round(used_width, max_size.width), round(used_height, max_size.height));
+            return Ok((size, placements));
+        }
+        if bin_size == max_size {
+            return Err(format!(
+                "{} rectangles do not fit in {}x{}",
+                sizes.len(),
+                max_size.width,
+                max_size.height
+            ));
+        }
+        let can_grow_width = bin_size.width < max_size.width;
+        let can_grow_height = bin_size.height < max_size.height;
+        if can_grow_width && (bin_size.width <= bin_size.height || !can_grow_height) {
+            bin_size.width = grow(bin_size.width, max_size.width);
+        } else {
+            bin_size.height = grow(bin_size.height, max_size.height);
+        }
+    }
+}
+
+/// 아틀라스의 `dst` 영역(여백 포함)에 원본을 복사한다. 출력 (px, py)는 원본 정수 좌표
+/// (ox + xx * px + xy * py, oy + yx * px + yy * py)에서 읽으며, 원본 밖(여백)은 `edge_mode`(Clamp)로 가장자리를 늘인다.
+#[cube(launch_unchecked)]
+fn kernel_atlas_blit<F: Float>(
+    input: &Array<F>,
+    constant: &Array<F>,
+    coeffs: &Array<i32>,
+    placement: &Array<u32>,
+    output: &mut Array<F>,
+) {
+    // placement: [width, height, dst_x, dst_y, dst_width, dst_height, atlas_width, edge_mode]
+    let width = placement[0];
+    let height = placement[1];
+    let dst_x = placement[2];
+    let dst_y = placement[3];
+    let dst_width = placement[4];
+    let dst_height = placement[5];
+    let atlas_width = placement[6];
+    let edge_mode = placement[7];
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < dst_width && py < dst_height {
+        let x = px as i32;
+        let y = py as i32;
+        let sx = coeffs[0] + coeffs[2] * x + coeffs[3] * y;
+        let sy = coeffs[1] + coeffs[4] * x + coeffs[5] * y;
+        let idx = ((dst_y + py) * atlas_width + dst_x + px) * 4;
+        for c in 0..4u32 {
+            output[idx + c] = load_texel::<F>(input, constant, sx, sy, c, width, height, edge_mode);
+        }
+    }
+}
+
+/// 여러 이미지를 하나의 아틀라스로 패킹한다.
+///
+/// 배치는 `pack_rects`(MaxRects)로 정하고, 각 이미지를 가장자리 연장 여백과 함께 GPU 의 아틀라스 버퍼에 바로 그린 뒤
+/// 한 번만 읽어 온다. 아틀라스의 색 공간/알파 모드는 첫 이미지를 따르며, 태그가 다른 이미지는 맞춰서 넣는다.
+/// 1·2채널 이미지는 RGBA 로 넓혀 넣는다.
+pub fn build_atlas<R: Runtime>(
+    run_device: &R::Device,
+    images: &[&SKIDImage],
+    options: &SKIDAtlasOptions,
+) -> Result<SKIDAtlas, String> {
+    if options.format.channel_count() != 4 {
+        return Err(format!("Atlas format {:?} must have 4 channels", options.format));
+    }
+    let padding = options.padding;
+    let padded_sizes: Vec<SKIDSizeVector2> = images
+        .iter()
+        .map(|image| {
+            let size = image.get_size();
+            if size.is_empty() {
+                size
+            } else {
+                SKIDSizeVector2::new(size.width + padding * 2, size.height + padding * 2)
+            }
+        })
+        .collect();
+    let (atlas_size, placements) =
+        pack_rects(&padded_sizes, options.max_size, options.allow_rotation, options.power_of_two)?;
+    let (color_space, alpha_mode) = (images[0].get_color_space(), images[0].get_alpha_mode());
+
+    let client = R::client(run_device);
+    let atlas_len = atlas_size.width * atlas_size.height * 4;
+    let atlas_handle = client.create(bytemuck::cast_slice(&vec![0.0f32; atlas_len]));
+    let constant_handle = client.create(bytemuck::cast_slice(&[0.0f32; 4]));
+
+    let mut entries = Vec::with_capacity(images.len());
+    for (image, &(rect, rotated)) in images.iter().zip(&placements) {
+        let converted = match_tags::<R>(run_device, image, color_space, alpha_mode);
+        let image = converted.as_ref().unwrap_or(image);
+        let size = image.get_size();
+        let p = padding as i32;
+        // [ox, oy, xx, xy, yx, yy]. 회전하면 `rotate_quarter_turns(1)`과 같이 시계 방향으로 돌려 넣는다.
+        let coeffs: [i32; 6] = if rotated {
+            [-p, size.height as i32 - 1 + p, 0, 1, -1, 0]
+        } else {
+            [-p, -p, 1, 0, 0, 1]
+        };
+        let input = image.get_1d_data_as_f32();
+        let input_handle = client.create(bytemuck::cast_slice(&input));
+        let coeffs_handle = client.create(bytemuck::cast_slice(&coeffs));
+        let placement = [
+            size.width as u32,
+            size.height as u32,
+            rect.x as u32,
+            rect.y as u32,
+            rect.width as u32,
+            rect.height as u32,
+            atlas_size.width as u32,
+            SKIDEdgeMode::Clamp.to_u32(),
+        ];
+        let placement_handle = client.create(bytemuck::cast_slice(&placement));
+        let (cube_count, cube_dim) = image_cube_config(rect.width as u32, rect.height as u32);
+
+        unsafe {
+            kernel_atlas_blit::launch_unchecked::<f32, R>(
+                &client,
+                cube_count,
+                cube_dim,
+                ArrayArg::from_raw_parts::<f32>(&input_handle, size.width * size.height, 4),
+                ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+                ArrayArg::from_raw_parts::<i32>(&coeffs_handle, 6, 1),
+                ArrayArg::from_raw_parts::<u32>(&placement_handle, placement.len(), 1),
+                ArrayArg::from_raw_parts::<f32>(&atlas_handle, atlas_len, 1),
+            )
+        };
+
+        let content = SKIDRect::new(
+            rect.x + padding,
+            rect.y + padding,
+            rect.width - padding * 2,
+            rect.height - padding * 2,
+        );
+        entries.push(SKIDAtlasEntry::new(content, rotated, atlas_size));
+    }
+
+    let bytes = client.read_one(atlas_handle.binding());
+    let image = SKIDImage::from_f32_data(atlas_size, options.format, f32::from_bytes(&bytes), 4)
+        .with_color_space(color_space)
+        .with_alpha_mode(alpha_mode);
+    Ok(SKIDAtlas {
+        image,
+        manifest: SKIDAtlasManifest { size: atlas_size, padding, entries },
+    })
+}
@@ -0,0 +1,557 @@
+use cubecl::{cube, prelude::*, server::Handle, client::ComputeClient, CubeElement, Runtime};
+
+use crate::{
+    model::skid_image::{SKIDImage, SKIDRect},
+    processor::{
+        convolution::{convolve_separable_values, filter_image, load_texel, SKIDConvolutionOptions},
+        region,
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 가우시안 블러의 계산 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDGaussianMode {
+    /// 반경 ceil(3σ)의 분리 가능 가우시안 커널. 반경에 비례해 느려진다.
+    #[default]
+    Exact = 0,
+    /// 박스 블러 3회로 근사한다. 반경과 무관하게 일정한 비용이 든다.
+    Approximate = 1,
+}
+
+impl SKIDGaussianMode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDGaussianMode::Exact),
+            1 => Some(SKIDGaussianMode::Approximate),
+            _ => None,
+        }
+    }
+}
+
+/// 정규화된 1D 가우시안 가중치 (길이 2 * ceil(3σ) + 1).
+pub fn gaussian_kernel_1d(sigma: f32) -> Vec<f32> {
+    let radius = gaussian_radius(sigma) as i32;
+    let denom = 2.0 * sigma * sigma;
+    let weights: Vec<f32> = (-radius..=radius)
+        .map(|i| (-((i * i) as f32) / denom).exp())
+        .collect();
+    let sum: f32 = weights.iter().sum();
+    weights.into_iter().map(|w| w / sum).collect()
+}
+
+/// 정확한 가우시안 커널의 반경 ceil(3σ). (최소 1)
+fn gaussian_radius(sigma: f32) -> usize {
+    (sigma * 3.0).ceil().max(1.0) as usize
+}
+
+/// `gaussian_blur`가 읽는 이웃 반경. ROI 처리의 테두리(halo) 폭으로 쓴다.
+pub(crate) fn gaussian_halo(sigma: f32, mode: SKIDGaussianMode) -> usize {
+    match mode {
+        SKIDGaussianMode::Exact => gaussian_radius(sigma),
+        SKIDGaussianMode::Approximate => gaussian_box_radii(sigma).iter().map(|&r| r as usize).sum(),
+    }
+}
+
+/// 표준편차 σ 의 가우시안을 근사하는 박스 블러 3개의 반경. (Kovesi, "Fast Almost-Gaussian Filtering")
+pub fn gaussian_box_radii(sigma: f32) -> [u32; 3] {
+    const PASSES: f32 = 3.0;
+    let ideal_width = (12.0 * sigma * sigma / PASSES + 1.0).sqrt();
+    let mut lower = ideal_width.floor() as i32;
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let lower = Ord::max(lower, 1);
+    let upper = lower + 2;
+    let lf = lower as f32;
+    let ideal_count = (12.0 * sigma * sigma - PASSES * lf * lf - 4.0 * PASSES * lf - 3.0 * PASSES) / (-4.0 * lf - 4.0);
+    let lower_count = ideal_count.round() as usize;
+    let mut radii = [0u32; 3];
+    for (i, r) in radii.iter_mut().enumerate() {
+        let width = if i < lower_count { lower } else { upper };
+        *r = ((width - 1) / 2) as u32;
+    }
+    radii
+}
+
+/// 가우시안 블러. `sigma`가 0 이하이면 복사본을 돌려준다.
+pub fn gaussian_blur<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    sigma: f32,
+    mode: SKIDGaussianMode,
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    if sigma <= 0.0 {
+        return image.clone();
+    }
+    filter_image::<R>(run_device, image, "gaussian_blur", |img| {
+        gaussian_blur_values::<R>(run_device, img, sigma, mode, options)
+    })
+}
+
+/// `image`의 `roi` 영역만 `gaussian_blur`로 처리해 `target`의 같은 위치에 제자리로 쓴다.
+///
+/// 영역을 블러 반경만큼 `options.edge_mode`로 넓혀 잘라낸 뒤 처리하므로 결과는 전체를 처리한 것과 같다.
+/// 단, `Approximate` 모드는 박스 패스를 여러 번 거치므로 이미지 가장자리에 닿는 영역에서 `Clamp`/`Mirror`이면
+/// 가장자리 근처 값이 조금 다를 수 있고, `Constant` 모드의 이미지 밖 테두리는 투명 검정(0)으로 채워진다.
+pub fn gaussian_blur_region<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    sigma: f32,
+    mode: SKIDGaussianMode,
+    options: &SKIDConvolutionOptions,
+) -> Result<(), String> {
+    let halo = gaussian_halo(sigma, mode);
+    region::process_region::<R>(run_device, image, target, roi, halo, options.edge_mode, |region| {
+        gaussian_blur::<R>(run_device, region, sigma, mode, options)
+    })
+}
+
+/// `gaussian_blur`와 같지만 색 공간/알파 변환 없이 저장된 값 그대로 블러한다.
+/// 이미 필터 입력으로 준비된 이미지나, 색이 아닌 데이터 맵에 사용한다.
+pub fn gaussian_blur_values<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    sigma: f32,
+    mode: SKIDGaussianMode,
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    if sigma <= 0.0 {
+        return image.clone();
+    }
+    match mode {
+        SKIDGaussianMode::Exact => {
+            let weights = gaussian_kernel_1d(sigma);
+            convolve_separable_values::<R>(run_device, image, &weights, &weights, options)
+        }
+        SKIDGaussianMode::Approximate => {
+            launch_box::<R>(run_device, image, &gaussian_box_radii(sigma), options).with_metadata_from(image)
+        }
+    }
+}
+
+/// (2r+1)x(2r+1) 박스 블러. 가로/세로 줄마다 보정된 누적합을 만들어 계산하므로 반경과 무관하게 일정한 비용이 든다.
+pub fn box_blur<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    radius: u32,
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    if radius == 0 {
+        return image.clone();
+    }
+    filter_image::<R>(run_device, image, "box_blur", |img| launch_box::<R>(run_device, img, &[radius], options))
+}
+
+/// `image`의 `roi` 영역만 `box_blur`로 처리해 `target`의 같은 위치에 제자리로 쓴다. (`gaussian_blur_region` 참고)
+pub fn box_blur_region<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    radius: u32,
+    options: &SKIDConvolutionOptions,
+) -> Result<(), String> {
+    region::process_region::<R>(run_device, image, target, roi, radius as usize, options.edge_mode, |region| {
+        box_blur::<R>(run_device, region, radius, options)
+    })
+}
+
+/// 가장자리를 보존하는 바이리터럴 필터.
+///
+/// - `spatial_sigma`: 거리 가중치의 표준편차 (픽셀). 창 반경은 ceil(2σ).
+/// - `range_sigma`  : 값 차이 가중치의 표준편차 (0~1 값 기준). 작을수록 가장자리를 강하게 보존한다.
+///
+/// 값 차이는 색상 채널(최대 3개)의 유클리드 거리로 계산한다.
+pub fn bilateral_blur<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    spatial_sigma: f32,
+    range_sigma: f32,
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    if spatial_sigma <= 0.0 || range_sigma <= 0.0 {
+        return image.clone();
+    }
+    filter_image::<R>(run_device, image, "bilateral_blur", |img| {
+        launch_bilateral::<R>(run_device, img, spatial_sigma, range_sigma, options)
+    })
+}
+
+/// `image`의 `roi` 영역만 `bilateral_blur`로 처리해 `target`의 같은 위치에 제자리로 쓴다. 테두리 폭은 창 반경 ceil(2σ)이다.
+pub fn bilateral_blur_region<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    spatial_sigma: f32,
+    range_sigma: f32,
+    options: &SKIDConvolutionOptions,
+) -> Result<(), String> {
+    let halo = bilateral_radius(spatial_sigma) as usize;
+    region::process_region::<R>(run_device, image, target, roi, halo, options.edge_mode, |region| {
+        bilateral_blur::<R>(run_device, region, spatial_sigma, range_sigma, options)
+    })
+}
+
+/// 바이리터럴 창 반경 ceil(2σ).
+fn bilateral_radius(spatial_sigma: f32) -> u32 {
+    (spatial_sigma * 2.0).ceil() as u32
+}
+
+// ─── 박스 블러 (줄 단위 누적합) ───
+//
+// 2D 요약 면적 테이블(summed-area table) 대신 한 축씩 처리한다. 이미지 전체의 2D 누적합은
+// 8K 이미지에서 f32 정밀도를 넘어서므로 한 줄(행 또는 열) 단위의 누적합만 만든다.
+// 한 줄 누적합도 줄 길이 수준까지 커지므로, 두 누적합의 차이에서 잃는 자릿수를
+// Kahan 보정항으로 되살린다. 누적합 값은 `prefix`에, 보정항은 `prefix_lo`에 저장하며
+// 실제 누적합은 prefix + prefix_lo 이다.
+//
+// 줄(line) l 의 i 번째 픽셀 인덱스 = l * line_stride + i * elem_stride
+//   가로 패스: lines = height, len = width,  line_stride = width, elem_stride = 1
+//   세로 패스: lines = width,  len = height, line_stride = 1,     elem_stride = width
+
+/// 줄마다 Kahan 보정 배타적 누적합(길이 len + 1)을 만든다. 유닛 하나가 한 줄을 담당한다.
+#[cube(launch_unchecked)]
+fn kernel_line_prefix_sum<F: Float>(
+    input: &Array<F>,
+    lines: u32,
+    len: u32,
+    line_stride: u32,
+    elem_stride: u32,
+    channels: u32,
+    prefix: &mut Array<F>,
+    prefix_lo: &mut Array<F>,
+) {
+    let line = ABSOLUTE_POS;
+    if line < lines {
+        let base = line * (len + 1);
+        for c in 0..channels {
+            let mut acc = F::new(0.);
+            let mut compensation = F::new(0.);
+            prefix[base * channels + c] = acc;
+            prefix_lo[base * channels + c] = F::new(0.);
+            for i in 0..len {
+                let y = input[(line * line_stride + i * elem_stride) * channels + c] - compensation;
+                let t = acc + y;
+                compensation = (t - acc) - y;
+                acc = t;
+                prefix[(base + i + 1) * channels + c] = acc;
+                prefix_lo[(base + i + 1) * channels + c] = F::new(0.) - compensation;
+            }
+        }
+    }
+}
+
+/// 가장자리 모드로 무한히 확장한 줄에서 [0, k) 구간의 합.
+/// 누적합에 대해 선형이므로 보정항 배열에도 그대로 적용할 수 있다. (이때 `first`/`last`/`constant`는 0)
+#[cube]
+fn extended_prefix<F: Float>(
+    prefix: &Array<F>,
+    base: u32,
+    k: i32,
+    len: i32,
+    c: u32,
+    channels: u32,
+    edge_mode: u32,
+    first: F,
+    last: F,
+    constant: F,
+) -> F {
+    let total = prefix[(base + len as u32) * channels + c];
+    let mut result = total;
+    if edge_mode == 1 {
+        // Wrap: 주기 len
+        let q = if k >= 0 { k / len } else { (k + 1) / len - 1 };
+        let m = k - q * len;
+        result = F::cast_from(q) * total + prefix[(base + m as u32) * channels + c];
+    } else if edge_mode == 2 {
+        // Mirror: 주기 2 * len, 뒤쪽 절반은 앞쪽을 뒤집은 것
+        let period = len * 2;
+        let q = if k >= 0 { k / period } else { (k + 1) / period - 1 };
+        let m = k - q * period;
+        let mut partial = prefix[(base + select(m < len, m as u32, len as u32)) * channels + c];
+        if m > len {
+            partial = total * F::new(2.) - prefix[(base + (period - m) as u32) * channels + c];
+        }
+        result = F::cast_from(q) * total * F::new(2.) + partial;
+    } else {
+        // Clamp / Constant: 범위 밖은 가장자리 값 또는 상수가 이어진다.
+        let mut before = first;
+        let mut after = last;
+        if edge_mode == 3 {
+            before = constant;
+            after = constant;
+        }
+        if k <= 0 {
+            result = F::cast_from(k) * before;
+        } else if k >= len {
+            result += F::cast_from(k - len) * after;
+        } else {
+            result = prefix[(base + k as u32) * channels + c];
+        }
+    }
+    result
+}
+
+#[cube(launch_unchecked)]
+fn kernel_line_box<F: Float>(
+    input: &Array<F>,
+    prefix: &Array<F>,
+    prefix_lo: &Array<F>,
+    constant: &Array<F>,
+    params: &Array<u32>,
+    output: &mut Array<F>,
+) {
+    // params: [lines, len, line_stride, elem_stride, channels, radius, edge_mode, channel_mask]
+    let lines = params[0];
+    let len = params[1];
+    let line_stride = params[2];
+    let elem_stride = params[3];
+    let channels = params[4];
+    let radius = params[5];
+    let edge_mode = params[6];
+    let channel_mask = params[7];
+    let i = ABSOLUTE_POS_X;
+    let line = ABSOLUTE_POS_Y;
+    if i < len && line < lines {
+        let base = line * (len + 1);
+        let pixel = line * line_stride + i * elem_stride;
+        let first_pixel = line * line_stride;
+        let last_pixel = line * line_stride + (len - 1) * elem_stride;
+        let inv_count = F::new(1.) / F::cast_from(radius * 2 + 1);
+        for c in 0..channels {
+            if (channel_mask >> c) & 1 == 1 {
+                let first = input[first_pixel * channels + c];
+                let last = input[last_pixel * channels + c];
+                let hi = extended_prefix::<F>(
+                    prefix, base, (i + radius + 1) as i32, len as i32, c, channels, edge_mode, first, last, constant[c],
+                );
+                let lo = extended_prefix::<F>(
+                    prefix, base, i as i32 - radius as i32, len as i32, c, channels, edge_mode, first, last, constant[c],
+                );
+                // 큰 누적합끼리의 차이에서 잃은 부분을 보정항의 차이로 더한다.
+                let zero = F::new(0.);
+                let hi_lo = extended_prefix::<F>(
+                    prefix_lo, base, (i + radius + 1) as i32, len as i32, c, channels, edge_mode, zero, zero, zero,
+                );
+                let lo_lo = extended_prefix::<F>(
+                    prefix_lo, base, i as i32 - radius as i32, len as i32, c, channels, edge_mode, zero, zero, zero,
+                );
+                output[pixel * channels + c] = ((hi - lo) + (hi_lo - lo_lo)) * inv_count;
+            } else {
+                output[pixel * channels + c] = input[pixel * channels + c];
+            }
+        }
+    }
+}
+
+/// 반경 목록의 박스 블러를 차례로 적용한다. 중간 결과는 GPU 에 머문다.
+fn launch_box<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    radii: &[u32],
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (w, h) = (size.width as u32, size.height as u32);
+
+    let input = image.get_1d_data_as_f32_channels();
+    let mut handle = client.create(bytemuck::cast_slice(&input));
+    let constant_handle = client.create(bytemuck::cast_slice(&options.constant_color.to_f32_array()));
+
+    for &radius in radii {
+        handle = run_box_axis::<R>(&client, &handle, &constant_handle, h, w, w, 1, channels, radius, options);
+        handle = run_box_axis::<R>(&client, &handle, &constant_handle, w, h, 1, w, channels, radius, options);
+    }
+
+    let bytes = client.read_one(handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_box_axis<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input_handle: &Handle,
+    constant_handle: &Handle,
+    lines: u32,
+    len: u32,
+    line_stride: u32,
+    elem_stride: u32,
+    channels: usize,
+    radius: u32,
+    options: &SKIDConvolutionOptions,
+) -> Handle {
+    let value_count = (lines * len) as usize * channels;
+    let prefix_count = (lines * (len + 1)) as usize * channels;
+    let prefix_handle = client.empty(prefix_count * core::mem::size_of::<f32>());
+    let prefix_lo_handle = client.empty(prefix_count * core::mem::size_of::<f32>());
+    let output_handle = client.empty(value_count * core::mem::size_of::<f32>());
+
+    let params = [
+        lines,
+        len,
+        line_stride,
+        elem_stride,
+        channels as u32,
+        radius,
+        options.edge_mode.to_u32(),
+        options.channel_mask,
+    ];
+    let params_handle = client.create(bytemuck::cast_slice(&params));
+
+    let line_cube = 256u32;
+    let (cube_count, cube_dim) = image_cube_config(len, lines);
+    unsafe {
+        kernel_line_prefix_sum::launch_unchecked::<f32, R>(
+            client,
+            CubeCount::Static(lines.div_ceil(line_cube), 1, 1),
+            CubeDim::new(line_cube, 1, 1),
+            ArrayArg::from_raw_parts::<f32>(input_handle, value_count, 1),
+            ScalarArg { elem: lines },
+            ScalarArg { elem: len },
+            ScalarArg { elem: line_stride },
+            ScalarArg { elem: elem_stride },
+            ScalarArg { elem: channels as u32 },
+            ArrayArg::from_raw_parts::<f32>(&prefix_handle, prefix_count, 1),
+            ArrayArg::from_raw_parts::<f32>(&prefix_lo_handle, prefix_count, 1),
+        );
+        kernel_line_box::launch_unchecked::<f32, R>(
+            client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(input_handle, value_count, 1),
+            ArrayArg::from_raw_parts::<f32>(&prefix_handle, prefix_count, 1),
+            ArrayArg::from_raw_parts::<f32>(&prefix_lo_handle, prefix_count, 1),
+            ArrayArg::from_raw_parts::<f32>(constant_handle, 4, 1),
+            ArrayArg::from_raw_parts::<u32>(&params_handle, params.len(), 1),
+            ArrayArg::from_raw_parts::<f32>(&output_handle, value_count, 1),
+        );
+    }
+    output_handle
+}
+
+// ─── 바이리터럴 ───
+
+#[cube(launch_unchecked)]
+fn kernel_bilateral<F: Float>(
+    input: &Array<F>,
+    constant: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    radius: u32,
+    spatial_coeff: F,
+    range_coeff: F,
+    edge_mode: u32,
+    channel_mask: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let color_channels = select(channels < 3, channels, 3u32);
+        let r = radius as i32;
+
+        let mut weight_sum = F::new(0.);
+        let mut sum_0 = F::new(0.);
+        let mut sum_1 = F::new(0.);
+        let mut sum_2 = F::new(0.);
+        let mut sum_3 = F::new(0.);
+        for dy in 0..radius * 2 + 1 {
+            for dx in 0..radius * 2 + 1 {
+                let ox = dx as i32 - r;
+                let oy = dy as i32 - r;
+                let sx = px as i32 + ox;
+                let sy = py as i32 + oy;
+
+                let mut diff = F::new(0.);
+                for c in 0..color_channels {
+                    let d = load_texel::<F>(input, constant, sx, sy, c, width, height, edge_mode) - input[idx][c];
+                    diff += d * d;
+                }
+                let dist = F::cast_from(ox * ox + oy * oy);
+                // exp(-d²/2σs² - Δ²/2σr²), 계수는 호스트에서 -1/2σ² 로 넘어온다.
+                let w = F::exp(dist * spatial_coeff + diff * range_coeff);
+
+                weight_sum += w;
+                sum_0 += w * load_texel::<F>(input, constant, sx, sy, 0, width, height, edge_mode);
+                if channels > 1 {
+                    sum_1 += w * load_texel::<F>(input, constant, sx, sy, 1, width, height, edge_mode);
+                }
+                if channels > 2 {
+                    sum_2 += w * load_texel::<F>(input, constant, sx, sy, 2, width, height, edge_mode);
+                }
+                if channels > 3 {
+                    sum_3 += w * load_texel::<F>(input, constant, sx, sy, 3, width, height, edge_mode);
+                }
+            }
+        }
+
+        for c in 0..channels {
+            let mut value = input[idx][c];
+            if (channel_mask >> c) & 1 == 1 {
+                let mut sum = sum_0;
+                if c == 1 {
+                    sum = sum_1;
+                } else if c == 2 {
+                    sum = sum_2;
+                } else if c == 3 {
+                    sum = sum_3;
+                }
+                value = sum / weight_sum;
+            }
+            output[idx * channels + c] = value;
+        }
+    }
+}
+
+fn launch_bilateral<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    spatial_sigma: f32,
+    range_sigma: f32,
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+    let radius = bilateral_radius(spatial_sigma);
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let constant_handle = client.create(bytemuck::cast_slice(&options.constant_color.to_f32_array()));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_bilateral::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: radius },
+            ScalarArg { elem: -0.5 / (spatial_sigma * spatial_sigma) },
+            ScalarArg { elem: -0.5 / (range_sigma * range_sigma) },
+            ScalarArg { elem: options.edge_mode.to_u32() },
+            ScalarArg { elem: options.channel_mask },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
@@ -0,0 +1,239 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{skid_image::SKIDImage, skid_pixel_format::SKIDPixelFormat},
+    utils::{gpu_opt::image_cube_config, graphic_fn::{denormalize, normalize}},
+};
+
+/// 채널 패킹에서 출력 채널 하나가 어디서 값을 가져올지.
+///
+/// 원본 채널 값은 `invert` → `in_min..in_max`를 `out_min..out_max`로 재매핑하는 순서로 변환된다.
+/// 상수 채널에는 변환을 적용하지 않는다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDChannelSource {
+    /// 입력 이미지 인덱스 (0..4). 음수이면 `constant`로 채운다.
+    pub source: i32,
+    /// 입력 이미지의 채널 (0 R, 1 G, 2 B, 3 A). 1채널 이미지는 R/G/B 모두 같은 값을 준다.
+    pub channel: u32,
+    pub constant: f32,
+    pub invert: bool,
+    pub in_min: f32,
+    pub in_max: f32,
+    pub out_min: f32,
+    pub out_max: f32,
+}
+
+impl SKIDChannelSource {
+    /// `source` 이미지의 `channel` 값을 그대로 쓴다.
+    pub fn channel(source: usize, channel: u32) -> Self {
+        SKIDChannelSource {
+            source: source as i32,
+            channel,
+            constant: 0.0,
+            invert: false,
+            in_min: 0.0,
+            in_max: 1.0,
+            out_min: 0.0,
+            out_max: 1.0,
+        }
+    }
+    /// 고정 값으로 채운다.
+    pub fn constant(value: f32) -> Self {
+        SKIDChannelSource {
+            source: -1,
+            constant: value,
+            ..SKIDChannelSource::channel(0, 0)
+        }
+    }
+    /// 값을 1 - v 로 뒤집는다. (예: smoothness → roughness)
+    pub fn inverted(mut self) -> Self {
+        self.invert = true;
+        self
+    }
+    /// `in_min..in_max` 범위를 `out_min..out_max`로 선형 재매핑한다.
+    pub fn remapped(mut self, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> Self {
+        self.in_min = in_min;
+        self.in_max = in_max;
+        self.out_min = out_min;
+        self.out_max = out_max;
+        self
+    }
+}
+
+/// "BGRA", "RRR1", "xyz1" 같은 스위즐 문자열을 출력 채널별 소스로 바꾼다.
+///
+/// R/X, G/Y, B/Z, A/W 는 입력 채널, `0`/`1`은 상수이다. 대소문자를 구분하지 않으며 정확히 4글자여야 한다.
+pub fn parse_swizzle(pattern: &str) -> Result<[SKIDChannelSource; 4], String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    if chars.len() != 4 {
+        return Err(format!("Swizzle pattern must have 4 characters, got {:?}", pattern));
+    }
+    let mut mapping = [SKIDChannelSource::constant(0.0); 4];
+    for (slot, ch) in mapping.iter_mut().zip(chars) {
+        *slot = match ch.to_ascii_uppercase() {
+            'R' | 'X' => SKIDChannelSource::channel(0, 0),
+            'G' | 'Y' => SKIDChannelSource::channel(0, 1),
+            'B' | 'Z' => SKIDChannelSource::channel(0, 2),
+            'A' | 'W' => SKIDChannelSource::channel(0, 3),
+            '0' => SKIDChannelSource::constant(0.0),
+            '1' => SKIDChannelSource::constant(1.0),
+            other => return Err(format!("Invalid swizzle character {:?} in {:?}", other, pattern)),
+        };
+    }
+    Ok(mapping)
+}
+
+/// 출력 채널당 파라미터 개수: [source, channel, constant, invert, in_min, in_max, out_min, out_max]
+const PARAMS_PER_CHANNEL: usize = 8;
+
+#[cube]
+fn read_source<F: Float>(
+    src0: &Array<F>,
+    src1: &Array<F>,
+    src2: &Array<F>,
+    src3: &Array<F>,
+    source: F,
+    idx: u32,
+    channel: u32,
+) -> F {
+    let mut value = src0[idx][channel];
+    if source > F::new(2.5) {
+        value = src3[idx][channel];
+    } else if source > F::new(1.5) {
+        value = src2[idx][channel];
+    } else if source > F::new(0.5) {
+        value = src1[idx][channel];
+    }
+    value
+}
+
+#[cube(launch_unchecked)]
+fn kernel_pack_channels<F: Float>(
+    src0: &Array<F>,
+    src1: &Array<F>,
+    src2: &Array<F>,
+    src3: &Array<F>,
+    params: &Array<F>,
+    width: u32,
+    height: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        for c in 0..4u32 {
+            let base = c * 8;
+            let source = params[base];
+            let mut value = params[base + 2];
+            if source >= F::new(0.) {
+                let channel = u32::cast_from(params[base + 1]);
+                value = read_source::<F>(src0, src1, src2, src3, source, idx, channel);
+                if params[base + 3] > F::new(0.5) {
+                    value = F::new(1.) - value;
+                }
+                // 재매핑 범위는 호스트에서 in_min != in_max 로 검증된다.
+                let t = normalize::<F>(value, params[base + 4], params[base + 5]);
+                value = denormalize::<F>(t, params[base + 6], params[base + 7]);
+            }
+            output[idx * 4 + c] = value;
+        }
+    }
+}
+
+/// 최대 4장의 이미지에서 채널을 골라 RGBA 이미지 하나로 합친다. (ORM/MRA 패킹 등)
+///
+/// 채널 값은 색 공간 변환 없이 저장된 값 그대로 옮기며, 결과는 `format`으로 저장되고
+/// 첫 번째 입력의 색 공간/알파 태그를 물려받는다. 모든 입력은 크기가 같아야 한다.
+pub fn pack_channels<R: Runtime>(
+    run_device: &R::Device,
+    sources: &[&SKIDImage],
+    mapping: &[SKIDChannelSource; 4],
+    format: SKIDPixelFormat,
+) -> Result<SKIDImage, String> {
+    if sources.is_empty() || sources.len() > 4 {
+        return Err(format!("pack_channels takes 1 to 4 source images, got {}", sources.len()));
+    }
+    let size = sources[0].get_size();
+    if let Some(other) = sources.iter().find(|img| img.get_size() != size) {
+        return Err(format!("Source sizes do not match ({:?} vs {:?})", size, other.get_size()));
+    }
+    for (c, m) in mapping.iter().enumerate() {
+        if m.source >= sources.len() as i32 {
+            return Err(format!("Output channel {} refers to missing source {}", c, m.source));
+        }
+        if m.source >= 0 && (m.channel > 3 || m.in_min == m.in_max) {
+            return Err(format!("Output channel {} has an invalid channel or remap range", c));
+        }
+    }
+    Ok(launch::<R>(run_device, sources, mapping, format).with_metadata_from(sources[0]))
+}
+
+/// 스위즐 문자열("BGRA", "RRR1" 등)에 따라 채널을 재배치한다.
+/// 결과는 입력과 같은 정밀도의 RGBA 형식이다.
+pub fn swizzle<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    pattern: &str,
+) -> Result<SKIDImage, String> {
+    let mapping = parse_swizzle(pattern)?;
+    pack_channels::<R>(run_device, &[image], &mapping, image.get_pixel_format().rgba_equivalent())
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    sources: &[&SKIDImage],
+    mapping: &[SKIDChannelSource; 4],
+    format: SKIDPixelFormat,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = sources[0].get_size();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let handles: Vec<_> = sources
+        .iter()
+        .map(|img| client.create(bytemuck::cast_slice(&img.get_1d_data_as_f32())))
+        .collect();
+    // 비어 있는 슬롯은 첫 번째 입력을 다시 바인딩한다. (매핑에서 참조되지 않음)
+    let slot = |i: usize| handles.get(i).unwrap_or(&handles[0]);
+
+    let params: Vec<f32> = mapping
+        .iter()
+        .flat_map(|m| {
+            [
+                if m.source < 0 { -1.0 } else { m.source as f32 },
+                m.channel as f32,
+                m.constant,
+                if m.invert { 1.0 } else { 0.0 },
+                m.in_min,
+                m.in_max,
+                m.out_min,
+                m.out_max,
+            ]
+        })
+        .collect();
+    let params_handle = client.create(bytemuck::cast_slice(&params));
+    let output_handle = client.empty(pixel_count * 4 * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_pack_channels::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(slot(0), pixel_count, 4),
+            ArrayArg::from_raw_parts::<f32>(slot(1), pixel_count, 4),
+            ArrayArg::from_raw_parts::<f32>(slot(2), pixel_count, 4),
+            ArrayArg::from_raw_parts::<f32>(slot(3), pixel_count, 4),
+            ArrayArg::from_raw_parts::<f32>(&params_handle, 4 * PARAMS_PER_CHANNEL, 1),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count * 4, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, format, f32::from_bytes(&bytes), 4)
+}
@@ -0,0 +1,235 @@
+use cubecl::{cube, prelude::*, Runtime};
+
+use crate::{
+    model::{skid_alpha_mode::SKIDAlphaMode, skid_color_space::SKIDColorSpace, skid_image::SKIDImage},
+    utils::{
+        gpu_opt::image_cube_config,
+        graphic_fn::{
+            hsl_to_rgb, hsv_to_rgb, lab_to_linear_rgb, linear_rgb_to_lab, linear_to_srgb,
+            rgb_to_hsl, rgb_to_hsv, rgb_to_ycbcr, srgb_to_linear, ycbcr_to_rgb, ColorTriple,
+        },
+    },
+};
+
+// 색 공간 코드는 SKIDColorSpace 의 판별값과 동일하다.
+// 0: sRGB, 1: Linear, 2: HSV, 3: HSL, 4: YCbCr, 5: Lab
+
+/// 임의의 색 공간 값을 linear RGB 로 디코딩한다.
+#[cube]
+fn decode_to_linear<F: Float>(x: F, y: F, z: F, space: u32) -> ColorTriple<F> {
+    let mut r = x;
+    let mut g = y;
+    let mut b = z;
+    if space == 5 {
+        let rgb = lab_to_linear_rgb::<F>(x, y, z);
+        r = rgb.x;
+        g = rgb.y;
+        b = rgb.z;
+    } else if space != 1 {
+        // HSV/HSL/YCbCr 은 감마 인코딩된 sRGB 위에서 정의되므로 먼저 sRGB 로 되돌린다.
+        if space == 2 {
+            let rgb = hsv_to_rgb::<F>(x, y, z);
+            r = rgb.x;
+            g = rgb.y;
+            b = rgb.z;
+        } else if space == 3 {
+            let rgb = hsl_to_rgb::<F>(x, y, z);
+            r = rgb.x;
+            g = rgb.y;
+            b = rgb.z;
+        } else if space == 4 {
+            let rgb = ycbcr_to_rgb::<F>(x, y, z);
+            r = rgb.x;
+            g = rgb.y;
+            b = rgb.z;
+        }
+        r = srgb_to_linear::<F>(r);
+        g = srgb_to_linear::<F>(g);
+        b = srgb_to_linear::<F>(b);
+    }
+    ColorTriple::<F> { x: r, y: g, z: b }
+}
+
+/// linear RGB 값을 목표 색 공간으로 인코딩한다.
+#[cube]
+fn encode_from_linear<F: Float>(r: F, g: F, b: F, space: u32) -> ColorTriple<F> {
+    let mut x = r;
+    let mut y = g;
+    let mut z = b;
+    if space == 5 {
+        let lab = linear_rgb_to_lab::<F>(r, g, b);
+        x = lab.x;
+        y = lab.y;
+        z = lab.z;
+    } else if space != 1 {
+        let sr = linear_to_srgb::<F>(F::max(r, F::new(0.)));
+        let sg = linear_to_srgb::<F>(F::max(g, F::new(0.)));
+        let sb = linear_to_srgb::<F>(F::max(b, F::new(0.)));
+        x = sr;
+        y = sg;
+        z = sb;
+        if space == 2 {
+            let hsv = rgb_to_hsv::<F>(sr, sg, sb);
+            x = hsv.x;
+            y = hsv.y;
+            z = hsv.z;
+        } else if space == 3 {
+            let hsl = rgb_to_hsl::<F>(sr, sg, sb);
+            x = hsl.x;
+            y = hsl.y;
+            z = hsl.z;
+        } else if space == 4 {
+            let ycc = rgb_to_ycbcr::<F>(sr, sg, sb);
+            x = ycc.x;
+            y = ycc.y;
+            z = ycc.z;
+        }
+    }
+    ColorTriple::<F> { x, y, z }
+}
+
+#[cube(launch_unchecked)]
+fn kernel_convert_color_space<F: Float>(
+    input: &Array<F>,
+    width: u32,
+    height: u32,
+    from_space: u32,
+    to_space: u32,
+    in_premultiplied: u32,
+    out_premultiplied: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let alpha = input[idx][3];
+        // 프리멀티플라이드 입력은 전달 함수가 비선형이므로 straight 로 풀어서 변환한 뒤 다시 곱한다.
+        // 입력과 출력의 알파 모드를 따로 받아 알파 변환도 같은 패스에서 처리한다.
+        let mut inv_alpha = F::new(1.);
+        let mut re_alpha = F::new(1.);
+        if in_premultiplied == 1 {
+            // 완전 투명 픽셀은 색 정보가 없으므로 검정으로 둔다.
+            inv_alpha = if alpha > F::new(0.) { F::new(1.) / alpha } else { F::new(0.) };
+        }
+        if out_premultiplied == 1 {
+            re_alpha = alpha;
+        }
+        let linear = decode_to_linear::<F>(
+            input[idx][0] * inv_alpha,
+            input[idx][1] * inv_alpha,
+            input[idx][2] * inv_alpha,
+            from_space,
+        );
+        let encoded = encode_from_linear::<F>(linear.x, linear.y, linear.z, to_space);
+        output[idx * 4] = encoded.x * re_alpha;
+        output[idx * 4 + 1] = encoded.y * re_alpha;
+        output[idx * 4 + 2] = encoded.z * re_alpha;
+        output[idx * 4 + 3] = alpha; // 알파는 그대로
+    }
+}
+
+/// 이미지를 `target` 색 공간으로 변환한다. 이미 같은 공간이면 복사본을 돌려준다.
+pub fn convert_color_space<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: SKIDColorSpace,
+) -> SKIDImage {
+    if image.get_color_space() == target {
+        return image.clone();
+    }
+    launch::<R>(run_device, image, target, image.get_alpha_mode())
+}
+
+/// 색 공간과 알파 모드를 한 번의 GPU 패스로 함께 바꾼다. 둘 다 이미 같으면 복사본을 돌려준다.
+pub fn convert_color_space_and_alpha<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: SKIDColorSpace,
+    target_alpha: SKIDAlphaMode,
+) -> SKIDImage {
+    if image.get_color_space() == target && image.get_alpha_mode() == target_alpha {
+        return image.clone();
+    }
+    launch::<R>(run_device, image, target, target_alpha)
+}
+
+/// sRGB(또는 다른 공간) 이미지를 linear RGB 로 변환한다.
+pub fn to_linear<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    convert_color_space::<R>(run_device, image, SKIDColorSpace::Linear)
+}
+
+/// 이미지를 sRGB 로 변환한다.
+pub fn to_srgb<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    convert_color_space::<R>(run_device, image, SKIDColorSpace::Srgb)
+}
+
+/// 선형 공간에서 동작해야 하는 프로세서(리사이즈, 블렌드 등)가 입력을 준비할 때 사용한다.
+/// linear 가 아닌 입력은 자동으로 변환하며, RGB 계열이 아닌 공간이면 경고를 남긴다.
+/// 반환값의 두 번째 요소는 원래 색 공간으로, 처리 후 `restore_color_space`에 넘긴다.
+pub fn prepare_linear_input<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    processor_name: &str,
+) -> (SKIDImage, SKIDColorSpace) {
+    let original = image.get_color_space();
+    if !original.is_rgb() {
+        log::warn!(
+            "[{}] input is in {:?} space, converting to linear RGB before processing",
+            processor_name, original
+        );
+    }
+    (to_linear::<R>(run_device, image), original)
+}
+
+/// `prepare_linear_input`으로 변환했던 결과를 원래 색 공간으로 되돌린다.
+pub fn restore_color_space<R: Runtime>(
+    run_device: &R::Device,
+    image: SKIDImage,
+    original: SKIDColorSpace,
+) -> SKIDImage {
+    if image.get_color_space() == original {
+        return image;
+    }
+    launch::<R>(run_device, &image, original, image.get_alpha_mode())
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: SKIDColorSpace,
+    target_alpha: SKIDAlphaMode,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let w_u32 = size.width as u32;
+    let h_u32 = size.height as u32;
+
+    let input = image.get_1d_data_as_f32();
+    let pixel_count = input.len() / 4;
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_convert_color_space::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, 4),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: image.get_color_space().to_u32() },
+            ScalarArg { elem: target.to_u32() },
+            ScalarArg { elem: (image.get_alpha_mode() == SKIDAlphaMode::Premultiplied) as u32 },
+            ScalarArg { elem: (target_alpha == SKIDAlphaMode::Premultiplied) as u32 },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), 4)
+        .with_metadata_from(image)
+        .with_color_space(target)
+        .with_alpha_mode(target_alpha)
+}
@@ -0,0 +1,478 @@
+use cubecl::{cube, prelude::*, server::Handle, client::ComputeClient, CubeElement, Runtime};
+
+use crate::{
+    model::{skid_color::SKIDColor, skid_image::{SKIDImage, SKIDRect}},
+    processor::{alpha, region},
+    utils::gpu_opt::{image_cube_config, IMAGE_CUBE_DIM},
+};
+
+/// 이미지 밖의 좌표를 샘플링할 때의 처리 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDEdgeMode {
+    /// 가장자리 픽셀을 반복한다.
+    #[default]
+    Clamp = 0,
+    /// 반대편 가장자리에서 이어진다. (타일링 텍스처용)
+    Wrap = 1,
+    /// 가장자리를 기준으로 거울처럼 반사한다. (가장자리 픽셀 포함)
+    Mirror = 2,
+    /// 고정 색상(`constant_color`)으로 채운다.
+    Constant = 3,
+}
+
+impl SKIDEdgeMode {
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDEdgeMode::Clamp),
+            1 => Some(SKIDEdgeMode::Wrap),
+            2 => Some(SKIDEdgeMode::Mirror),
+            3 => Some(SKIDEdgeMode::Constant),
+            _ => None,
+        }
+    }
+}
+
+/// 컨볼루션 채널 마스크: 모든 채널 (R, G, B, A).
+pub const CHANNEL_MASK_ALL: u32 = 0b1111;
+/// 컨볼루션 채널 마스크: 색상 채널만 (알파 유지).
+pub const CHANNEL_MASK_RGB: u32 = 0b0111;
+
+/// `convolve`의 동작 옵션.
+#[derive(Debug, Clone, Copy)]
+pub struct SKIDConvolutionOptions {
+    pub edge_mode: SKIDEdgeMode,
+    /// `SKIDEdgeMode::Constant`일 때 이미지 밖을 채우는 값. 4채널 입력은 linear + 프리멀티플라이드 기준이다.
+    pub constant_color: SKIDColor,
+    /// 비트 i 가 켜진 채널만 필터링하고, 나머지 채널은 입력 값을 그대로 복사한다.
+    pub channel_mask: u32,
+}
+
+impl Default for SKIDConvolutionOptions {
+    fn default() -> Self {
+        SKIDConvolutionOptions {
+            edge_mode: SKIDEdgeMode::Clamp,
+            constant_color: SKIDColor::new(0.0, 0.0, 0.0, 0.0),
+            channel_mask: CHANNEL_MASK_ALL,
+        }
+    }
+}
+
+/// NxM 컨볼루션 커널. 가중치는 행 우선으로 저장되며 중심은 (width / 2, height / 2)이다.
+///
+/// 가중치는 뒤집지 않고 그대로 적용한다(상관, correlation). 대칭 커널에서는 차이가 없다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SKIDConvolutionKernel {
+    width: usize,
+    height: usize,
+    weights: Vec<f32>,
+}
+
+impl SKIDConvolutionKernel {
+    pub fn new(width: usize, height: usize, weights: Vec<f32>) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("Kernel size must be non-zero".to_string());
+        }
+        if weights.len() != width * height {
+            return Err(format!(
+                "Kernel weight count {} does not match {}x{}",
+                weights.len(), width, height
+            ));
+        }
+        Ok(SKIDConvolutionKernel { width, height, weights })
+    }
+    /// 가로 1D 커널과 세로 1D 커널의 외적으로 커널을 만든다.
+    pub fn from_separable(horizontal: &[f32], vertical: &[f32]) -> Self {
+        let weights = vertical
+            .iter()
+            .flat_map(|&v| horizontal.iter().map(move |&h| v * h))
+            .collect();
+        SKIDConvolutionKernel {
+            width: horizontal.len(),
+            height: vertical.len(),
+            weights,
+        }
+    }
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+    pub fn get_weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    /// 커널이 (세로 벡터) x (가로 벡터)로 분리 가능하면 `(horizontal, vertical)`을 돌려준다.
+    /// 한쪽 크기가 1 이면 이미 1D 커널이므로 `None`을 돌려준다.
+    pub fn separate(&self) -> Option<(Vec<f32>, Vec<f32>)> {
+        if self.width == 1 || self.height == 1 {
+            return None;
+        }
+        // 절댓값이 가장 큰 원소를 기준으로 행/열을 뽑아 rank-1 여부를 검사한다.
+        let (pivot, max_abs) = self
+            .weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (i, w.abs()))
+            .fold((0, 0.0f32), |acc, cur| if cur.1 > acc.1 { cur } else { acc });
+        if max_abs == 0.0 {
+            return None;
+        }
+        let (pivot_x, pivot_y) = (pivot % self.width, pivot / self.width);
+        let pivot_value = self.weights[pivot];
+        let horizontal: Vec<f32> = self.weights[pivot_y * self.width..(pivot_y + 1) * self.width].to_vec();
+        let vertical: Vec<f32> = (0..self.height)
+            .map(|y| self.weights[y * self.width + pivot_x] / pivot_value)
+            .collect();
+
+        let tolerance = max_abs * 1e-5;
+        for (row, &v) in self.weights.chunks_exact(self.width).zip(&vertical) {
+            for (&weight, &h) in row.iter().zip(&horizontal) {
+                if (weight - v * h).abs() > tolerance {
+                    return None;
+                }
+            }
+        }
+        Some((horizontal, vertical))
+    }
+}
+
+/// 이미지 밖 좌표를 가장자리 모드에 따라 이미지 안 좌표로 옮긴다. `Constant` 모드에서 밖이면 -1.
+#[cube]
+pub fn resolve_edge_coord(coord: i32, size: i32, edge_mode: u32) -> i32 {
+    let mut resolved = coord;
+    if coord < 0 || coord >= size {
+        if edge_mode == 0 {
+            resolved = select(coord < 0, 0i32, size - 1);
+        } else if edge_mode == 1 {
+            resolved = ((coord % size) + size) % size;
+        } else if edge_mode == 2 {
+            let period = size * 2;
+            let m = ((coord % period) + period) % period;
+            resolved = if m >= size { period - 1 - m } else { m };
+        } else {
+            resolved = -1;
+        }
+    }
+    resolved
+}
+
+/// 가장자리 모드를 적용해 채널 값 하나를 읽는다. `input`은 채널 수만큼 벡터화되어 있어야 한다.
+#[cube]
+pub fn load_texel<F: Float>(
+    input: &Array<F>,
+    constant: &Array<F>,
+    x: i32,
+    y: i32,
+    c: u32,
+    width: u32,
+    height: u32,
+    edge_mode: u32,
+) -> F {
+    let sx = resolve_edge_coord(x, width as i32, edge_mode);
+    let sy = resolve_edge_coord(y, height as i32, edge_mode);
+    let mut value = constant[c];
+    if sx >= 0 && sy >= 0 {
+        value = input[(sy as u32) * width + (sx as u32)][c];
+    }
+    value
+}
+
+/// 큐브가 담당하는 영역과 커널 반경만큼의 테두리를 공유 메모리 타일에 올린 뒤 컨볼루션한다.
+/// 커널 크기는 공유 메모리 크기를 정하므로 comptime 이다.
+#[cube(launch_unchecked)]
+fn kernel_convolve_shared<F: Float>(
+    input: &Array<F>,
+    weights: &Array<F>,
+    constant: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    edge_mode: u32,
+    channel_mask: u32,
+    output: &mut Array<F>,
+    #[comptime] kernel_w: u32,
+    #[comptime] kernel_h: u32,
+) {
+    let tile_w = comptime!(IMAGE_CUBE_DIM + kernel_w - 1);
+    let tile_h = comptime!(IMAGE_CUBE_DIM + kernel_h - 1);
+    let tile_pixels = comptime!(tile_w * tile_h);
+    let cube_units = comptime!(IMAGE_CUBE_DIM * IMAGE_CUBE_DIM);
+    let load_steps = comptime!(tile_pixels.div_ceil(cube_units));
+    // 채널 수와 관계없이 픽셀당 4칸을 잡는다.
+    let mut tile = SharedMemory::<F>::new(comptime!(tile_pixels * 4));
+
+    let origin_x = (CUBE_POS_X * IMAGE_CUBE_DIM) as i32 - comptime!((kernel_w / 2) as i32);
+    let origin_y = (CUBE_POS_Y * IMAGE_CUBE_DIM) as i32 - comptime!((kernel_h / 2) as i32);
+    let local = UNIT_POS_Y * IMAGE_CUBE_DIM + UNIT_POS_X;
+
+    // 이미지 밖 유닛도 타일 로딩과 동기화에는 참여해야 한다.
+    for step in 0..load_steps {
+        let t = step * cube_units + local;
+        if t < tile_pixels {
+            let tx = origin_x + (t % tile_w) as i32;
+            let ty = origin_y + (t / tile_w) as i32;
+            for c in 0..channels {
+                tile[t * 4 + c] = load_texel::<F>(input, constant, tx, ty, c, width, height, edge_mode);
+            }
+        }
+    }
+    sync_units();
+
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        for c in 0..channels {
+            if (channel_mask >> c) & 1 == 1 {
+                let mut sum = F::new(0.);
+                for j in 0..kernel_h {
+                    for i in 0..kernel_w {
+                        let t = (UNIT_POS_Y + j) * tile_w + UNIT_POS_X + i;
+                        sum += tile[t * 4 + c] * weights[j * kernel_w + i];
+                    }
+                }
+                output[idx * channels + c] = sum;
+            } else {
+                output[idx * channels + c] = input[idx][c];
+            }
+        }
+    }
+}
+
+/// 공유 메모리에 담기지 않는 큰 커널용. 전역 메모리에서 직접 샘플링한다.
+#[cube(launch_unchecked)]
+fn kernel_convolve_direct<F: Float>(
+    input: &Array<F>,
+    weights: &Array<F>,
+    constant: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    edge_mode: u32,
+    channel_mask: u32,
+    kernel_w: u32,
+    kernel_h: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let origin_x = px as i32 - (kernel_w / 2) as i32;
+        let origin_y = py as i32 - (kernel_h / 2) as i32;
+        for c in 0..channels {
+            if (channel_mask >> c) & 1 == 1 {
+                let mut sum = F::new(0.);
+                for j in 0..kernel_h {
+                    for i in 0..kernel_w {
+                        let value = load_texel::<F>(
+                            input,
+                            constant,
+                            origin_x + i as i32,
+                            origin_y + j as i32,
+                            c,
+                            width,
+                            height,
+                            edge_mode,
+                        );
+                        sum += value * weights[j * kernel_w + i];
+                    }
+                }
+                output[idx * channels + c] = sum;
+            } else {
+                output[idx * channels + c] = input[idx][c];
+            }
+        }
+    }
+}
+
+/// 공유 메모리 타일의 최대 크기 (f32 개수). 16KB 로 대부분의 GPU 에서 큐브당 한도 안에 든다.
+const MAX_SHARED_TILE_FLOATS: usize = 4096;
+
+/// 이미지에 NxM 커널을 적용한다.
+///
+/// 분리 가능한 커널은 가로/세로 두 번의 1D 패스로 실행된다.
+/// 4채널 입력은 linear + 프리멀티플라이드 공간에서 필터링한 뒤 원래 형식으로 되돌리고,
+/// 1·2채널 입력(높이맵, 마스크 등)은 값 그대로 필터링한다.
+pub fn convolve<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    kernel: &SKIDConvolutionKernel,
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    match kernel.separate() {
+        Some((horizontal, vertical)) => convolve_separable::<R>(run_device, image, &horizontal, &vertical, options),
+        None => filter_image::<R>(run_device, image, "convolve", |img| launch::<R>(run_device, img, &[ConvolutionPass {
+            weights: kernel.get_weights(),
+            kernel_w: kernel.get_width(),
+            kernel_h: kernel.get_height(),
+        }], options)),
+    }
+}
+
+/// `image`의 `roi` 영역만 `convolve`로 처리해 `target`의 같은 위치에 제자리로 쓴다.
+///
+/// 영역을 커널 반경만큼 `options.edge_mode`로 넓혀 잘라낸 뒤 처리하므로 결과는 전체를 처리한 것과 같다.
+/// 단, `Constant` 모드에서 이미지 밖 테두리는 `constant_color`가 아니라 투명 검정(0)으로 채워진다.
+/// `roi`가 None 이면 전체를 처리한다.
+pub fn convolve_region<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    kernel: &SKIDConvolutionKernel,
+    options: &SKIDConvolutionOptions,
+) -> Result<(), String> {
+    let halo = Ord::max(kernel.get_width(), kernel.get_height()) / 2;
+    region::process_region::<R>(run_device, image, target, roi, halo, options.edge_mode, |region| {
+        convolve::<R>(run_device, region, kernel, options)
+    })
+}
+
+/// 가로 1D 커널과 세로 1D 커널을 차례로 적용한다. (2D 커널을 만들지 않는 분리 가능 컨볼루션)
+pub fn convolve_separable<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    horizontal: &[f32],
+    vertical: &[f32],
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    filter_image::<R>(run_device, image, "convolve", |img| {
+        convolve_separable_values::<R>(run_device, img, horizontal, vertical, options)
+    })
+}
+
+/// `convolve_separable`과 같지만 색 공간/알파 변환 없이 저장된 값 그대로 필터링한다.
+/// 이미 필터 입력으로 준비된 이미지나, 색이 아닌 데이터 맵에 사용한다.
+pub fn convolve_separable_values<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    horizontal: &[f32],
+    vertical: &[f32],
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    launch::<R>(run_device, image, &[
+        ConvolutionPass { weights: horizontal, kernel_w: horizontal.len(), kernel_h: 1 },
+        ConvolutionPass { weights: vertical, kernel_w: 1, kernel_h: vertical.len() },
+    ], options)
+    .with_metadata_from(image)
+}
+
+/// 주변 픽셀을 섞는 필터의 공통 입출력 처리.
+/// 4채널 입력은 `prepare_filter_input`/`restore_filter_output`으로 감싸고, 1·2채널 입력은 그대로 넘긴다.
+pub fn filter_image<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    processor_name: &str,
+    filter: impl FnOnce(&SKIDImage) -> SKIDImage,
+) -> SKIDImage {
+    if image.get_channel_count() < 4 {
+        return filter(image).with_metadata_from(image);
+    }
+    let (prepared, state) = alpha::prepare_filter_input::<R>(run_device, image, processor_name);
+    let filtered = filter(&prepared).with_metadata_from(&prepared);
+    alpha::restore_filter_output::<R>(run_device, filtered, &state)
+}
+
+/// GPU 에서 연속으로 실행할 컨볼루션 패스 하나.
+struct ConvolutionPass<'a> {
+    weights: &'a [f32],
+    kernel_w: usize,
+    kernel_h: usize,
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    passes: &[ConvolutionPass],
+    options: &SKIDConvolutionOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+
+    let input = image.get_1d_data_as_f32_channels();
+    let mut handle = client.create(bytemuck::cast_slice(&input));
+    let mut constant = options.constant_color.to_f32_array();
+
+    // 중간 결과는 GPU 에 둔 채로 다음 패스에 넘긴다.
+    for pass in passes {
+        handle = run_pass::<R>(&client, &handle, image, pass.weights, pass.kernel_w, pass.kernel_h, options, &constant);
+        // 다음 패스에서 이미지 밖 값은 이번 패스를 거친 상수, 즉 constant * sum(weights)에 해당한다.
+        let weight_sum: f32 = pass.weights.iter().sum();
+        constant = constant.map(|v| v * weight_sum);
+    }
+
+    let bytes = client.read_one(handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
+
+/// 컨볼루션 패스 하나를 실행하고 결과 버퍼 핸들을 돌려준다. 입력/출력 모두 GPU 에 머문다.
+#[allow(clippy::too_many_arguments)]
+fn run_pass<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input_handle: &Handle,
+    image: &SKIDImage,
+    weights: &[f32],
+    kernel_w: usize,
+    kernel_h: usize,
+    options: &SKIDConvolutionOptions,
+    constant: &[f32; 4],
+) -> Handle {
+    let size = image.get_size();
+    let w_u32 = size.width as u32;
+    let h_u32 = size.height as u32;
+    let channels = image.get_channel_count();
+    let pixel_count = size.width * size.height;
+
+    let weights_handle = client.create(bytemuck::cast_slice(weights));
+    let constant_handle = client.create(bytemuck::cast_slice(constant));
+    let output_handle = client.empty(pixel_count * channels * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    let tile_dim = IMAGE_CUBE_DIM as usize;
+    let tile_floats = (tile_dim + kernel_w - 1) * (tile_dim + kernel_h - 1) * 4;
+    unsafe {
+        if tile_floats <= MAX_SHARED_TILE_FLOATS {
+            kernel_convolve_shared::launch_unchecked::<f32, R>(
+                client,
+                cube_count,
+                cube_dim,
+                ArrayArg::from_raw_parts::<f32>(input_handle, pixel_count, channels as u8),
+                ArrayArg::from_raw_parts::<f32>(&weights_handle, weights.len(), 1),
+                ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+                ScalarArg { elem: w_u32 },
+                ScalarArg { elem: h_u32 },
+                ScalarArg { elem: channels as u32 },
+                ScalarArg { elem: options.edge_mode.to_u32() },
+                ScalarArg { elem: options.channel_mask },
+                ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count * channels, 1),
+                kernel_w as u32,
+                kernel_h as u32,
+            )
+        } else {
+            kernel_convolve_direct::launch_unchecked::<f32, R>(
+                client,
+                cube_count,
+                cube_dim,
+                ArrayArg::from_raw_parts::<f32>(input_handle, pixel_count, channels as u8),
+                ArrayArg::from_raw_parts::<f32>(&weights_handle, weights.len(), 1),
+                ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+                ScalarArg { elem: w_u32 },
+                ScalarArg { elem: h_u32 },
+                ScalarArg { elem: channels as u32 },
+                ScalarArg { elem: options.edge_mode.to_u32() },
+                ScalarArg { elem: options.channel_mask },
+                ScalarArg { elem: kernel_w as u32 },
+                ScalarArg { elem: kernel_h as u32 },
+                ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count * channels, 1),
+            )
+        }
+    };
+    output_handle
+}
@@ -0,0 +1,271 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_color::SKIDColor,
+        skid_image::{SKIDImage, SKIDRect, SKIDSizeVector2},
+        skid_matrix2::SKIDMatrix2x3,
+    },
+    processor::{
+        convolution::{filter_image, load_texel, SKIDEdgeMode},
+        sampling::{sample_texel, SKIDSampling},
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// `affine_warp` 등 좌표를 옮겨 다시 샘플링하는 변환의 옵션.
+#[derive(Debug, Clone, Copy)]
+pub struct SKIDWarpOptions {
+    pub sampling: SKIDSampling,
+    pub edge_mode: SKIDEdgeMode,
+    /// `SKIDEdgeMode::Constant`일 때 원본 밖을 채우는 값. 4채널 입력은 linear + 프리멀티플라이드 기준이다.
+    pub constant_color: SKIDColor,
+}
+
+impl Default for SKIDWarpOptions {
+    fn default() -> Self {
+        SKIDWarpOptions {
+            sampling: SKIDSampling::Bilinear,
+            edge_mode: SKIDEdgeMode::Constant,
+            constant_color: SKIDColor::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+}
+
+/// `pad`가 각 변에 덧붙이는 픽셀 수.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SKIDPadding {
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+}
+
+impl SKIDPadding {
+    pub fn new(left: usize, top: usize, right: usize, bottom: usize) -> Self {
+        SKIDPadding { left, top, right, bottom }
+    }
+    /// 네 변에 같은 폭을 덧붙인다.
+    pub fn uniform(amount: usize) -> Self {
+        SKIDPadding { left: amount, top: amount, right: amount, bottom: amount }
+    }
+}
+
+/// 출력 (px, py)를 원본 정수 좌표 (ox + xx * px + xy * py, oy + yx * px + yy * py)에서 복사한다.
+/// `coeffs`는 [ox, oy, xx, xy, yx, yy] 순서이며, 원본 밖은 `edge_mode`로 처리한다.
+#[cube(launch_unchecked)]
+fn kernel_remap<F: Float>(
+    input: &Array<F>,
+    constant: &Array<F>,
+    coeffs: &Array<i32>,
+    width: u32,
+    height: u32,
+    out_width: u32,
+    out_height: u32,
+    channels: u32,
+    edge_mode: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < out_width && py < out_height {
+        let x = px as i32;
+        let y = py as i32;
+        let sx = coeffs[0] + coeffs[2] * x + coeffs[3] * y;
+        let sy = coeffs[1] + coeffs[4] * x + coeffs[5] * y;
+        let idx = (py * out_width + px) * channels;
+        for c in 0..channels {
+            output[idx + c] = load_texel::<F>(input, constant, sx, sy, c, width, height, edge_mode);
+        }
+    }
+}
+
+/// 출력 픽셀 중심을 역변환(`inverse`, 행 우선 2x3)으로 원본 좌표에 옮겨 공용 샘플러로 읽는다.
+#[cube(launch_unchecked)]
+fn kernel_affine_warp<F: Float>(
+    input: &Array<F>,
+    constant: &Array<F>,
+    inverse: &Array<F>,
+    width: u32,
+    height: u32,
+    out_width: u32,
+    out_height: u32,
+    channels: u32,
+    edge_mode: u32,
+    sampling: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < out_width && py < out_height {
+        let dx = F::cast_from(px) + F::new(0.5);
+        let dy = F::cast_from(py) + F::new(0.5);
+        let sx = inverse[0] * dx + inverse[1] * dy + inverse[2];
+        let sy = inverse[3] * dx + inverse[4] * dy + inverse[5];
+        let idx = (py * out_width + px) * channels;
+        for c in 0..channels {
+            output[idx + c] = sample_texel::<F>(input, constant, sx, sy, c, width, height, edge_mode, sampling);
+        }
+    }
+}
+
+/// `rect` 영역만 잘라낸다. 값은 변환 없이 그대로 복사한다.
+pub fn crop<R: Runtime>(run_device: &R::Device, image: &SKIDImage, rect: SKIDRect) -> Result<SKIDImage, String> {
+    let size = image.get_size();
+    if rect.is_empty() || !rect.fits_in(size) {
+        return Err(format!("Crop rectangle {:?} is empty or outside of the {}x{} image", rect, size.width, size.height));
+    }
+    let coeffs = [rect.x as i32, rect.y as i32, 1, 0, 0, 1];
+    Ok(remap::<R>(run_device, image, rect.get_size(), coeffs, SKIDEdgeMode::Clamp, SKIDColor::new(0.0, 0.0, 0.0, 0.0)))
+}
+
+/// 이미지 둘레에 `padding`만큼 픽셀을 덧붙인다.
+///
+/// - `SKIDEdgeMode::Constant`: `fill`로 채운다. `fill`은 이미지와 같은 색 공간/알파 모드의 값이다.
+/// - `Clamp`: 가장자리 픽셀을 늘린다. `Wrap`/`Mirror`는 반대편/반사된 픽셀로 채운다.
+pub fn pad<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    padding: SKIDPadding,
+    edge_mode: SKIDEdgeMode,
+    fill: SKIDColor,
+) -> SKIDImage {
+    let size = image.get_size();
+    let out_size = SKIDSizeVector2::new(
+        size.width + padding.left + padding.right,
+        size.height + padding.top + padding.bottom,
+    );
+    let coeffs = [-(padding.left as i32), -(padding.top as i32), 1, 0, 0, 1];
+    remap::<R>(run_device, image, out_size, coeffs, edge_mode, fill)
+}
+
+/// 좌우를 뒤집는다.
+pub fn flip_horizontal<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    let size = image.get_size();
+    let coeffs = [size.width as i32 - 1, 0, -1, 0, 0, 1];
+    remap::<R>(run_device, image, size, coeffs, SKIDEdgeMode::Clamp, SKIDColor::new(0.0, 0.0, 0.0, 0.0))
+}
+
+/// 위아래를 뒤집는다.
+pub fn flip_vertical<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    let size = image.get_size();
+    let coeffs = [0, size.height as i32 - 1, 1, 0, 0, -1];
+    remap::<R>(run_device, image, size, coeffs, SKIDEdgeMode::Clamp, SKIDColor::new(0.0, 0.0, 0.0, 0.0))
+}
+
+/// 행과 열을 바꾼다. (주대각선 기준 반사, 결과 크기는 height x width)
+pub fn transpose<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    let size = image.get_size();
+    let out_size = SKIDSizeVector2::new(size.height, size.width);
+    remap::<R>(run_device, image, out_size, [0, 0, 0, 1, 1, 0], SKIDEdgeMode::Clamp, SKIDColor::new(0.0, 0.0, 0.0, 0.0))
+}
+
+/// 원본 좌표를 출력 좌표로 보내는 아핀 변환 `matrix`로 이미지를 변형해 `out_size` 크기로 만든다.
+///
+/// 좌표는 픽셀 단위이며 픽셀 (i, j)의 중심은 (i + 0.5, j + 0.5)이다. 출력 픽셀마다 역변환한 원본 위치를
+/// `options.sampling`으로 보간하고, 원본 밖은 `options.edge_mode`로 처리한다.
+/// 4채널 입력은 linear + 프리멀티플라이드 공간에서 보간한다. 역변환이 없으면 Err.
+pub fn affine_warp<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    matrix: &SKIDMatrix2x3,
+    out_size: SKIDSizeVector2,
+    options: &SKIDWarpOptions,
+) -> Result<SKIDImage, String> {
+    let inverse = matrix.inverse().ok_or_else(|| format!("Affine matrix {:?} is not invertible", matrix))?;
+    if out_size.is_empty() {
+        return Err("Output size must be non-zero".to_string());
+    }
+    Ok(filter_image::<R>(run_device, image, "affine_warp", |img| {
+        launch_affine_warp::<R>(run_device, img, &inverse, out_size, options)
+    }))
+}
+
+/// 정수 좌표 매핑으로 픽셀을 복사한다. 보간하지 않으므로 색 공간/알파 변환 없이 네이티브 채널 수로 처리한다.
+pub(crate) fn remap<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    out_size: SKIDSizeVector2,
+    coeffs: [i32; 6],
+    edge_mode: SKIDEdgeMode,
+    fill: SKIDColor,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (out_w, out_h) = (out_size.width as u32, out_size.height as u32);
+    let out_len = out_size.width * out_size.height * channels;
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let constant_handle = client.create(bytemuck::cast_slice(&fill.to_f32_array()));
+    let coeffs_handle = client.create(bytemuck::cast_slice(&coeffs));
+    let output_handle = client.empty(out_len * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(out_w, out_h);
+
+    unsafe {
+        kernel_remap::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, size.width * size.height, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+            ArrayArg::from_raw_parts::<i32>(&coeffs_handle, coeffs.len(), 1),
+            ScalarArg { elem: size.width as u32 },
+            ScalarArg { elem: size.height as u32 },
+            ScalarArg { elem: out_w },
+            ScalarArg { elem: out_h },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: edge_mode.to_u32() },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, out_len, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(out_size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+        .with_metadata_from(image)
+}
+
+fn launch_affine_warp<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    inverse: &SKIDMatrix2x3,
+    out_size: SKIDSizeVector2,
+    options: &SKIDWarpOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (out_w, out_h) = (out_size.width as u32, out_size.height as u32);
+    let out_len = out_size.width * out_size.height * channels;
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let constant_handle = client.create(bytemuck::cast_slice(&options.constant_color.to_f32_array()));
+    let inverse_handle = client.create(bytemuck::cast_slice(inverse.to_rows().as_flattened()));
+    let output_handle = client.empty(out_len * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(out_w, out_h);
+
+    unsafe {
+        kernel_affine_warp::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, size.width * size.height, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+            ArrayArg::from_raw_parts::<f32>(&inverse_handle, 6, 1),
+            ScalarArg { elem: size.width as u32 },
+            ScalarArg { elem: size.height as u32 },
+            ScalarArg { elem: out_w },
+            ScalarArg { elem: out_h },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: options.edge_mode.to_u32() },
+            ScalarArg { elem: options.sampling.to_u32() },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, out_len, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(out_size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
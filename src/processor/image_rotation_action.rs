@@ -1,68 +1,60 @@
-use cubecl::{cube, prelude::*};
-
-use crate::model::skid_image::{SKIDImage, SKIDSizeVector2};
-
-
-pub fn launch_image_rotation(
-    run_device: &impl Runtime,
-    rotation_radius: f32,
-    thread_count: Option<usize>,
-) -> SKIDImage {
-    // Implementation for launching image rotation
-    todo!("Implement the launch logic for image rotation");
+use cubecl::Runtime;
+
+use crate::{
+    model::{
+        skid_color::SKIDColor,
+        skid_image::{SKIDImage, SKIDSizeVector2},
+        skid_matrix2::SKIDMatrix2x3,
+        skid_vector2::SKIDVector2,
+    },
+    processor::{
+        convolution::SKIDEdgeMode,
+        geometry::{affine_warp, remap, SKIDWarpOptions},
+    },
+};
+
+/// 이미지 중심을 기준으로 `radians`만큼 회전한다. (양의 각도는 화면에서 시계 방향)
+///
+/// `expand`가 true 이면 회전한 이미지 전체가 들어가도록 결과 크기를 키우고, false 이면 원래 크기를 유지한다.
+/// 원본 밖이 된 영역은 `options.edge_mode`로 채운다. 90도 배수 회전은 `rotate_quarter_turns`가 정확하다.
+pub fn rotate_image<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    radians: f32,
+    expand: bool,
+    options: &SKIDWarpOptions,
+) -> Result<SKIDImage, String> {
+    let size = image.get_size();
+    let (sin, cos) = radians.sin_cos();
+    let out_size = if expand {
+        let (w, h) = (size.width as f32, size.height as f32);
+        // 부동소수점 오차로 한 픽셀이 더 붙지 않도록 약간 깎아서 올림한다.
+        SKIDSizeVector2::new(
+            ((w * cos.abs() + h * sin.abs()) - 1e-3).ceil().max(1.0) as usize,
+            ((w * sin.abs() + h * cos.abs()) - 1e-3).ceil().max(1.0) as usize,
+        )
+    } else {
+        size
+    };
+    let in_center = SKIDVector2::new(size.width as f32 * 0.5, size.height as f32 * 0.5);
+    let out_center = SKIDVector2::new(out_size.width as f32 * 0.5, out_size.height as f32 * 0.5);
+    let matrix = SKIDMatrix2x3::translation(-in_center)
+        .then(&SKIDMatrix2x3::rotation(radians))
+        .then(&SKIDMatrix2x3::translation(out_center));
+    affine_warp::<R>(run_device, image, &matrix, out_size, options)
 }
 
-
-
-#[cube(launch_unchecked)]
-fn kernel_image_rotation<F: Float>(
-    input: Array<F>,
-    width: u32,
-    height: u32,
-    rotation_radius: f32, 
-    output: &mut Array<F>,
-) {
-    // Kernel implementation for image rotation
-    let center_x = F::cast_from(width) / F::new(2.0);
-    let center_y = F::cast_from(height) / F::new(2.0);
-    let radius = F::cast_from(rotation_radius);
-    let cos_angle = F::new(0.0);
-    let sin_angle = F::new(0.0);
-    let width_i = (width) as i32;
-    let height_i = (height) as i32;
-
-    // Perform the rotation
-
-    for y in 0..height {
-        let py = ABSOLUTE_POS_Y + y;
-        for x in 0..width {
-            let px = ABSOLUTE_POS_X + x;
-            let src_x = F::cast_from(px) - center_x;
-            let src_y = F::cast_from(py) - center_y;
-
-            let rotated_x = src_x * cos_angle - src_y * sin_angle;
-            let rotated_y = src_x * sin_angle + src_y * cos_angle;
-
-            let cal_rotated_x = F::cast_from(rotated_x) + center_x;
-            let cal_rotated_y = F::cast_from(rotated_y) + center_y;
-
-            let cal_src_x = i32::cast_from(F::floor(cal_rotated_x));
-            let cal_src_y = i32::cast_from(F::floor(cal_rotated_y));
-
-            if cal_src_x >= 0i32 && cal_src_x < width_i && cal_src_y >= 0i32 && cal_src_y < height_i {
-                let output_idx = (y * width + x) * 4; // Assuming each pixel has 4 components (RGBA)
-                let input_idx = (cal_src_y * width_i + cal_src_x) as u32;
-                
-                let in_r = input[input_idx][0];
-                let in_g = input[input_idx][1];
-                let in_b = input[input_idx][2];
-                let in_a = input[input_idx][3];
-                output[output_idx] = in_r; // R
-                output[output_idx + 1] = in_g; // G
-                output[output_idx + 2] = in_b; // B
-                output[output_idx + 3] = in_a; // A
-
-            }
-        }
-    }
-}
\ No newline at end of file
+/// 90도 단위로 시계 방향 `turns`번 회전한다. 보간 없이 픽셀을 그대로 옮긴다.
+pub fn rotate_quarter_turns<R: Runtime>(run_device: &R::Device, image: &SKIDImage, turns: i32) -> SKIDImage {
+    let size = image.get_size();
+    let (w, h) = (size.width as i32, size.height as i32);
+    let swapped = SKIDSizeVector2::new(size.height, size.width);
+    // [ox, oy, xx, xy, yx, yy]: 출력 (x, y) ← 원본 (ox + xx * x + xy * y, oy + yx * x + yy * y)
+    let (out_size, coeffs) = match turns.rem_euclid(4) {
+        1 => (swapped, [0, h - 1, 0, 1, -1, 0]),
+        2 => (size, [w - 1, h - 1, -1, 0, 0, -1]),
+        3 => (swapped, [w - 1, 0, 0, -1, 1, 0]),
+        _ => (size, [0, 0, 1, 0, 0, 1]),
+    };
+    remap::<R>(run_device, image, out_size, coeffs, SKIDEdgeMode::Clamp, SKIDColor::new(0.0, 0.0, 0.0, 0.0))
+}
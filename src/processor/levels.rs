@@ -0,0 +1,385 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::skid_image::SKIDImage,
+    processor::{convolution::CHANNEL_MASK_RGB, statistics},
+    utils::{
+        gpu_opt::image_cube_config,
+        graphic_fn::{denormalize, normalize},
+    },
+};
+
+/// 레벨 조정 값. 입력 [in_black, in_white]를 0~1 로 펴고 감마를 적용한 뒤 [out_black, out_white]로 옮긴다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDLevels {
+    pub in_black: f32,
+    pub in_white: f32,
+    /// 1.0 보다 크면 중간톤이 밝아진다.
+    pub gamma: f32,
+    pub out_black: f32,
+    pub out_white: f32,
+}
+
+impl Default for SKIDLevels {
+    fn default() -> Self {
+        SKIDLevels { in_black: 0.0, in_white: 1.0, gamma: 1.0, out_black: 0.0, out_white: 1.0 }
+    }
+}
+
+impl SKIDLevels {
+    fn validate(&self) -> Result<(), String> {
+        if self.in_white <= self.in_black {
+            return Err(format!("Levels input range [{}, {}] is empty", self.in_black, self.in_white));
+        }
+        if self.gamma <= 0.0 {
+            return Err(format!("Levels gamma must be positive, got {}", self.gamma));
+        }
+        Ok(())
+    }
+    fn to_params(self) -> [f32; LEVELS_PARAMS] {
+        [self.in_black, self.in_white, 1.0 / self.gamma, self.out_black, self.out_white]
+    }
+}
+
+/// 제어점을 지나는 톤 커브. 제어점 사이는 단조 3차 Hermite(Fritsch–Carlson)로 보간해 오버슈트가 없다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SKIDCurve {
+    points: Vec<(f32, f32)>,
+}
+
+impl SKIDCurve {
+    /// 항등 커브 (0,0)-(1,1).
+    pub fn identity() -> Self {
+        SKIDCurve { points: vec![(0.0, 0.0), (1.0, 1.0)] }
+    }
+    /// 제어점 (x, y)로 커브를 만든다. x 는 0~1 이고 서로 달라야 하며, 순서는 자동으로 정렬된다.
+    pub fn new(mut points: Vec<(f32, f32)>) -> Result<Self, String> {
+        if points.len() < 2 {
+            return Err("A curve needs at least two control points".to_string());
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if points.iter().any(|p| !(0.0..=1.0).contains(&p.0)) {
+            return Err("Curve control point x must be within 0..1".to_string());
+        }
+        if points.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err("Curve control points must have distinct x".to_string());
+        }
+        Ok(SKIDCurve { points })
+    }
+    pub fn get_points(&self) -> &[(f32, f32)] {
+        &self.points
+    }
+    /// 커브 값. 첫/마지막 제어점 밖은 끝점 값으로 유지한다.
+    pub fn evaluate(&self, x: f32) -> f32 {
+        let p = &self.points;
+        let n = p.len();
+        if x <= p[0].0 {
+            return p[0].1;
+        }
+        if x >= p[n - 1].0 {
+            return p[n - 1].1;
+        }
+        let k = p.partition_point(|q| q.0 <= x) - 1;
+        let tangents = self.tangents();
+        let h = p[k + 1].0 - p[k].0;
+        let t = (x - p[k].0) / h;
+        let (t2, t3) = (t * t, t * t * t);
+        (2.0 * t3 - 3.0 * t2 + 1.0) * p[k].1
+            + (t3 - 2.0 * t2 + t) * h * tangents[k]
+            + (-2.0 * t3 + 3.0 * t2) * p[k + 1].1
+            + (t3 - t2) * h * tangents[k + 1]
+    }
+    /// Fritsch–Carlson 단조 접선.
+    fn tangents(&self) -> Vec<f32> {
+        let p = &self.points;
+        let n = p.len();
+        let slopes: Vec<f32> = p.windows(2).map(|w| (w[1].1 - w[0].1) / (w[1].0 - w[0].0)).collect();
+        let mut m = vec![0.0f32; n];
+        m[0] = slopes[0];
+        m[n - 1] = slopes[n - 2];
+        for (i, w) in slopes.windows(2).enumerate() {
+            m[i + 1] = if w[0] * w[1] <= 0.0 { 0.0 } else { (w[0] + w[1]) * 0.5 };
+        }
+        for (i, &slope) in slopes.iter().enumerate() {
+            if slope == 0.0 {
+                m[i] = 0.0;
+                m[i + 1] = 0.0;
+                continue;
+            }
+            let a = m[i] / slope;
+            let b = m[i + 1] / slope;
+            let s = a * a + b * b;
+            if s > 9.0 {
+                let tau = 3.0 / s.sqrt();
+                m[i] = tau * a * slope;
+                m[i + 1] = tau * b * slope;
+            }
+        }
+        m
+    }
+}
+
+impl Default for SKIDCurve {
+    fn default() -> Self {
+        SKIDCurve::identity()
+    }
+}
+
+/// 커브 세트. 색상 채널(R, G, B)에는 `rgb` 커브를 먼저 적용한 뒤 채널별 커브를 적용한다.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SKIDCurves {
+    pub rgb: SKIDCurve,
+    /// R, G, B, A 순서의 채널별 커브
+    pub channels: [SKIDCurve; 4],
+}
+
+/// 자동 대비 옵션.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDAutoContrastOptions {
+    /// 이 비율(0~1) 아래의 픽셀은 검정으로 자른다.
+    pub low_percentile: f32,
+    /// 이 비율(0~1) 위의 픽셀은 흰색으로 자른다.
+    pub high_percentile: f32,
+    /// true 이면 채널마다 따로 범위를 정한다 (색 캐스트 보정). false 이면 색상 채널에 같은 범위를 써서 색조를 유지한다.
+    pub per_channel: bool,
+}
+
+impl Default for SKIDAutoContrastOptions {
+    fn default() -> Self {
+        SKIDAutoContrastOptions { low_percentile: 0.005, high_percentile: 0.995, per_channel: false }
+    }
+}
+
+/// 채널당 레벨 파라미터 수: [in_black, in_white, 1 / gamma, out_black, out_white]
+const LEVELS_PARAMS: usize = 5;
+/// 커브 LUT 의 채널당 항목 수.
+const CURVE_LUT_SIZE: usize = 1024;
+/// 자동 대비 백분위 계산에 쓰는 히스토그램 칸 수.
+const AUTO_CONTRAST_BINS: u32 = 4096;
+
+#[cube(launch_unchecked)]
+fn kernel_levels<F: Float>(
+    input: &Array<F>,
+    params: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    channel_mask: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        for c in 0..channels {
+            let mut value = input[idx][c];
+            if ((channel_mask >> c) & 1) == 1 {
+                let base = c * 5; // LEVELS_PARAMS
+                let t = F::min(F::max(normalize::<F>(value, params[base], params[base + 1]), F::new(0.)), F::new(1.));
+                value = denormalize::<F>(F::powf(t, params[base + 2]), params[base + 3], params[base + 4]);
+            }
+            output[idx * channels + c] = value;
+        }
+    }
+}
+
+/// 채널별 LUT(0~1 구간, 선형 보간)를 적용한다.
+#[cube(launch_unchecked)]
+fn kernel_apply_lut<F: Float>(
+    input: &Array<F>,
+    lut: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    lut_size: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let last = F::cast_from(lut_size - 1);
+        for c in 0..channels {
+            let pos = F::min(F::max(input[idx][c], F::new(0.)), F::new(1.)) * last;
+            let i0 = u32::cast_from(F::floor(pos));
+            let i1 = select(i0 + 1 < lut_size, i0 + 1, lut_size - 1);
+            let frac = pos - F::floor(pos);
+            let base = c * lut_size;
+            output[idx * channels + c] = lut[base + i0] + (lut[base + i1] - lut[base + i0]) * frac;
+        }
+    }
+}
+
+/// 레벨 조정. `channel_mask`의 비트 i 가 켜진 채널만 바꾸고 나머지는 그대로 복사한다.
+///
+/// 저장된 값 그대로(색 공간 변환 없이) 계산하므로 sRGB 이미지는 감마 인코딩된 값 기준, 높이맵은 높이 값 기준이다.
+pub fn levels<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    levels: &SKIDLevels,
+    channel_mask: u32,
+) -> Result<SKIDImage, String> {
+    levels.validate()?;
+    Ok(launch_levels::<R>(run_device, image, &[*levels; 4], channel_mask))
+}
+
+/// 톤 커브를 LUT 로 구워 적용한다. 1·2채널 이미지는 첫 채널들에 `rgb` → 채널별 커브 순서로 적용된다.
+pub fn curves<R: Runtime>(run_device: &R::Device, image: &SKIDImage, curves: &SKIDCurves) -> SKIDImage {
+    let channels = image.get_channel_count();
+    let mut lut = Vec::with_capacity(channels * CURVE_LUT_SIZE);
+    for c in 0..channels {
+        let is_alpha = channels == 4 && c == 3;
+        for i in 0..CURVE_LUT_SIZE {
+            let x = i as f32 / (CURVE_LUT_SIZE - 1) as f32;
+            let y = if is_alpha { x } else { curves.rgb.evaluate(x) };
+            lut.push(curves.channels[c].evaluate(y));
+        }
+    }
+
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let lut_handle = client.create(bytemuck::cast_slice(&lut));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_apply_lut::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&lut_handle, lut.len(), 1),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: CURVE_LUT_SIZE as u32 },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+        .with_metadata_from(image)
+}
+
+/// 백분위 기반 자동 대비. 히스토그램에서 아래/위 백분위 값을 찾아 그 범위를 0~1 로 펴는 레벨을 적용한다.
+/// 알파 채널은 건드리지 않는다. 스캔한 높이맵을 노멀맵 생성 전에 레벨링할 때 사용한다.
+pub fn auto_contrast<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    options: &SKIDAutoContrastOptions,
+) -> Result<SKIDImage, String> {
+    if !(0.0..1.0).contains(&options.low_percentile) || options.high_percentile <= options.low_percentile
+        || options.high_percentile > 1.0
+    {
+        return Err(format!(
+            "Invalid percentiles [{}, {}]",
+            options.low_percentile, options.high_percentile
+        ));
+    }
+    let channels = image.get_channel_count();
+    let color_channels = if channels == 4 { 3 } else { channels };
+
+    // HDR 값도 다루도록 실제 값 범위를 히스토그램 범위로 쓴다.
+    let stats = statistics::compute_statistics::<R>(run_device, image);
+    let range_min = stats.min[..color_channels].iter().copied().fold(f32::INFINITY, f32::min);
+    let range_max = stats.max[..color_channels].iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if range_max <= range_min {
+        return Ok(image.clone()); // 단색 이미지
+    }
+    let bins = AUTO_CONTRAST_BINS as usize;
+    let histogram = statistics::compute_histogram::<R>(run_device, image, AUTO_CONTRAST_BINS, range_min, range_max)?;
+
+    let mut params = [SKIDLevels::default(); 4];
+    if options.per_channel {
+        for (c, level) in params.iter_mut().enumerate().take(color_channels) {
+            let counts = &histogram[c * bins..(c + 1) * bins];
+            *level = percentile_levels(counts, range_min, range_max, options);
+        }
+    } else {
+        let mut merged = vec![0u32; bins];
+        for c in 0..color_channels {
+            for (m, &count) in merged.iter_mut().zip(&histogram[c * bins..(c + 1) * bins]) {
+                *m += count;
+            }
+        }
+        params = [percentile_levels(&merged, range_min, range_max, options); 4];
+    }
+    let mask = if channels == 4 { CHANNEL_MASK_RGB } else { (1u32 << channels) - 1 };
+    Ok(launch_levels::<R>(run_device, image, &params, mask))
+}
+
+fn percentile_levels(counts: &[u32], range_min: f32, range_max: f32, options: &SKIDAutoContrastOptions) -> SKIDLevels {
+    let black = histogram_percentile(counts, range_min, range_max, options.low_percentile);
+    let white = histogram_percentile(counts, range_min, range_max, options.high_percentile);
+    if white <= black {
+        return SKIDLevels { in_black: range_min, in_white: range_max, ..Default::default() };
+    }
+    SKIDLevels { in_black: black, in_white: white, ..Default::default() }
+}
+
+/// 히스토그램에서 누적 비율이 `fraction`에 닿는 값. 칸 안에서는 선형으로 보간한다.
+pub fn histogram_percentile(counts: &[u32], range_min: f32, range_max: f32, fraction: f32) -> f32 {
+    let total: u64 = counts.iter().map(|&c| c as u64).sum();
+    if total == 0 || counts.is_empty() {
+        return range_min;
+    }
+    let target = fraction.clamp(0.0, 1.0) as f64 * total as f64;
+    let bin_width = (range_max - range_min) / counts.len() as f32;
+    let mut accumulated = 0.0f64;
+    for (i, &count) in counts.iter().enumerate() {
+        let next = accumulated + count as f64;
+        if next >= target && count > 0 {
+            let within = ((target - accumulated) / count as f64) as f32;
+            return range_min + (i as f32 + within) * bin_width;
+        }
+        accumulated = next;
+    }
+    range_max
+}
+
+fn launch_levels<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    levels: &[SKIDLevels; 4],
+    channel_mask: u32,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let params: Vec<f32> = levels.iter().flat_map(|l| l.to_params()).collect();
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let params_handle = client.create(bytemuck::cast_slice(&params));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_levels::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&params_handle, params.len(), 1),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: channel_mask },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+        .with_metadata_from(image)
+}
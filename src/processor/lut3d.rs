@@ -0,0 +1,367 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_alpha_mode::SKIDAlphaMode,
+        skid_image::{SKIDImage, SKIDSizeVector2},
+        skid_pixel_format::SKIDPixelFormat,
+    },
+    processor::{alpha, color_space},
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 3D LUT 보간 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDLutInterpolation {
+    /// 격자 셀 8개 꼭짓점의 삼선형 보간
+    Trilinear = 0,
+    /// 셀을 사면체 6개로 나눠 4개 꼭짓점만 섞는다. 회색 축이 정확히 보존되어 그레이딩 툴과 결과가 가깝다.
+    #[default]
+    Tetrahedral = 1,
+}
+
+impl SKIDLutInterpolation {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDLutInterpolation::Trilinear),
+            1 => Some(SKIDLutInterpolation::Tetrahedral),
+            _ => None,
+        }
+    }
+}
+
+/// .cube 파일이 허용하는 최대 격자 크기.
+const MAX_LUT_SIZE: usize = 256;
+
+/// size³ 격자의 3D LUT. 항목은 .cube 파일과 같이 R 이 가장 빠르게 변하는 순서로 저장된다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SKIDLut3D {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    title: Option<String>,
+    data: Vec<[f32; 3]>,
+}
+
+impl SKIDLut3D {
+    /// 입력을 그대로 돌려주는 항등 LUT.
+    pub fn identity(size: usize) -> Result<Self, String> {
+        if !(2..=MAX_LUT_SIZE).contains(&size) {
+            return Err(format!("LUT size must be within 2..={}, got {}", MAX_LUT_SIZE, size));
+        }
+        let step = 1.0 / (size - 1) as f32;
+        let data = (0..size * size * size)
+            .map(|i| [(i % size) as f32 * step, (i / size % size) as f32 * step, (i / (size * size)) as f32 * step])
+            .collect();
+        Ok(SKIDLut3D { size, domain_min: [0.0; 3], domain_max: [1.0; 3], title: None, data })
+    }
+    /// 격자 크기와 R 우선 순서의 항목으로 LUT 를 만든다.
+    pub fn from_data(size: usize, data: Vec<[f32; 3]>) -> Result<Self, String> {
+        if !(2..=MAX_LUT_SIZE).contains(&size) {
+            return Err(format!("LUT size must be within 2..={}, got {}", MAX_LUT_SIZE, size));
+        }
+        if data.len() != size * size * size {
+            return Err(format!("LUT of size {} needs {} entries, got {}", size, size * size * size, data.len()));
+        }
+        Ok(SKIDLut3D { size, domain_min: [0.0; 3], domain_max: [1.0; 3], title: None, data })
+    }
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+    pub fn get_data(&self) -> &[[f32; 3]] {
+        &self.data
+    }
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+    pub fn get_domain(&self) -> ([f32; 3], [f32; 3]) {
+        (self.domain_min, self.domain_max)
+    }
+
+    /// Adobe/Resolve `.cube` 텍스트를 읽는다.
+    /// `TITLE`, `LUT_3D_SIZE`, `DOMAIN_MIN`, `DOMAIN_MAX`를 지원하며 `#` 주석과 빈 줄은 무시한다.
+    pub fn parse_cube(text: &str) -> Result<Self, String> {
+        let mut size = None;
+        let mut title = None;
+        let mut domain_min = [0.0f32; 3];
+        let mut domain_max = [1.0f32; 3];
+        let mut data = Vec::new();
+
+        let parse_triplet = |parts: &[&str], line_no: usize| -> Result<[f32; 3], String> {
+            if parts.len() != 3 {
+                return Err(format!("Line {}: expected 3 values", line_no));
+            }
+            let mut values = [0.0f32; 3];
+            for (v, part) in values.iter_mut().zip(parts) {
+                *v = part.parse().map_err(|_| format!("Line {}: invalid number {:?}", line_no, part))?;
+            }
+            Ok(values)
+        };
+
+        for (i, raw) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts[0] {
+                "TITLE" => title = Some(line["TITLE".len()..].trim().trim_matches('"').to_string()),
+                "LUT_3D_SIZE" => {
+                    let n = parts.get(1).and_then(|v| v.parse::<usize>().ok());
+                    size = Some(n.ok_or_else(|| format!("Line {}: invalid LUT_3D_SIZE", line_no))?);
+                }
+                "DOMAIN_MIN" => domain_min = parse_triplet(&parts[1..], line_no)?,
+                "DOMAIN_MAX" => domain_max = parse_triplet(&parts[1..], line_no)?,
+                "LUT_1D_SIZE" => return Err("1D LUTs are not supported".to_string()),
+                keyword if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    // 알 수 없는 키워드 (LUT_3D_INPUT_RANGE 등 확장)는 무시한다.
+                }
+                _ => data.push(parse_triplet(&parts, line_no)?),
+            }
+        }
+
+        let size = size.ok_or("Missing LUT_3D_SIZE")?;
+        if (0..3).any(|c| domain_max[c] <= domain_min[c]) {
+            return Err("DOMAIN_MAX must be greater than DOMAIN_MIN".to_string());
+        }
+        let mut lut = SKIDLut3D::from_data(size, data)?;
+        lut.domain_min = domain_min;
+        lut.domain_max = domain_max;
+        lut.title = title;
+        Ok(lut)
+    }
+
+    /// `.cube` 텍스트로 쓴다.
+    pub fn to_cube_string(&self) -> String {
+        let mut out = String::with_capacity(self.data.len() * 28 + 128);
+        if let Some(title) = &self.title {
+            out.push_str(&format!("TITLE \"{}\"\n", title));
+        }
+        out.push_str(&format!("LUT_3D_SIZE {}\n", self.size));
+        if self.domain_min != [0.0; 3] || self.domain_max != [1.0; 3] {
+            let [a, b, c] = self.domain_min;
+            out.push_str(&format!("DOMAIN_MIN {} {} {}\n", a, b, c));
+            let [a, b, c] = self.domain_max;
+            out.push_str(&format!("DOMAIN_MAX {} {} {}\n", a, b, c));
+        }
+        for [r, g, b] in &self.data {
+            out.push_str(&format!("{:.6} {:.6} {:.6}\n", r, g, b));
+        }
+        out
+    }
+
+    pub fn load_cube_file(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        SKIDLut3D::parse_cube(&text)
+    }
+
+    pub fn save_cube_file(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.to_cube_string()).map_err(|e| format!("Failed to write {}: {}", path, e))
+    }
+
+    /// `identity_lut_image`로 만든 이미지를 편집 툴에서 그레이딩한 결과로부터 LUT 를 만든다.
+    pub fn from_strip_image(image: &SKIDImage) -> Result<Self, String> {
+        let size = image.get_size();
+        if size.width != size.height * size.height {
+            return Err(format!(
+                "LUT strip must be (n*n) x n pixels, got {}x{}",
+                size.width, size.height
+            ));
+        }
+        let n = size.height;
+        let pixels = image.get_1d_data();
+        let mut data = vec![[0.0f32; 3]; n * n * n];
+        for (i, pixel) in pixels.iter().enumerate() {
+            let (x, g) = (i % size.width, i / size.width);
+            let (r, b) = (x % n, x / n);
+            data[(b * n + g) * n + r] = [pixel.r, pixel.g, pixel.b];
+        }
+        SKIDLut3D::from_data(n, data)
+    }
+}
+
+/// 항등 LUT 스트립 이미지 ((size*size) x size). x = b * size + r, y = g 이다.
+/// 이 이미지를 그레이딩한 뒤 `SKIDLut3D::from_strip_image`로 LUT 를 만들 수 있다.
+/// 8비트 형식은 격자 값이 양자화되므로 `RGBA16` 이상을 권장한다.
+pub fn identity_lut_image(size: usize, format: SKIDPixelFormat) -> Result<SKIDImage, String> {
+    let lut = SKIDLut3D::identity(size)?;
+    let image_size = SKIDSizeVector2::new(size * size, size);
+    let mut data = Vec::with_capacity(size * size * size * 4);
+    for g in 0..size {
+        for b in 0..size {
+            for r in 0..size {
+                let [vr, vg, vb] = lut.data[(b * size + g) * size + r];
+                data.extend_from_slice(&[vr, vg, vb, 1.0]);
+            }
+        }
+    }
+    Ok(SKIDImage::from_f32_data(image_size, format, &data, 4))
+}
+
+#[cube]
+fn lut_fetch<F: Float>(lut: &Array<F>, size: u32, r: u32, g: u32, b: u32, c: u32) -> F {
+    lut[((b * size + g) * size + r) * 3 + c]
+}
+
+#[cube(launch_unchecked)]
+fn kernel_apply_lut3d<F: Float>(
+    input: &Array<F>,
+    lut: &Array<F>,
+    domain: &Array<F>,
+    width: u32,
+    height: u32,
+    size: u32,
+    tetrahedral: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let last = F::cast_from(size - 1);
+        let mut pos_r = (input[idx][0] - domain[0]) / (domain[3] - domain[0]);
+        let mut pos_g = (input[idx][1] - domain[1]) / (domain[4] - domain[1]);
+        let mut pos_b = (input[idx][2] - domain[2]) / (domain[5] - domain[2]);
+        pos_r = F::min(F::max(pos_r, F::new(0.)), F::new(1.)) * last;
+        pos_g = F::min(F::max(pos_g, F::new(0.)), F::new(1.)) * last;
+        pos_b = F::min(F::max(pos_b, F::new(0.)), F::new(1.)) * last;
+
+        // 마지막 격자점에서도 r0 + 1 이 범위 안에 있도록 size - 2 로 자른다.
+        let last_cell = size - 2;
+        let cell_r = u32::cast_from(F::floor(pos_r));
+        let cell_g = u32::cast_from(F::floor(pos_g));
+        let cell_b = u32::cast_from(F::floor(pos_b));
+        let r0 = select(cell_r < last_cell, cell_r, last_cell);
+        let g0 = select(cell_g < last_cell, cell_g, last_cell);
+        let b0 = select(cell_b < last_cell, cell_b, last_cell);
+        let fr = pos_r - F::cast_from(r0);
+        let fg = pos_g - F::cast_from(g0);
+        let fb = pos_b - F::cast_from(b0);
+        let r1 = r0 + 1;
+        let g1 = g0 + 1;
+        let b1 = b0 + 1;
+
+        for c in 0..3u32 {
+            let c000 = lut_fetch::<F>(lut, size, r0, g0, b0, c);
+            let c111 = lut_fetch::<F>(lut, size, r1, g1, b1, c);
+            if tetrahedral == 1 {
+                // 소수부 크기 순서로 사면체를 고른다.
+                if fr > fg {
+                    if fg > fb {
+                        let c100 = lut_fetch::<F>(lut, size, r1, g0, b0, c);
+                        let c110 = lut_fetch::<F>(lut, size, r1, g1, b0, c);
+                        output[idx * 4 + c] = c000 + (c100 - c000) * fr + (c110 - c100) * fg + (c111 - c110) * fb;
+                    } else if fr > fb {
+                        let c100 = lut_fetch::<F>(lut, size, r1, g0, b0, c);
+                        let c101 = lut_fetch::<F>(lut, size, r1, g0, b1, c);
+                        output[idx * 4 + c] = c000 + (c100 - c000) * fr + (c111 - c101) * fg + (c101 - c100) * fb;
+                    } else {
+                        let c001 = lut_fetch::<F>(lut, size, r0, g0, b1, c);
+                        let c101 = lut_fetch::<F>(lut, size, r1, g0, b1, c);
+                        output[idx * 4 + c] = c000 + (c101 - c001) * fr + (c111 - c101) * fg + (c001 - c000) * fb;
+                    }
+                } else if fb > fg {
+                    let c001 = lut_fetch::<F>(lut, size, r0, g0, b1, c);
+                    let c011 = lut_fetch::<F>(lut, size, r0, g1, b1, c);
+                    output[idx * 4 + c] = c000 + (c111 - c011) * fr + (c011 - c001) * fg + (c001 - c000) * fb;
+                } else if fb > fr {
+                    let c010 = lut_fetch::<F>(lut, size, r0, g1, b0, c);
+                    let c011 = lut_fetch::<F>(lut, size, r0, g1, b1, c);
+                    output[idx * 4 + c] = c000 + (c111 - c011) * fr + (c010 - c000) * fg + (c011 - c010) * fb;
+                } else {
+                    let c010 = lut_fetch::<F>(lut, size, r0, g1, b0, c);
+                    let c110 = lut_fetch::<F>(lut, size, r1, g1, b0, c);
+                    output[idx * 4 + c] = c000 + (c110 - c010) * fr + (c010 - c000) * fg + (c111 - c110) * fb;
+                }
+            } else {
+                let c100 = lut_fetch::<F>(lut, size, r1, g0, b0, c);
+                let c010 = lut_fetch::<F>(lut, size, r0, g1, b0, c);
+                let c110 = lut_fetch::<F>(lut, size, r1, g1, b0, c);
+                let c001 = lut_fetch::<F>(lut, size, r0, g0, b1, c);
+                let c101 = lut_fetch::<F>(lut, size, r1, g0, b1, c);
+                let c011 = lut_fetch::<F>(lut, size, r0, g1, b1, c);
+                let x00 = c000 + (c100 - c000) * fr;
+                let x10 = c010 + (c110 - c010) * fr;
+                let x01 = c001 + (c101 - c001) * fr;
+                let x11 = c011 + (c111 - c011) * fr;
+                let y0 = x00 + (x10 - x00) * fg;
+                let y1 = x01 + (x11 - x01) * fg;
+                output[idx * 4 + c] = y0 + (y1 - y0) * fb;
+            }
+        }
+        output[idx * 4 + 3] = input[idx][3];
+    }
+}
+
+/// 3D LUT 로 색을 그레이딩한다. 알파는 유지된다.
+///
+/// LUT 는 저장된 값(sRGB 이미지는 감마 인코딩된 값)에 적용된다. 그레이딩 툴에서 보던 결과와 같도록
+/// 프리멀티플라이드 입력은 straight 로 바꿔 적용한 뒤 되돌리고, RGB 가 아닌 색 공간은 sRGB 로 바꾼다.
+pub fn apply_lut3d<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    lut: &SKIDLut3D,
+    interpolation: SKIDLutInterpolation,
+) -> Result<SKIDImage, String> {
+    if image.get_channel_count() < 4 {
+        return Err("3D LUT grading needs an RGBA image".to_string());
+    }
+    let mut source = image.clone();
+    if !source.get_color_space().is_rgb() {
+        log::warn!(
+            "[apply_lut3d] input is in {:?} space, converting to sRGB first",
+            source.get_color_space()
+        );
+        source = color_space::to_srgb::<R>(run_device, &source);
+    }
+    let alpha_mode = source.get_alpha_mode();
+    let straight = alpha::convert_alpha_mode::<R>(run_device, &source, SKIDAlphaMode::Straight);
+    let graded = launch::<R>(run_device, &straight, lut, interpolation).with_metadata_from(&straight);
+    Ok(alpha::convert_alpha_mode::<R>(run_device, &graded, alpha_mode))
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    lut: &SKIDLut3D,
+    interpolation: SKIDLutInterpolation,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let input = image.get_1d_data_as_f32();
+    let lut_values: Vec<f32> = lut.data.iter().flatten().copied().collect();
+    let domain: Vec<f32> = lut.domain_min.iter().chain(&lut.domain_max).copied().collect();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let lut_handle = client.create(bytemuck::cast_slice(&lut_values));
+    let domain_handle = client.create(bytemuck::cast_slice(&domain));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_apply_lut3d::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, 4),
+            ArrayArg::from_raw_parts::<f32>(&lut_handle, lut_values.len(), 1),
+            ArrayArg::from_raw_parts::<f32>(&domain_handle, 6, 1),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: lut.size as u32 },
+            ScalarArg { elem: (interpolation == SKIDLutInterpolation::Tetrahedral) as u32 },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), 4)
+}
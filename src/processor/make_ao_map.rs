@@ -0,0 +1,161 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{skid_color_space::SKIDColorSpace, skid_image::SKIDImage},
+    processor::{
+        color_space,
+        convolution::{resolve_edge_coord, SKIDEdgeMode},
+        make_normal_map::read_height,
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 높이맵 → AO 맵 생성 옵션.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SKIDAOMapOptions {
+    /// 픽셀마다 탐색할 방향 수.
+    pub ray_count: u32,
+    /// 방향마다 샘플링할 단계 수.
+    pub step_count: u32,
+    /// 탐색 반경 (픽셀).
+    pub radius: f32,
+    /// 높이 1.0 이 몇 픽셀의 높이에 해당하는지. 클수록 골이 깊어진다.
+    pub height_scale: f32,
+    /// 차폐량 배율. 0 이면 AO 가 없고, 1 이면 계산된 차폐를 그대로 쓴다.
+    pub strength: f32,
+    /// true 이면 노멀맵 커널과 같이 가장자리를 반대편으로 이어서 샘플링한다. (타일링 텍스처)
+    pub tileable: bool,
+}
+
+impl Default for SKIDAOMapOptions {
+    fn default() -> Self {
+        SKIDAOMapOptions {
+            ray_count: 8,
+            step_count: 16,
+            radius: 16.0,
+            height_scale: 16.0,
+            strength: 1.0,
+            tileable: true,
+        }
+    }
+}
+
+/// 지평선(horizon) 기반 AO.
+/// 각 방향으로 반경 안을 진행하며 가장 높은 앙각(tan)을 찾고, sin(앙각)을 방향별 차폐량으로 평균낸다.
+/// 방향은 픽셀마다 interleaved gradient noise 로 회전시켜 적은 방향 수에서도 밴딩을 줄인다.
+#[cube(launch_unchecked)]
+fn kernel_make_ao_map<F: Float>(
+    input: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    ray_count: u32,
+    step_count: u32,
+    radius: F,
+    height_scale: F,
+    strength: F,
+    edge_mode: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let center = read_height::<F>(input, idx, channels) * height_scale;
+        let fx = F::cast_from(px);
+        let fy = F::cast_from(py);
+
+        let noise_seed = fx * F::new(0.06711056) + fy * F::new(0.00583715);
+        let noise = F::new(52.982918) * (noise_seed - F::floor(noise_seed));
+        let jitter = noise - F::floor(noise);
+
+        let mut occlusion = F::new(0.);
+        for r in 0..ray_count {
+            let angle = (F::cast_from(r) + jitter) * F::new(6.2831855) / F::cast_from(ray_count);
+            let dir_x = F::cos(angle);
+            let dir_y = F::sin(angle);
+
+            let mut max_slope = F::new(0.);
+            for s in 1..step_count + 1 {
+                let dist = radius * F::cast_from(s) / F::cast_from(step_count);
+                let sx = i32::cast_from(F::floor(fx + dir_x * dist + F::new(0.5)));
+                let sy = i32::cast_from(F::floor(fy + dir_y * dist + F::new(0.5)));
+                let rx = resolve_edge_coord(sx, width as i32, edge_mode);
+                let ry = resolve_edge_coord(sy, height as i32, edge_mode);
+                let sample = read_height::<F>(input, (ry as u32) * width + (rx as u32), channels) * height_scale;
+                max_slope = F::max(max_slope, (sample - center) / dist);
+            }
+            // sin(atan(t)) = t / sqrt(1 + t²)
+            occlusion += max_slope / F::sqrt(F::new(1.) + max_slope * max_slope);
+        }
+
+        let ao = F::new(1.) - strength * occlusion / F::cast_from(ray_count);
+        output[idx] = F::max(F::min(ao, F::new(1.)), F::new(0.));
+    }
+}
+
+/// 높이맵에서 단일 채널 AO 맵을 만든다. (1.0 = 가려지지 않음)
+///
+/// 높이는 `make_normal_map_base`와 같은 방식으로 읽으며(4채널은 그레이스케일, 1·2채널은 첫 채널),
+/// 결과는 입력과 같은 정밀도의 1채널 형식(`R8`/`R16`/`R32F`)에 Linear 로 태그된다.
+pub fn make_ao_map<R: Runtime>(
+    run_device: &R::Device,
+    height_map: &SKIDImage,
+    options: &SKIDAOMapOptions,
+) -> SKIDImage {
+    if !height_map.get_color_space().is_rgb() {
+        log::warn!(
+            "[make_ao_map] height map is in {:?} space, converting to sRGB first",
+            height_map.get_color_space()
+        );
+        let converted = color_space::to_srgb::<R>(run_device, height_map);
+        return launch::<R>(run_device, &converted, options).with_color_space(SKIDColorSpace::Linear);
+    }
+    launch::<R>(run_device, height_map, options).with_color_space(SKIDColorSpace::Linear)
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    height_map: &SKIDImage,
+    options: &SKIDAOMapOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = height_map.get_size();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+    let edge_mode = if options.tileable { SKIDEdgeMode::Wrap } else { SKIDEdgeMode::Clamp };
+
+    let channels = height_map.get_channel_count();
+    let input = height_map.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(pixel_count * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_make_ao_map::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: Ord::max(options.ray_count, 1) },
+            ScalarArg { elem: Ord::max(options.step_count, 1) },
+            ScalarArg { elem: options.radius.max(1.0) },
+            ScalarArg { elem: options.height_scale },
+            ScalarArg { elem: options.strength },
+            ScalarArg { elem: edge_mode.to_u32() },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(
+        size,
+        height_map.get_pixel_format().single_channel_equivalent(),
+        f32::from_bytes(&bytes),
+        1,
+    )
+}
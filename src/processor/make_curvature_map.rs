@@ -0,0 +1,239 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{skid_color_space::SKIDColorSpace, skid_image::SKIDImage},
+    processor::{
+        color_space,
+        convolution::{resolve_edge_coord, SKIDEdgeMode},
+        make_normal_map::read_height,
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 곡률을 계산할 입력의 종류.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDCurvatureSource {
+    /// 높이맵 (`make_normal_map_base`와 같은 방식으로 높이를 읽는다)
+    #[default]
+    HeightMap = 0,
+    /// `make_normal_map_base`가 만든 노멀맵 (RGB = 법선 * 0.5 + 0.5)
+    NormalMap = 1,
+}
+
+/// 곡률 맵의 출력 형식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDCurvatureOutput {
+    /// 1채널. 0.5 가 평면, 0.5 보다 크면 볼록, 작으면 오목.
+    #[default]
+    Signed = 0,
+    /// RGBA. R = 볼록(엣지 마모용), G = 오목(먼지/캐비티용), B = 0, A = 1.
+    Split = 1,
+}
+
+impl SKIDCurvatureSource {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDCurvatureSource::HeightMap),
+            1 => Some(SKIDCurvatureSource::NormalMap),
+            _ => None,
+        }
+    }
+}
+
+impl SKIDCurvatureOutput {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDCurvatureOutput::Signed),
+            1 => Some(SKIDCurvatureOutput::Split),
+            _ => None,
+        }
+    }
+}
+
+/// 곡률 맵 생성 옵션.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SKIDCurvatureOptions {
+    pub source: SKIDCurvatureSource,
+    pub output: SKIDCurvatureOutput,
+    /// 가장 작은 스케일의 샘플 거리 (픽셀).
+    pub radius: f32,
+    /// 스케일 수. k 번째 스케일은 radius * 2^k 거리에서 샘플링하며, 모든 스케일의 평균을 쓴다.
+    pub scale_count: u32,
+    /// 높이 1.0 이 몇 픽셀의 높이에 해당하는지. (높이맵 입력에만 적용)
+    pub height_scale: f32,
+    /// 결과 곡률 배율.
+    pub strength: f32,
+    /// true 이면 가장자리를 반대편으로 이어서 샘플링한다. (타일링 텍스처)
+    pub tileable: bool,
+}
+
+impl Default for SKIDCurvatureOptions {
+    fn default() -> Self {
+        SKIDCurvatureOptions {
+            source: SKIDCurvatureSource::HeightMap,
+            output: SKIDCurvatureOutput::Signed,
+            radius: 1.0,
+            scale_count: 3,
+            height_scale: 8.0,
+            strength: 1.0,
+            tileable: true,
+        }
+    }
+}
+
+/// 가장자리 모드를 적용한 픽셀 인덱스.
+#[cube]
+fn wrapped_index(x: i32, y: i32, width: u32, height: u32, edge_mode: u32) -> u32 {
+    let rx = resolve_edge_coord(x, width as i32, edge_mode);
+    let ry = resolve_edge_coord(y, height as i32, edge_mode);
+    (ry as u32) * width + (rx as u32)
+}
+
+/// 노멀맵 픽셀에서 한 축의 경사(법선 x 또는 y / 법선 z)를 읽는다.
+#[cube]
+fn read_normal_slope<F: Float>(input: &Array<F>, idx: u32, axis: u32) -> F {
+    let n = input[idx][axis] * F::new(2.) - F::new(1.);
+    let nz = F::max(input[idx][2] * F::new(2.) - F::new(1.), F::new(0.001));
+    n / nz
+}
+
+/// 볼록한 곳이 양수가 되는 곡률. 스케일마다 양쪽 경사의 차이(높이맵은 2차 차분)를 거리로 나눠 평균낸다.
+///
+/// `make_normal_map_base`의 법선은 +∇h 방향이므로 높이맵 입력과 노멀맵 입력의 부호가 일치한다.
+#[cube(launch_unchecked)]
+fn kernel_make_curvature_map<F: Float>(
+    input: &Array<F>,
+    params: &Array<u32>,
+    radius: F,
+    height_scale: F,
+    strength: F,
+    output: &mut Array<F>,
+) {
+    // params: [width, height, channels, from_normal, split, scale_count, edge_mode]
+    let width = params[0];
+    let height = params[1];
+    let channels = params[2];
+    let from_normal = params[3];
+    let split = params[4];
+    let scale_count = params[5];
+    let edge_mode = params[6];
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let x = px as i32;
+        let y = py as i32;
+
+        let mut curvature = F::new(0.);
+        let mut scale = radius;
+        for _k in 0..scale_count {
+            let rounded = i32::cast_from(F::floor(scale + F::new(0.5)));
+            let r = select(rounded < 1, 1i32, rounded);
+            let left = wrapped_index(x - r, y, width, height, edge_mode);
+            let right = wrapped_index(x + r, y, width, height, edge_mode);
+            let up = wrapped_index(x, y - r, width, height, edge_mode);
+            let down = wrapped_index(x, y + r, width, height, edge_mode);
+            let dist = F::cast_from(r);
+
+            if from_normal == 1 {
+                // 경사가 줄어드는 곳이 볼록
+                let dx = read_normal_slope::<F>(input, left, 0) - read_normal_slope::<F>(input, right, 0);
+                let dy = read_normal_slope::<F>(input, up, 1) - read_normal_slope::<F>(input, down, 1);
+                curvature += (dx + dy) * F::new(0.5);
+            } else {
+                let center = read_height::<F>(input, idx, channels);
+                let sum = read_height::<F>(input, left, channels)
+                    + read_height::<F>(input, right, channels)
+                    + read_height::<F>(input, up, channels)
+                    + read_height::<F>(input, down, channels);
+                curvature += (center * F::new(4.) - sum) * height_scale / dist;
+            }
+            scale *= F::new(2.);
+        }
+        curvature = curvature * strength / F::cast_from(scale_count);
+
+        if split == 1 {
+            output[idx * 4] = F::min(F::max(curvature, F::new(0.)), F::new(1.));
+            output[idx * 4 + 1] = F::min(F::max(-curvature, F::new(0.)), F::new(1.));
+            output[idx * 4 + 2] = F::new(0.);
+            output[idx * 4 + 3] = F::new(1.);
+        } else {
+            output[idx] = F::min(F::max(F::new(0.5) + curvature * F::new(0.5), F::new(0.)), F::new(1.));
+        }
+    }
+}
+
+/// 높이맵 또는 노멀맵에서 곡률(볼록/오목) 맵을 만든다. 결과는 Linear 로 태그된다.
+///
+/// 노멀맵 입력은 RGB 채널이 필요하다. 3채널 미만 입력은 높이맵으로 취급한다.
+pub fn make_curvature_map<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    options: &SKIDCurvatureOptions,
+) -> SKIDImage {
+    if !image.get_color_space().is_rgb() {
+        log::warn!(
+            "[make_curvature_map] input is in {:?} space, converting to sRGB first",
+            image.get_color_space()
+        );
+        let converted = color_space::to_srgb::<R>(run_device, image);
+        return launch::<R>(run_device, &converted, options).with_color_space(SKIDColorSpace::Linear);
+    }
+    launch::<R>(run_device, image, options).with_color_space(SKIDColorSpace::Linear)
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    options: &SKIDCurvatureOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+    let channels = image.get_channel_count();
+    let from_normal = options.source == SKIDCurvatureSource::NormalMap && channels >= 3;
+    let split = options.output == SKIDCurvatureOutput::Split;
+    let out_channels = if split { 4 } else { 1 };
+    let edge_mode = if options.tileable { SKIDEdgeMode::Wrap } else { SKIDEdgeMode::Clamp };
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(pixel_count * out_channels * core::mem::size_of::<f32>());
+    let params = [
+        w_u32,
+        h_u32,
+        channels as u32,
+        from_normal as u32,
+        split as u32,
+        Ord::max(options.scale_count, 1),
+        edge_mode.to_u32(),
+    ];
+    let params_handle = client.create(bytemuck::cast_slice(&params));
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_make_curvature_map::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ArrayArg::from_raw_parts::<u32>(&params_handle, params.len(), 1),
+            ScalarArg { elem: options.radius.max(1.0) },
+            ScalarArg { elem: options.height_scale },
+            ScalarArg { elem: options.strength },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count * out_channels, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    let format = if split {
+        image.get_pixel_format().rgba_equivalent()
+    } else {
+        image.get_pixel_format().single_channel_equivalent()
+    };
+    SKIDImage::from_f32_data(size, format, f32::from_bytes(&bytes), out_channels)
+}
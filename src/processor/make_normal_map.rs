@@ -1,13 +1,22 @@
-use std::sync::{Arc, Mutex};
-
-use cubecl::{cube, frontend::CompilationArg, prelude::{index, le, Array, ArrayArg, Float, FloatExpand, ScalarArg, ABSOLUTE_POS, ABSOLUTE_POS_X, ABSOLUTE_POS_Y, CUBE_CLUSTER_DIM_X, CUBE_CLUSTER_DIM_Y, CUBE_COUNT_Y, UNIT_POS_PLANE, UNIT_POS_X, UNIT_POS_Y}, terminate, CubeCount, CubeDim, CubeElement, Runtime};
-use crate::{model::{skid_color::SKIDColor, skid_image::SKIDImage}, utils::graphic_fn::{compute_grayscale, normal_vector_size}};
+use cubecl::{cube, frontend::CompilationArg, prelude::{select, Array, ArrayArg, Float, FloatExpand, ScalarArg, ABSOLUTE_POS_X, ABSOLUTE_POS_Y, CUBE_CLUSTER_DIM_X, CUBE_CLUSTER_DIM_Y}, terminate, CubeCount, CubeDim, CubeElement, Runtime};
+use crate::{model::{skid_color_space::SKIDColorSpace, skid_image::{SKIDImage, SKIDRect}}, processor::{color_space, convolution::SKIDEdgeMode, region}, utils::graphic_fn::{compute_grayscale, normal_vector_size}};
+
+/// 높이값을 읽는다. 4채널 입력은 그레이스케일, 1·2채널 입력은 첫 채널을 그대로 높이로 사용한다.
+#[cube]
+pub fn read_height<F: Float>(input: &Array<F>, idx: u32, channels: u32) -> F {
+    if channels < 4 {
+        input[idx][0]
+    } else {
+        compute_grayscale::<F>(input[idx][0], input[idx][1], input[idx][2])
+    }
+}
 
 #[cube(launch_unchecked)]
 fn kernel_make_normal_map<F: Float>(
     input: &Array<F>,
     width: u32,
     height: u32,
+    channels: u32,
     x_factor: F,
     y_factor: F,
     output: &mut Array<F>,
@@ -19,28 +28,16 @@ fn kernel_make_normal_map<F: Float>(
             let idx = py * width + px;
 
             let up_y = if py > 0 { py - 1 } else { height - 1 };
-            let up_r = input[up_y* width + px][0]; // R
-            let up_g = input[up_y* width + px][1]; // G
-            let up_b = input[up_y* width + px][2]; // B
-            let d_up = compute_grayscale::<F>(up_r, up_g, up_b);
+            let d_up = read_height::<F>(input, up_y * width + px, channels);
 
-            let down_y = if py < height - 1 { py + 1 } else { 0u32.into() };
-            let down_r = input[down_y * width + px][0]; // R
-            let down_g = input[down_y * width + px][1]; // G
-            let down_b = input[down_y * width + px][2]; // B
-            let d_down = compute_grayscale::<F>(down_r, down_g, down_b);
+            let down_y = select(py < height - 1, py + 1, 0u32);
+            let d_down = read_height::<F>(input, down_y * width + px, channels);
 
             let left_x = if px > 0 { px - 1 } else { width - 1 };
-            let left_r = input[py * width + left_x][0]; // R
-            let left_g = input[py * width + left_x][1]; // G
-            let left_b = input[py * width + left_x][2]; // B
-            let d_left = compute_grayscale::<F>(left_r, left_g, left_b);
+            let d_left = read_height::<F>(input, py * width + left_x, channels);
 
-            let right_x = if px < width - 1 { px + 1 } else { 0u32.into() };
-            let right_r = input[py * width + right_x][0]; // R
-            let right_g = input[py * width + right_x][1]; // G
-            let right_b = input[py * width + right_x][2]; // B
-            let d_right = compute_grayscale::<F>(right_r, right_g, right_b);
+            let right_x = select(px < width - 1, px + 1, 0u32);
+            let d_right = read_height::<F>(input, py * width + right_x, channels);
 
             let d_x = (d_right - d_left) * x_factor;
             let d_y = (d_down - d_up) * y_factor;
@@ -61,7 +58,7 @@ fn kernel_make_normal_map<F: Float>(
             let n_r = normal_vector_size::<F>(normalized_x , F::new(-0.), F::new(1.));
             let n_g = normal_vector_size::<F>(normalized_y , F::new(-0.), F::new(1.));
             let n_b = normal_vector_size::<F>(normalized_z , F::new(-0.), F::new(1.));
-            output[idx*4+0] = n_r; // R
+            output[idx*4] = n_r; // R
             output[idx*4+1] = n_g; // G
             output[idx*4+2] = n_b; // B
             output[idx*4+3] = F::new(1.0); // A
@@ -75,14 +72,42 @@ pub fn make_normal_map_base<R:Runtime>(
     x_factor: Option<f32>,
     y_factor: Option<f32>,
 ) -> SKIDImage {
+    // 높이값은 그레이스케일로 읽으므로 RGB 계열 공간이어야 한다.
+    // 노멀맵 자체는 감마가 없는 데이터이므로 Linear 로 태그한다.
+    if !original_image.get_color_space().is_rgb() {
+        log::warn!(
+            "[make_normal_map] height map is in {:?} space, converting to sRGB first",
+            original_image.get_color_space()
+        );
+        let converted = color_space::to_srgb::<R>(&runtime, original_image);
+        return launch::<R>(&runtime, &converted, x_factor, y_factor)
+            .with_color_space(SKIDColorSpace::Linear);
+    }
     launch::<R>(
         &runtime,
         original_image,
         x_factor,
         y_factor,
-    )
+    ).with_color_space(SKIDColorSpace::Linear)
 }
 
+/// 높이맵의 `roi` 영역만 다시 계산해 기존 노멀맵 `normal_map`의 같은 위치에 제자리로 쓴다.
+///
+/// 커널은 상하좌우 1픽셀을 읽고 가장자리에서 반대편으로 감기므로, 1픽셀 테두리를 감아서 붙인 뒤 계산한다.
+/// 따라서 결과는 전체를 다시 계산한 것과 같다. `roi`가 None 이면 전체를 다시 계산한다.
+pub fn make_normal_map_region<R: Runtime>(
+    runtime: R::Device,
+    height_map: &SKIDImage,
+    normal_map: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    x_factor: Option<f32>,
+    y_factor: Option<f32>,
+) -> Result<(), String> {
+    let device = runtime.clone();
+    region::process_region::<R>(&device, height_map, normal_map, roi, 1, SKIDEdgeMode::Wrap, |region| {
+        make_normal_map_base::<R>(runtime, region, x_factor, y_factor)
+    })
+}
 
 fn launch<T: Runtime>(
     run_device: &T::Device,
@@ -100,38 +125,40 @@ fn launch<T: Runtime>(
     let w_u32 = w_size as u32;
     let h_u32 = h_size as u32;
     let (x_count, y_count, _z_count) = T::max_cube_count();
-    let block_x = (w_u32 + x_count - 1) / x_count;
+    let block_x = w_u32.div_ceil(x_count);
     let threads_x = if w_u32 < x_count { w_u32 } else { x_count };
-    let block_y = (h_u32 + y_count - 1) / y_count;
+    let block_y = h_u32.div_ceil(y_count);
     let threads_y = if h_u32 < y_count { h_u32 } else { y_count };
 
 
-    let input = original_image.get_1d_data_as_f32();
+    // 단일 채널 높이맵은 RGBA 로 넓히지 않고 네이티브 채널 수 그대로 업로드한다.
+    let channels = original_image.get_channel_count();
+    let input = original_image.get_1d_data_as_f32_channels();
     let input_handle = client.create(bytemuck::cast_slice(&input));
 
-    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
-    let pixel_count = input.len() / 4 ; // Assuming each color has 4 components (RGBA)
+    let pixel_count = input.len() / channels;
+    let output_handle = client.empty(pixel_count * 4 * core::mem::size_of::<f32>());
     
     unsafe{
         kernel_make_normal_map::launch_unchecked::<f32, T>(
             &client,
-            CubeCount::Static(threads_x as u32, threads_y as u32, 1),
-            CubeDim::new(block_x as u32, block_y as u32, 1),
-            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, 4),
-            ScalarArg::from(cubecl::frontend::ScalarArg { elem: w_u32 }),
-            ScalarArg::from(cubecl::frontend::ScalarArg { elem: h_u32 }),
-            ScalarArg::from(cubecl::frontend::ScalarArg { elem:x_factor }),
-            ScalarArg::from(cubecl::frontend::ScalarArg { elem:y_factor }),
+            CubeCount::Static(threads_x, threads_y, 1),
+            CubeDim::new(block_x, block_y, 1),
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: x_factor },
+            ScalarArg { elem: y_factor },
             ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count, 1),
         )
     };
     let bytes = client.read_one(output_handle.binding());
-    let output = f32::from_bytes(&bytes);
-    let output_colors: Vec<SKIDColor> = output.chunks(4)
-        .map(|chunk| SKIDColor::from_f32_array(chunk.try_into().unwrap()))
-        .collect();
-    SKIDImage::from_1d_data(
-        original_image.get_size(), 
-        output_colors
+    // 노멀맵은 항상 RGBA 이며, 입력과 같은 정밀도의 형식으로 저장한다.
+    SKIDImage::from_f32_data(
+        original_image.get_size(),
+        original_image.get_pixel_format().rgba_equivalent(),
+        f32::from_bytes(&bytes),
+        4,
     )
 }
@@ -1,9 +1,31 @@
 pub mod image_sync_action;
 pub mod make_normal_map;
+pub mod make_ao_map;
+pub mod make_curvature_map;
 pub mod resize_image;
 pub mod example_generator;
 pub mod image_synthesis_action;
 pub mod image_rotation_action;
+pub mod color_space;
+pub mod alpha;
+pub mod convolution;
+pub mod blur;
+pub mod sharpen;
+pub mod channel_pack;
+pub mod seamless;
+pub mod noise;
+pub mod test_pattern;
+pub mod statistics;
+pub mod levels;
+pub mod lut3d;
+pub mod tone_map;
+pub mod palette;
+pub mod sampling;
+pub mod geometry;
+pub mod perspective;
+pub mod region;
+pub mod tiling;
+pub mod atlas;
 
 
 pub enum ProcessorError {
@@ -0,0 +1,454 @@
+use std::f32::consts::SQRT_2;
+
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_color_space::SKIDColorSpace,
+        skid_image::{SKIDImage, SKIDSizeVector2},
+        skid_pixel_format::SKIDPixelFormat,
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 기본 노이즈 함수.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDNoiseType {
+    /// 격자점 난수를 quintic 보간한 값 노이즈
+    Value = 0,
+    /// 격자점 기울기 기반 Perlin 노이즈
+    #[default]
+    Perlin = 1,
+    /// 2D Simplex 노이즈
+    Simplex = 2,
+    /// 가장 가까운 특징점까지의 거리(F1) 기반 Worley(셀룰러) 노이즈
+    Worley = 3,
+}
+
+/// 옥타브를 합치는 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDFractalType {
+    /// 옥타브 1개만 사용
+    None = 0,
+    /// fBm: 옥타브 합
+    #[default]
+    Fbm = 1,
+    /// 리지드: (1 - |n|)² 합. 산맥 같은 날카로운 능선
+    Ridged = 2,
+    /// 터뷸런스: |n| 합
+    Turbulence = 3,
+}
+
+impl SKIDNoiseType {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDNoiseType::Value),
+            1 => Some(SKIDNoiseType::Perlin),
+            2 => Some(SKIDNoiseType::Simplex),
+            3 => Some(SKIDNoiseType::Worley),
+            _ => None,
+        }
+    }
+}
+
+impl SKIDFractalType {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDFractalType::None),
+            1 => Some(SKIDFractalType::Fbm),
+            2 => Some(SKIDFractalType::Ridged),
+            3 => Some(SKIDFractalType::Turbulence),
+            _ => None,
+        }
+    }
+}
+
+/// 절차적 노이즈 생성 옵션.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SKIDNoiseOptions {
+    pub noise_type: SKIDNoiseType,
+    pub fractal: SKIDFractalType,
+    /// 같은 시드는 같은 결과를 만든다.
+    pub seed: u32,
+    /// 가로 방향 셀 수 (첫 옥타브). 세로는 종횡비에 맞춰 정사각형 셀이 되도록 정한다.
+    pub frequency: f32,
+    /// 옥타브 수 (`SKIDFractalType::None`이면 무시)
+    pub octaves: u32,
+    /// 옥타브마다 곱하는 주파수 배율
+    pub lacunarity: f32,
+    /// 옥타브마다 곱하는 진폭 배율
+    pub gain: f32,
+    /// 도메인 워핑 세기 (첫 옥타브 셀 단위). 0 이면 워핑하지 않는다.
+    pub warp_strength: f32,
+    /// 워핑 필드의 주파수 배율 (`frequency` 기준)
+    pub warp_frequency: f32,
+    /// true 이면 결과가 가로/세로로 이음매 없이 반복된다.
+    /// 옥타브마다 셀 수를 정수로 반올림하므로 주파수가 약간 달라질 수 있다.
+    pub tileable: bool,
+}
+
+impl Default for SKIDNoiseOptions {
+    fn default() -> Self {
+        SKIDNoiseOptions {
+            noise_type: SKIDNoiseType::Perlin,
+            fractal: SKIDFractalType::Fbm,
+            seed: 0,
+            frequency: 8.0,
+            octaves: 5,
+            lacunarity: 2.0,
+            gain: 0.5,
+            warp_strength: 0.0,
+            warp_frequency: 1.0,
+            tileable: true,
+        }
+    }
+}
+
+/// 격자 좌표를 음수에서도 부호 없는 해시 입력으로 쓰기 위한 오프셋.
+const LATTICE_OFFSET: i32 = 32768;
+
+/// 정수 격자점 + 시드 해시.
+#[cube]
+fn hash_lattice(x: i32, y: i32, seed: u32) -> u32 {
+    let ux = u32::cast_from(x + LATTICE_OFFSET);
+    let uy = u32::cast_from(y + LATTICE_OFFSET);
+    let mut h = ux * 374761393u32 + uy * 668265263u32 + seed * 2246822519u32;
+    h = (h ^ (h >> 13)) * 1274126177u32;
+    h ^ (h >> 16)
+}
+
+/// 해시를 0~1 실수로.
+#[cube]
+fn hash_to_unit<F: Float>(h: u32) -> F {
+    F::cast_from(h & 0xFFFFFFu32) / F::new(16777216.)
+}
+
+/// 주기 `period`로 격자 좌표를 감는다. (period == 0 이면 감지 않음)
+#[cube]
+fn wrap_lattice(i: i32, period: u32) -> i32 {
+    let mut result = i;
+    if period > 0 {
+        let p = period as i32;
+        result = ((i % p) + p) % p;
+    }
+    result
+}
+
+#[cube]
+fn quintic<F: Float>(t: F) -> F {
+    t * t * t * (t * (t * F::new(6.) - F::new(15.)) + F::new(10.))
+}
+
+#[cube]
+fn lattice_value<F: Float>(ix: i32, iy: i32, seed: u32, period_x: u32, period_y: u32) -> F {
+    hash_to_unit::<F>(hash_lattice(wrap_lattice(ix, period_x), wrap_lattice(iy, period_y), seed)) * F::new(2.)
+        - F::new(1.)
+}
+
+/// 격자점 기울기(해시로 정한 단위 벡터)와 오프셋의 내적.
+#[cube]
+fn lattice_gradient<F: Float>(ix: i32, iy: i32, dx: F, dy: F, seed: u32, period_x: u32, period_y: u32) -> F {
+    let h = hash_lattice(wrap_lattice(ix, period_x), wrap_lattice(iy, period_y), seed);
+    let angle = hash_to_unit::<F>(h) * F::new(6.2831855);
+    F::cos(angle) * dx + F::sin(angle) * dy
+}
+
+/// 값 노이즈 (-1~1).
+#[cube]
+fn value_noise<F: Float>(x: F, y: F, seed: u32, period_x: u32, period_y: u32) -> F {
+    let fx = F::floor(x);
+    let fy = F::floor(y);
+    let ix = i32::cast_from(fx);
+    let iy = i32::cast_from(fy);
+    let u = quintic::<F>(x - fx);
+    let v = quintic::<F>(y - fy);
+    let a = lattice_value::<F>(ix, iy, seed, period_x, period_y);
+    let b = lattice_value::<F>(ix + 1, iy, seed, period_x, period_y);
+    let c = lattice_value::<F>(ix, iy + 1, seed, period_x, period_y);
+    let d = lattice_value::<F>(ix + 1, iy + 1, seed, period_x, period_y);
+    let top = a + (b - a) * u;
+    let bottom = c + (d - c) * u;
+    top + (bottom - top) * v
+}
+
+/// Perlin 노이즈 (약 -1~1).
+#[cube]
+fn perlin_noise<F: Float>(x: F, y: F, seed: u32, period_x: u32, period_y: u32) -> F {
+    let fx = F::floor(x);
+    let fy = F::floor(y);
+    let ix = i32::cast_from(fx);
+    let iy = i32::cast_from(fy);
+    let rx = x - fx;
+    let ry = y - fy;
+    let u = quintic::<F>(rx);
+    let v = quintic::<F>(ry);
+    let a = lattice_gradient::<F>(ix, iy, rx, ry, seed, period_x, period_y);
+    let b = lattice_gradient::<F>(ix + 1, iy, rx - F::new(1.), ry, seed, period_x, period_y);
+    let c = lattice_gradient::<F>(ix, iy + 1, rx, ry - F::new(1.), seed, period_x, period_y);
+    let d = lattice_gradient::<F>(ix + 1, iy + 1, rx - F::new(1.), ry - F::new(1.), seed, period_x, period_y);
+    let top = a + (b - a) * u;
+    let bottom = c + (d - c) * u;
+    // 2D Perlin 의 이론적 최대값은 √0.5 이므로 -1~1 로 넓힌다.
+    (top + (bottom - top) * v) * F::new(SQRT_2)
+}
+
+#[cube]
+fn simplex_corner<F: Float>(ix: i32, iy: i32, dx: F, dy: F, seed: u32) -> F {
+    let t = F::new(0.5) - dx * dx - dy * dy;
+    let mut result = F::new(0.);
+    if t > F::new(0.) {
+        let t2 = t * t;
+        result = t2 * t2 * lattice_gradient::<F>(ix, iy, dx, dy, seed, 0, 0);
+    }
+    result
+}
+
+/// 2D Simplex 노이즈 (약 -1~1). 기울어진 격자는 축 방향 주기로 감을 수 없으므로 주기를 받지 않는다.
+#[cube]
+fn simplex_noise<F: Float>(x: F, y: F, seed: u32) -> F {
+    // F2 = (√3 - 1) / 2, G2 = (3 - √3) / 6
+    let skew = (x + y) * F::new(0.3660254);
+    let fi = F::floor(x + skew);
+    let fj = F::floor(y + skew);
+    let unskew = (fi + fj) * F::new(0.21132487);
+    let x0 = x - (fi - unskew);
+    let y0 = y - (fj - unskew);
+
+    let mut i1 = 0i32;
+    let mut j1 = 1i32;
+    if x0 > y0 {
+        i1 = 1;
+        j1 = 0;
+    }
+    let x1 = x0 - F::cast_from(i1) + F::new(0.21132487);
+    let y1 = y0 - F::cast_from(j1) + F::new(0.21132487);
+    let x2 = x0 - F::new(1.) + F::new(2.) * F::new(0.21132487);
+    let y2 = y0 - F::new(1.) + F::new(2.) * F::new(0.21132487);
+
+    let i = i32::cast_from(fi);
+    let j = i32::cast_from(fj);
+    let n = simplex_corner::<F>(i, j, x0, y0, seed)
+        + simplex_corner::<F>(i + i1, j + j1, x1, y1, seed)
+        + simplex_corner::<F>(i + 1, j + 1, x2, y2, seed);
+    n * F::new(70.)
+}
+
+/// 주기 (period_x, period_y)로 반복되는 Simplex 노이즈.
+/// 한 주기 떨어진 네 샘플을 위치에 따라 쌍선형으로 섞어 경계에서 값이 일치하게 한다.
+/// (경계에서 먼 곳은 대비가 약간 줄어든다)
+#[cube]
+fn simplex_noise_tiled<F: Float>(x: F, y: F, seed: u32, period_x: u32, period_y: u32) -> F {
+    let px = F::cast_from(period_x);
+    let py = F::cast_from(period_y);
+    let wx = x - F::floor(x / px) * px;
+    let wy = y - F::floor(y / py) * py;
+    let s = wx / px;
+    let t = wy / py;
+    let a = simplex_noise::<F>(wx, wy, seed);
+    let b = simplex_noise::<F>(wx - px, wy, seed);
+    let c = simplex_noise::<F>(wx, wy - py, seed);
+    let d = simplex_noise::<F>(wx - px, wy - py, seed);
+    let top = a * (F::new(1.) - s) + b * s;
+    let bottom = c * (F::new(1.) - s) + d * s;
+    // 독립 샘플을 섞으면 분산이 줄어드므로 중앙(s = t = 0.5, 가중치 제곱합 0.25)까지 고려해 보정한다.
+    let weight_sq = ((F::new(1.) - s) * (F::new(1.) - s) + s * s) * ((F::new(1.) - t) * (F::new(1.) - t) + t * t);
+    (top * (F::new(1.) - t) + bottom * t) / F::sqrt(weight_sq)
+}
+
+/// Worley F1 노이즈 (-1~1, 특징점 위에서 -1).
+#[cube]
+fn worley_noise<F: Float>(x: F, y: F, seed: u32, period_x: u32, period_y: u32) -> F {
+    let fx = F::floor(x);
+    let fy = F::floor(y);
+    let ix = i32::cast_from(fx);
+    let iy = i32::cast_from(fy);
+    let mut nearest = F::new(8.);
+    for oy in 0..3i32 {
+        for ox in 0..3i32 {
+            let cx = ix + ox - 1;
+            let cy = iy + oy - 1;
+            let wx = wrap_lattice(cx, period_x);
+            let wy = wrap_lattice(cy, period_y);
+            let h = hash_lattice(wx, wy, seed);
+            let fpx = F::cast_from(cx) + hash_to_unit::<F>(h);
+            let fpy = F::cast_from(cy) + hash_to_unit::<F>(hash_lattice(wx, wy, seed ^ 0x9E3779B9u32));
+            let dx = fpx - x;
+            let dy = fpy - y;
+            nearest = F::min(nearest, dx * dx + dy * dy);
+        }
+    }
+    F::min(F::sqrt(nearest), F::new(1.)) * F::new(2.) - F::new(1.)
+}
+
+#[cube]
+fn base_noise<F: Float>(x: F, y: F, noise_type: u32, seed: u32, period_x: u32, period_y: u32) -> F {
+    let mut n = F::new(0.);
+    if noise_type == 0 {
+        n = value_noise::<F>(x, y, seed, period_x, period_y);
+    } else if noise_type == 1 {
+        n = perlin_noise::<F>(x, y, seed, period_x, period_y);
+    } else if noise_type == 2 {
+        if period_x > 0 {
+            n = simplex_noise_tiled::<F>(x, y, seed, period_x, period_y);
+        } else {
+            n = simplex_noise::<F>(x, y, seed);
+        }
+    } else if noise_type == 3 {
+        n = worley_noise::<F>(x, y, seed, period_x, period_y);
+    }
+    n
+}
+
+/// 옥타브 k 의 셀 수. 타일링할 때는 정수로 반올림한다.
+#[cube]
+fn octave_cells<F: Float>(base: F, lacunarity: F, k: u32, tileable: u32) -> F {
+    let cells = base * F::powf(lacunarity, F::cast_from(k));
+    let mut result = cells;
+    if tileable == 1 {
+        result = F::max(F::floor(cells + F::new(0.5)), F::new(1.));
+    }
+    result
+}
+
+/// (u, v) ∈ [0, 1)² 위치의 프랙탈 노이즈. None/Fbm 은 -1~1, Ridged/Turbulence 는 0~1.
+#[cube]
+fn fractal_noise<F: Float>(
+    u: F,
+    v: F,
+    noise_type: u32,
+    fractal: u32,
+    seed: u32,
+    cells_x: F,
+    cells_y: F,
+    octaves: u32,
+    lacunarity: F,
+    gain: F,
+    tileable: u32,
+) -> F {
+    let octave_count = select(fractal == 0, 1u32, octaves);
+    let mut sum = F::new(0.);
+    let mut norm = F::new(0.);
+    let mut amplitude = F::new(1.);
+    for k in 0..octave_count {
+        let fx = octave_cells::<F>(cells_x, lacunarity, k, tileable);
+        let fy = octave_cells::<F>(cells_y, lacunarity, k, tileable);
+        let mut period_x = 0u32;
+        let mut period_y = 0u32;
+        if tileable == 1 {
+            period_x = u32::cast_from(fx);
+            period_y = u32::cast_from(fy);
+        }
+        let n = base_noise::<F>(u * fx, v * fy, noise_type, seed + k * 1013u32, period_x, period_y);
+        let mut term = n;
+        if fractal == 2 {
+            let ridge = F::new(1.) - F::abs(n);
+            term = ridge * ridge;
+        } else if fractal == 3 {
+            term = F::abs(n);
+        }
+        sum += term * amplitude;
+        norm += amplitude;
+        amplitude *= gain;
+    }
+    sum / norm
+}
+
+#[cube(launch_unchecked)]
+fn kernel_generate_noise<F: Float>(
+    params: &Array<u32>,
+    cells_x: F,
+    cells_y: F,
+    lacunarity: F,
+    gain: F,
+    warp_strength: F,
+    warp_frequency: F,
+    output: &mut Array<F>,
+) {
+    // params: [width, height, noise_type, fractal, seed, octaves, tileable]
+    let width = params[0];
+    let height = params[1];
+    let noise_type = params[2];
+    let fractal = params[3];
+    let seed = params[4];
+    let octaves = params[5];
+    let tileable = params[6];
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let mut u = (F::cast_from(px) + F::new(0.5)) / F::cast_from(width);
+        let mut v = (F::cast_from(py) + F::new(0.5)) / F::cast_from(height);
+
+        if warp_strength != F::new(0.) {
+            // 워핑 필드는 fBm 으로 만들고, 첫 옥타브 셀 단위의 변위를 uv 로 바꿔 더한다.
+            let warp_x = octave_cells::<F>(cells_x, warp_frequency, 1, tileable);
+            let warp_y = octave_cells::<F>(cells_y, warp_frequency, 1, tileable);
+            let qx = fractal_noise::<F>(u, v, noise_type, 1, seed ^ 0x5bd1e995u32, warp_x, warp_y, octaves, lacunarity, gain, tileable);
+            let qy = fractal_noise::<F>(u, v, noise_type, 1, seed ^ 0x27d4eb2fu32, warp_x, warp_y, octaves, lacunarity, gain, tileable);
+            u += qx * warp_strength / cells_x;
+            v += qy * warp_strength / cells_y;
+        }
+
+        let n = fractal_noise::<F>(u, v, noise_type, fractal, seed, cells_x, cells_y, octaves, lacunarity, gain, tileable);
+        let mut value = n;
+        if fractal < 2 {
+            value = n * F::new(0.5) + F::new(0.5);
+        }
+        output[py * width + px] = F::min(F::max(value, F::new(0.)), F::new(1.));
+    }
+}
+
+/// 절차적 노이즈 이미지를 GPU 에서 생성한다. 값은 0~1 이며 결과는 Linear 로 태그된다.
+///
+/// 1채널 형식(`R8`/`R16`/`R32F`)을 주면 높이맵으로 바로 `make_normal_map_base`에 넘길 수 있고,
+/// 다채널 형식은 같은 값을 그레이스케일로 채운다.
+pub fn generate_noise<R: Runtime>(
+    run_device: &R::Device,
+    size: SKIDSizeVector2,
+    format: SKIDPixelFormat,
+    options: &SKIDNoiseOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+    let cells_x = options.frequency.max(1e-3);
+    let mut cells_y = cells_x * size.height as f32 / size.width.max(1) as f32;
+    if options.tileable {
+        cells_y = cells_y.round().max(1.0);
+    }
+    let cells_x = if options.tileable { cells_x.round().max(1.0) } else { cells_x };
+
+    let params = [
+        w_u32,
+        h_u32,
+        options.noise_type as u32,
+        options.fractal as u32,
+        options.seed,
+        options.octaves.clamp(1, 16),
+        options.tileable as u32,
+    ];
+    let params_handle = client.create(bytemuck::cast_slice(&params));
+    let output_handle = client.empty(pixel_count * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_generate_noise::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<u32>(&params_handle, params.len(), 1),
+            ScalarArg { elem: cells_x },
+            ScalarArg { elem: cells_y },
+            ScalarArg { elem: options.lacunarity },
+            ScalarArg { elem: options.gain },
+            ScalarArg { elem: options.warp_strength },
+            ScalarArg { elem: options.warp_frequency.max(1e-3) },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, format, f32::from_bytes(&bytes), 1).with_color_space(SKIDColorSpace::Linear)
+}
@@ -0,0 +1,544 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_alpha_mode::SKIDAlphaMode,
+        skid_color::SKIDColor,
+        skid_color_space::{srgb_to_linear, SKIDColorSpace},
+        skid_image::SKIDImage,
+    },
+    processor::{alpha, color_space},
+    utils::{
+        dither::{self, SKIDDitherMethod},
+        gpu_opt::image_cube_config,
+        graphic_fn::{linear_rgb_to_lab, srgb_to_linear as gpu_srgb_to_linear},
+    },
+};
+
+/// 인덱스 PNG 가 담을 수 있는 최대 색 수.
+pub const MAX_PALETTE_COLORS: usize = 256;
+/// 중앙값 분할에 쓰는 최대 표본 픽셀 수. 더 큰 이미지는 일정 간격으로 건너뛰며 고른다.
+const MAX_MEDIAN_CUT_SAMPLES: usize = 1 << 18;
+const KMEANS_MAX_ITERATIONS: usize = 16;
+/// 모든 중심이 이 거리(ΔE) 미만으로 움직이면 k-means 를 멈춘다.
+const KMEANS_CONVERGENCE_DELTA_E: f32 = 0.1;
+/// 알파가 이 값보다 작은 픽셀은 투명 항목에 대응시킨다.
+const TRANSPARENT_ALPHA: f32 = 0.5;
+
+/// 팔레트 생성 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDPaletteMethod {
+    /// 색 분포의 가장 긴 축을 중앙값으로 반복 분할 (Heckbert)
+    MedianCut = 0,
+    /// 중앙값 분할 결과에서 시작해 Lab 공간에서 k-means 로 다듬는다. 할당 단계는 GPU 에서 수행한다.
+    #[default]
+    KMeansLab = 1,
+}
+
+impl SKIDPaletteMethod {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDPaletteMethod::MedianCut),
+            1 => Some(SKIDPaletteMethod::KMeansLab),
+            _ => None,
+        }
+    }
+}
+
+/// 최대 256색 팔레트. 색은 감마 인코딩된 sRGB, straight 알파이다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SKIDPalette {
+    colors: Vec<SKIDColor>,
+}
+
+impl SKIDPalette {
+    pub fn new(colors: Vec<SKIDColor>) -> Result<Self, String> {
+        if colors.is_empty() || colors.len() > MAX_PALETTE_COLORS {
+            return Err(format!("Palette must have 1..={} colors, got {}", MAX_PALETTE_COLORS, colors.len()));
+        }
+        let colors = colors.into_iter().map(|c| SKIDColor::from_f32_array(c.to_f32_array())).collect();
+        Ok(SKIDPalette { colors })
+    }
+    /// RGBA 인터리브 f32 배열(0~1)로 팔레트를 만든다.
+    pub fn from_f32_rgba(values: &[f32]) -> Result<Self, String> {
+        if !values.len().is_multiple_of(4) {
+            return Err(format!("Palette array length {} is not a multiple of 4", values.len()));
+        }
+        SKIDPalette::new(values.chunks_exact(4).map(|c| SKIDColor::new(c[0], c[1], c[2], c[3])).collect())
+    }
+    pub fn get_colors(&self) -> &[SKIDColor] {
+        &self.colors
+    }
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+    /// 알파가 0.5 미만인 첫 항목. 투명 픽셀은 이 항목으로 매핑된다.
+    pub fn transparent_index(&self) -> Option<usize> {
+        self.colors.iter().position(|c| c.a < TRANSPARENT_ALPHA)
+    }
+
+    /// 팔레트 텍스트를 읽는다. 첫 줄로 형식을 판별한다.
+    /// - GIMP `.gpl` : `GIMP Palette` 머리말, `R G B [이름]` 줄
+    /// - JASC `.pal` : `JASC-PAL`, 버전, 색 수, `R G B` 줄
+    /// - 그 외      : 한 줄에 `RRGGBB` 또는 `RRGGBBAA` (앞의 `#` 생략 가능, Lospec `.hex`)
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let lines = text.lines().map(str::trim).enumerate().filter(|(_, l)| !l.is_empty());
+        let Some((_, first)) = lines.clone().next() else {
+            return Err("Palette file is empty".to_string());
+        };
+
+        let parse_rgb = |line_no: usize, line: &str| -> Result<SKIDColor, String> {
+            let parts: Vec<&str> = line.split_whitespace().take(3).collect();
+            if parts.len() != 3 {
+                return Err(format!("Line {}: expected R G B", line_no + 1));
+            }
+            let mut rgb = [0.0f32; 3];
+            for (v, part) in rgb.iter_mut().zip(&parts) {
+                let byte: u8 = part.parse().map_err(|_| format!("Line {}: invalid value {:?}", line_no + 1, part))?;
+                *v = byte as f32 / 255.0;
+            }
+            Ok(SKIDColor::new(rgb[0], rgb[1], rgb[2], 1.0))
+        };
+
+        let mut colors = Vec::new();
+        if first.starts_with("GIMP Palette") {
+            for (line_no, line) in lines.skip(1) {
+                if line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+                    continue;
+                }
+                colors.push(parse_rgb(line_no, line)?);
+            }
+        } else if first.starts_with("JASC-PAL") {
+            let mut lines = lines.skip(2);
+            let count: usize = lines
+                .next()
+                .and_then(|(_, l)| l.parse().ok())
+                .ok_or("JASC-PAL: missing color count")?;
+            for (line_no, line) in lines.take(count) {
+                colors.push(parse_rgb(line_no, line)?);
+            }
+            if colors.len() != count {
+                return Err(format!("JASC-PAL: expected {} colors, got {}", count, colors.len()));
+            }
+        } else {
+            for (line_no, line) in lines {
+                if line.starts_with(';') || line.starts_with("//") {
+                    continue;
+                }
+                let hex = line.trim_start_matches('#');
+                let bytes: Option<Vec<u8>> = match hex.len() {
+                    6 | 8 => (0..hex.len())
+                        .step_by(2)
+                        .map(|i| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+                        .collect(),
+                    _ => None,
+                };
+                let bytes = bytes.ok_or_else(|| format!("Line {}: invalid hex color {:?}", line_no + 1, line))?;
+                let a = bytes.get(3).copied().unwrap_or(255);
+                colors.push(SKIDColor::from_u8_array([bytes[0], bytes[1], bytes[2], a]));
+            }
+        }
+        SKIDPalette::new(colors)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        SKIDPalette::parse(&text)
+    }
+
+    /// GIMP `.gpl` 텍스트로 쓴다. (알파는 저장되지 않는다)
+    pub fn to_gpl_string(&self, name: &str) -> String {
+        let mut out = format!("GIMP Palette\nName: {}\nColumns: 16\n#\n", name);
+        for color in &self.colors {
+            let [r, g, b, _] = color.to_u8_array();
+            out.push_str(&format!("{:3} {:3} {:3}\t#{:02x}{:02x}{:02x}\n", r, g, b, r, g, b));
+        }
+        out
+    }
+
+    pub fn save_gpl(&self, path: &str, name: &str) -> Result<(), String> {
+        std::fs::write(path, self.to_gpl_string(name)).map_err(|e| format!("Failed to write {}: {}", path, e))
+    }
+
+    /// 항목별 Lab 값 (`graphic_fn::linear_rgb_to_lab`과 같은 0~1 정규화)과 알파.
+    fn lab_entries(&self) -> (Vec<f32>, Vec<f32>) {
+        let lab = self.colors.iter().flat_map(|c| srgb_to_lab([c.r, c.g, c.b])).collect();
+        let alpha = self.colors.iter().map(|c| c.a).collect();
+        (lab, alpha)
+    }
+}
+
+/// CPU 측 sRGB → 정규화 Lab. GPU 의 `linear_rgb_to_lab`과 같은 식을 쓴다.
+fn srgb_to_lab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let f = |t: f32| if t > 0.008856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+    let fx = f((0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047);
+    let fy = f(0.2126 * r + 0.7152 * g + 0.0722 * b);
+    let fz = f((0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883);
+    [(116.0 * fy - 16.0) / 100.0, 500.0 * (fx - fy) / 256.0 + 0.5, 200.0 * (fy - fz) / 256.0 + 0.5]
+}
+
+/// 정규화 Lab 두 점 사이의 ΔE76 제곱.
+fn lab_distance_sq(a: &[f32], b: &[f32]) -> f32 {
+    let dl = (a[0] - b[0]) * 100.0;
+    let da = (a[1] - b[1]) * 256.0;
+    let db = (a[2] - b[2]) * 256.0;
+    dl * dl + da * da + db * db
+}
+
+/// 픽셀마다 Lab 거리가 가장 가까운 팔레트 항목을 찾는다.
+/// 순서 디더링은 비교 전에 sRGB 값에 (임계값 - 0.5) * spread 를 더하는 방식이다.
+#[cube(launch_unchecked)]
+fn kernel_nearest_palette<F: Float>(
+    input: &Array<F>,
+    palette_lab: &Array<F>,
+    palette_alpha: &Array<F>,
+    thresholds: &Array<F>,
+    width: u32,
+    height: u32,
+    palette_len: u32,
+    transparent_index: u32,
+    tile_size: u32,
+    spread: F,
+    indices: &mut Array<u32>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        if input[idx][3] < F::new(0.5) && transparent_index < palette_len {
+            indices[idx] = transparent_index;
+        } else {
+            let offset = (thresholds[(py % tile_size) * tile_size + px % tile_size] - F::new(0.5)) * spread;
+            let r = gpu_srgb_to_linear::<F>(F::min(F::max(input[idx][0] + offset, F::new(0.)), F::new(1.)));
+            let g = gpu_srgb_to_linear::<F>(F::min(F::max(input[idx][1] + offset, F::new(0.)), F::new(1.)));
+            let b = gpu_srgb_to_linear::<F>(F::min(F::max(input[idx][2] + offset, F::new(0.)), F::new(1.)));
+            let lab = linear_rgb_to_lab::<F>(r, g, b);
+
+            let mut best = 0u32;
+            let mut best_distance = F::new(3.0e38);
+            for i in 0..palette_len {
+                if palette_alpha[i] >= F::new(0.5) {
+                    let dl = (lab.x - palette_lab[i * 3]) * F::new(100.);
+                    let da = (lab.y - palette_lab[i * 3 + 1]) * F::new(256.);
+                    let db = (lab.z - palette_lab[i * 3 + 2]) * F::new(256.);
+                    let distance = dl * dl + da * da + db * db;
+                    if distance < best_distance {
+                        best_distance = distance;
+                        best = i;
+                    }
+                }
+            }
+            indices[idx] = best;
+        }
+    }
+}
+
+/// 팔레트 처리용 입력: 감마 인코딩된 sRGB, straight 알파.
+fn prepare_input<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImage {
+    let srgb = color_space::to_srgb::<R>(run_device, image);
+    alpha::convert_alpha_mode::<R>(run_device, &srgb, SKIDAlphaMode::Straight)
+}
+
+fn launch_nearest<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    palette_lab: &[f32],
+    palette_alpha: &[f32],
+    transparent_index: Option<usize>,
+    dither: SKIDDitherMethod,
+) -> Vec<u32> {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+    let palette_len = palette_alpha.len();
+
+    let (thresholds, tile_size) = dither::threshold_map(dither).unwrap_or((vec![0.5], 1));
+    // 팔레트가 RGB 큐브를 고르게 나눈다고 볼 때의 한 칸 크기만큼 흔든다.
+    let spread = if tile_size > 1 { 1.0 / (palette_len as f32).cbrt() } else { 0.0 };
+
+    let input = image.get_1d_data_as_f32();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let lab_handle = client.create(bytemuck::cast_slice(palette_lab));
+    let alpha_handle = client.create(bytemuck::cast_slice(palette_alpha));
+    let threshold_handle = client.create(bytemuck::cast_slice(&thresholds));
+    let index_handle = client.empty(pixel_count * core::mem::size_of::<u32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_nearest_palette::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, 4),
+            ArrayArg::from_raw_parts::<f32>(&lab_handle, palette_lab.len(), 1),
+            ArrayArg::from_raw_parts::<f32>(&alpha_handle, palette_len, 1),
+            ArrayArg::from_raw_parts::<f32>(&threshold_handle, thresholds.len(), 1),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: palette_len as u32 },
+            ScalarArg { elem: transparent_index.unwrap_or(palette_len) as u32 },
+            ScalarArg { elem: tile_size as u32 },
+            ScalarArg { elem: spread },
+            ArrayArg::from_raw_parts::<u32>(&index_handle, pixel_count, 1),
+        )
+    };
+
+    let bytes = client.read_one(index_handle.binding());
+    u32::from_bytes(&bytes).to_vec()
+}
+
+/// Floyd-Steinberg 오차 확산 매핑. 오차는 sRGB 값 기준으로 퍼뜨린다. (순차 처리이므로 CPU)
+fn floyd_steinberg_indices(image: &SKIDImage, palette: &SKIDPalette) -> Vec<u32> {
+    let size = image.get_size();
+    let (width, height) = (size.width, size.height);
+    let (palette_lab, _) = palette.lab_entries();
+    let transparent = palette.transparent_index();
+    let opaque: Vec<usize> = (0..palette.len()).filter(|&i| palette.colors[i].a >= TRANSPARENT_ALPHA).collect();
+    let data = image.get_1d_data_as_f32();
+    let mut indices = vec![0u32; width * height];
+    let mut current = vec![[0.0f32; 3]; width];
+    let mut next = vec![[0.0f32; 3]; width];
+
+    for y in 0..height {
+        let reverse = y % 2 == 1;
+        for step in 0..width {
+            let x = if reverse { width - 1 - step } else { step };
+            let i = y * width + x;
+            let pixel = &data[i * 4..i * 4 + 4];
+            if let Some(t) = transparent.filter(|_| pixel[3] < TRANSPARENT_ALPHA) {
+                indices[i] = t as u32;
+                continue;
+            }
+            let wanted: [f32; 3] = std::array::from_fn(|c| (pixel[c] + current[x][c]).clamp(0.0, 1.0));
+            let lab = srgb_to_lab(wanted);
+            let best = opaque
+                .iter()
+                .copied()
+                .min_by(|&a, &b| {
+                    let da = lab_distance_sq(&lab, &palette_lab[a * 3..a * 3 + 3]);
+                    let db = lab_distance_sq(&lab, &palette_lab[b * 3..b * 3 + 3]);
+                    da.total_cmp(&db)
+                })
+                .unwrap_or(0);
+            indices[i] = best as u32;
+
+            let chosen = palette.colors[best];
+            let error = [wanted[0] - chosen.r, wanted[1] - chosen.g, wanted[2] - chosen.b];
+            let forward = if reverse { x.checked_sub(1) } else { Some(x + 1).filter(|&v| v < width) };
+            let backward = if reverse { Some(x + 1).filter(|&v| v < width) } else { x.checked_sub(1) };
+            for (c, e) in error.into_iter().enumerate() {
+                if let Some(fx) = forward {
+                    current[fx][c] += e * 7.0 / 16.0;
+                    next[fx][c] += e / 16.0;
+                }
+                if let Some(bx) = backward {
+                    next[bx][c] += e * 3.0 / 16.0;
+                }
+                next[x][c] += e * 5.0 / 16.0;
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+        next.fill([0.0; 3]);
+    }
+    indices
+}
+
+/// 각 픽셀을 가장 가까운(Lab ΔE) 팔레트 항목의 인덱스로 바꾼다.
+///
+/// 알파가 0.5 미만인 픽셀은 팔레트에 투명 항목이 있으면 그 항목이 된다.
+/// Bayer/블루 노이즈 디더링은 GPU 에서, Floyd-Steinberg 는 CPU 에서 처리한다.
+pub fn map_to_palette_indices<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    palette: &SKIDPalette,
+    dither: SKIDDitherMethod,
+) -> Result<Vec<u8>, String> {
+    if palette.is_empty() {
+        return Err("Palette is empty".to_string());
+    }
+    let input = prepare_input::<R>(run_device, image);
+    let indices = if dither == SKIDDitherMethod::FloydSteinberg {
+        floyd_steinberg_indices(&input, palette)
+    } else {
+        let (lab, alpha) = palette.lab_entries();
+        launch_nearest::<R>(run_device, &input, &lab, &alpha, palette.transparent_index(), dither)
+    };
+    Ok(indices.into_iter().map(|i| i as u8).collect())
+}
+
+/// 이미지를 팔레트 색만 쓰도록 바꾼다. 결과는 sRGB, straight 알파이며 4채널 형식이다.
+pub fn map_to_palette<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    palette: &SKIDPalette,
+    dither: SKIDDitherMethod,
+) -> Result<SKIDImage, String> {
+    let indices = map_to_palette_indices::<R>(run_device, image, palette, dither)?;
+    let data: Vec<f32> = indices.iter().flat_map(|&i| palette.colors[i as usize].to_f32_array()).collect();
+    let format = image.get_pixel_format().rgba_equivalent();
+    Ok(SKIDImage::from_f32_data(image.get_size(), format, &data, 4)
+        .with_color_space(SKIDColorSpace::Srgb)
+        .with_alpha_mode(SKIDAlphaMode::Straight))
+}
+
+/// 중앙값 분할. 값 범위 x 픽셀 수가 가장 큰 상자를 그 축의 중앙값에서 나누기를 반복한다.
+fn median_cut(samples: &mut [[f32; 3]], count: usize) -> Vec<[f32; 3]> {
+    let mut boxes = vec![(0usize, samples.len())];
+    while boxes.len() < count {
+        let mut best: Option<(usize, usize, f32)> = None;
+        for (box_idx, &(start, end)) in boxes.iter().enumerate() {
+            if end - start < 2 {
+                continue;
+            }
+            let slice = &samples[start..end];
+            for c in 0..3 {
+                let (lo, hi) = slice.iter().fold((f32::MAX, f32::MIN), |(lo, hi), s| (lo.min(s[c]), hi.max(s[c])));
+                let score = (hi - lo) * (end - start) as f32;
+                let better = match best {
+                    Some((_, _, best_score)) => score > best_score,
+                    None => true,
+                };
+                if hi > lo && better {
+                    best = Some((box_idx, c, score));
+                }
+            }
+        }
+        let Some((box_idx, channel, _)) = best else {
+            break; // 더 나눌 수 있는 상자가 없다 (서로 다른 색이 count 개보다 적음)
+        };
+        let (start, end) = boxes[box_idx];
+        let slice = &mut samples[start..end];
+        slice.sort_unstable_by(|a, b| a[channel].total_cmp(&b[channel]));
+        // 같은 값이 두 상자로 갈라지지 않도록 중앙값과 같은 값의 시작(또는 끝)에서 자른다.
+        let pivot = slice[slice.len() / 2][channel];
+        let mut mid = slice.partition_point(|s| s[channel] < pivot);
+        if mid == 0 {
+            mid = slice.partition_point(|s| s[channel] <= pivot);
+        }
+        boxes[box_idx] = (start, start + mid);
+        boxes.push((start + mid, end));
+    }
+    boxes
+        .iter()
+        .filter(|(start, end)| end > start)
+        .map(|&(start, end)| {
+            let n = (end - start) as f32;
+            let sum = samples[start..end].iter().fold([0.0f32; 3], |acc, s| [acc[0] + s[0], acc[1] + s[1], acc[2] + s[2]]);
+            sum.map(|v| v / n)
+        })
+        .collect()
+}
+
+/// 이미지에서 최대 `color_count`색 팔레트를 만든다.
+///
+/// 알파가 0.5 미만인 픽셀이 있으면 투명 항목 (0, 0, 0, 0)을 0번에 두고 나머지 색을 불투명 픽셀에서 고른다.
+/// 서로 다른 색이 `color_count`보다 적으면 팔레트도 그만큼 작아진다.
+pub fn generate_palette<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    color_count: usize,
+    method: SKIDPaletteMethod,
+) -> Result<SKIDPalette, String> {
+    if !(2..=MAX_PALETTE_COLORS).contains(&color_count) {
+        return Err(format!("Color count must be within 2..={}, got {}", MAX_PALETTE_COLORS, color_count));
+    }
+    let input = prepare_input::<R>(run_device, image);
+    let data = input.get_1d_data_as_f32();
+    let pixels: Vec<&[f32]> = data.chunks_exact(4).collect();
+    let opaque_count = pixels.iter().filter(|p| p[3] >= TRANSPARENT_ALPHA).count();
+    let has_transparent = opaque_count < pixels.len();
+    let transparent = SKIDColor::new(0.0, 0.0, 0.0, 0.0);
+    if opaque_count == 0 {
+        return SKIDPalette::new(vec![transparent]);
+    }
+
+    let target = color_count - has_transparent as usize;
+    let stride = opaque_count.div_ceil(MAX_MEDIAN_CUT_SAMPLES);
+    let mut samples: Vec<[f32; 3]> = pixels
+        .iter()
+        .filter(|p| p[3] >= TRANSPARENT_ALPHA)
+        .step_by(stride)
+        .map(|p| [p[0], p[1], p[2]])
+        .collect();
+    let mut colors = median_cut(&mut samples, target);
+
+    if method == SKIDPaletteMethod::KMeansLab && colors.len() > 1 {
+        colors = refine_kmeans_lab::<R>(run_device, &input, &data, colors);
+    }
+
+    let mut entries: Vec<SKIDColor> = Vec::with_capacity(color_count);
+    if has_transparent {
+        entries.push(transparent);
+    }
+    entries.extend(colors.into_iter().map(|[r, g, b]| SKIDColor::new(r, g, b, 1.0)));
+    SKIDPalette::new(entries)
+}
+
+/// Lab 공간 k-means. 픽셀 할당은 GPU 커널(`kernel_nearest_palette`)로 하고 중심 갱신은 호스트에서 합한다.
+/// 최종 색은 각 군집에 속한 픽셀의 sRGB 평균이다.
+fn refine_kmeans_lab<R: Runtime>(
+    run_device: &R::Device,
+    input: &SKIDImage,
+    srgb: &[f32],
+    initial: Vec<[f32; 3]>,
+) -> Vec<[f32; 3]> {
+    let k = initial.len();
+    let lab_pixels = color_space::convert_color_space::<R>(run_device, input, SKIDColorSpace::Lab).get_1d_data_as_f32();
+    let mut centers: Vec<f32> = initial.iter().flat_map(|&c| srgb_to_lab(c)).collect();
+    let mut colors = initial;
+    let opaque_alpha = vec![1.0f32; k];
+
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        let assignment = launch_nearest::<R>(run_device, input, &centers, &opaque_alpha, None, SKIDDitherMethod::Round);
+        let mut lab_sum = vec![[0.0f64; 3]; k];
+        let mut rgb_sum = vec![[0.0f64; 3]; k];
+        let mut counts = vec![0usize; k];
+        for (i, &cluster) in assignment.iter().enumerate() {
+            if srgb[i * 4 + 3] < TRANSPARENT_ALPHA {
+                continue;
+            }
+            let cluster = cluster as usize;
+            counts[cluster] += 1;
+            for c in 0..3 {
+                lab_sum[cluster][c] += lab_pixels[i * 4 + c] as f64;
+                rgb_sum[cluster][c] += srgb[i * 4 + c] as f64;
+            }
+        }
+
+        let mut max_shift = 0.0f32;
+        for cluster in 0..k {
+            // 빈 군집은 이전 중심을 유지한다.
+            if counts[cluster] == 0 {
+                continue;
+            }
+            let n = counts[cluster] as f64;
+            let new_center = lab_sum[cluster].map(|v| (v / n) as f32);
+            max_shift = max_shift.max(lab_distance_sq(&new_center, &centers[cluster * 3..cluster * 3 + 3]).sqrt());
+            centers[cluster * 3..cluster * 3 + 3].copy_from_slice(&new_center);
+            colors[cluster] = rgb_sum[cluster].map(|v| (v / n) as f32);
+        }
+        if max_shift < KMEANS_CONVERGENCE_DELTA_E {
+            break;
+        }
+    }
+    colors
+}
+
+/// 팔레트로 매핑한 뒤 인덱스 PNG(PLTE + tRNS)로 저장한다.
+#[cfg(feature = "use_image")]
+pub fn export_indexed_png<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    palette: &SKIDPalette,
+    dither: SKIDDitherMethod,
+    file_path: &str,
+) -> Result<(), String> {
+    let indices = map_to_palette_indices::<R>(run_device, image, palette, dither)?;
+    crate::utils::file_io::export_indexed_png(file_path, image.get_size(), &indices, palette.get_colors())
+}
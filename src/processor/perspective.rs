@@ -0,0 +1,205 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_image::{SKIDImage, SKIDSizeVector2},
+        skid_matrix3::SKIDMatrix3,
+        skid_vector2::SKIDVector2,
+        skid_vector3::SKIDVector3,
+    },
+    processor::{
+        convolution::filter_image,
+        geometry::SKIDWarpOptions,
+        sampling::sample_texel,
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 단위 정사각형 (0,0), (1,0), (1,1), (0,1)을 사각형 `quad`의 네 꼭짓점으로 보내는 호모그래피. (Heckbert)
+/// 세 점이 한 직선 위에 있으면 None.
+fn square_to_quad(quad: &[SKIDVector2; 4]) -> Option<SKIDMatrix3> {
+    let [p0, p1, p2, p3] = *quad;
+    let sx = p0.x - p1.x + p2.x - p3.x;
+    let sy = p0.y - p1.y + p2.y - p3.y;
+    if sx == 0.0 && sy == 0.0 {
+        // 평행사변형이면 아핀 변환으로 충분하다.
+        return Some(SKIDMatrix3::from_rows([
+            [p1.x - p0.x, p3.x - p0.x, p0.x],
+            [p1.y - p0.y, p3.y - p0.y, p0.y],
+            [0.0, 0.0, 1.0],
+        ]));
+    }
+    let (dx1, dx2) = (p1.x - p2.x, p3.x - p2.x);
+    let (dy1, dy2) = (p1.y - p2.y, p3.y - p2.y);
+    let den = dx1 * dy2 - dx2 * dy1;
+    if den == 0.0 {
+        return None;
+    }
+    let g = (sx * dy2 - dx2 * sy) / den;
+    let h = (dx1 * sy - sx * dy1) / den;
+    Some(SKIDMatrix3::from_rows([
+        [p1.x - p0.x + g * p1.x, p3.x - p0.x + h * p3.x, p0.x],
+        [p1.y - p0.y + g * p1.y, p3.y - p0.y + h * p3.y, p0.y],
+        [g, h, 1.0],
+    ]))
+}
+
+/// 원본 사각형 `src`의 네 꼭짓점을 `dst`의 네 꼭짓점으로 보내는 호모그래피를 구한다.
+///
+/// 꼭짓점은 같은 순서(예: 왼쪽 위, 오른쪽 위, 오른쪽 아래, 왼쪽 아래)로 주어야 하며,
+/// 좌표는 픽셀 단위이다. (이미지 전체는 (0, 0) ~ (width, height))
+/// 어느 한쪽의 세 점이 한 직선 위에 있으면 Err.
+pub fn homography_from_corners(src: &[SKIDVector2; 4], dst: &[SKIDVector2; 4]) -> Result<SKIDMatrix3, String> {
+    let degenerate = || format!("Degenerate quadrilateral: {:?} -> {:?}", src, dst);
+    let from_src = square_to_quad(src).and_then(|m| m.inverse()).ok_or_else(degenerate)?;
+    let to_dst = square_to_quad(dst).ok_or_else(degenerate)?;
+    let homography = (to_dst * from_src).normalized();
+    if homography.inverse().is_none() {
+        return Err(degenerate());
+    }
+    Ok(homography)
+}
+
+/// 출력 픽셀 중심을 역 호모그래피(`inverse`, 행 우선 3x3)로 원본 좌표에 옮겨 공용 샘플러로 읽는다.
+/// w 가 0 이하인 곳은 원본 평면의 뒤쪽(지평선 너머)이므로 상수 색으로 채운다.
+#[cube(launch_unchecked)]
+fn kernel_perspective_warp<F: Float>(
+    input: &Array<F>,
+    constant: &Array<F>,
+    inverse: &Array<F>,
+    width: u32,
+    height: u32,
+    out_width: u32,
+    out_height: u32,
+    channels: u32,
+    edge_mode: u32,
+    sampling: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < out_width && py < out_height {
+        let dx = F::cast_from(px) + F::new(0.5);
+        let dy = F::cast_from(py) + F::new(0.5);
+        let hx = inverse[0] * dx + inverse[1] * dy + inverse[2];
+        let hy = inverse[3] * dx + inverse[4] * dy + inverse[5];
+        let hw = inverse[6] * dx + inverse[7] * dy + inverse[8];
+        let idx = (py * out_width + px) * channels;
+        if hw > F::new(1.0e-8) {
+            let sx = hx / hw;
+            let sy = hy / hw;
+            for c in 0..channels {
+                output[idx + c] = sample_texel::<F>(input, constant, sx, sy, c, width, height, edge_mode, sampling);
+            }
+        } else {
+            for c in 0..channels {
+                output[idx + c] = constant[c];
+            }
+        }
+    }
+}
+
+/// 원본 좌표를 출력 좌표로 보내는 호모그래피 `matrix`로 이미지를 변형해 `out_size` 크기로 만든다.
+///
+/// 좌표 규약과 보간/가장자리 처리는 `geometry::affine_warp`와 같다. 역변환이 없으면 Err.
+pub fn perspective_warp<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    matrix: &SKIDMatrix3,
+    out_size: SKIDSizeVector2,
+    options: &SKIDWarpOptions,
+) -> Result<SKIDImage, String> {
+    let inverse = matrix.inverse().ok_or_else(|| format!("Homography {:?} is not invertible", matrix))?;
+    if out_size.is_empty() {
+        return Err("Output size must be non-zero".to_string());
+    }
+    // 호모그래피는 음수 배율도 같은 변환이므로, 원본 중심이 옮겨 간 곳에서 w > 0 이 되도록 부호를 맞춘다.
+    // 그래야 커널에서 w 의 부호로 지평선 뒤쪽을 판별할 수 있다.
+    let size = image.get_size();
+    let center = SKIDVector2::new(size.width as f32 * 0.5, size.height as f32 * 0.5);
+    let center_w = matrix
+        .transform_point(center)
+        .map_or(1.0, |d| inverse.transform(SKIDVector3::new(d.x, d.y, 1.0)).z);
+    let inverse = if center_w < 0.0 {
+        SKIDMatrix3::new(-inverse.x_axis, -inverse.y_axis, -inverse.z_axis)
+    } else {
+        inverse
+    };
+    Ok(filter_image::<R>(run_device, image, "perspective_warp", |img| {
+        launch::<R>(run_device, img, &inverse, out_size, options)
+    }))
+}
+
+/// 비스듬히 찍힌 사각형 영역(`corners`: 왼쪽 위, 오른쪽 위, 오른쪽 아래, 왼쪽 아래)을 정면에서 본 직사각형으로 편다.
+///
+/// `out_size`가 None 이면 마주 보는 변 길이의 평균으로 결과 크기를 정한다.
+pub fn correct_perspective<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    corners: &[SKIDVector2; 4],
+    out_size: Option<SKIDSizeVector2>,
+    options: &SKIDWarpOptions,
+) -> Result<SKIDImage, String> {
+    let out_size = out_size.unwrap_or_else(|| {
+        let length = |a: SKIDVector2, b: SKIDVector2| {
+            let d = b - a;
+            d.dot(&d).sqrt()
+        };
+        let [tl, tr, br, bl] = *corners;
+        let width = (length(tl, tr) + length(bl, br)) * 0.5;
+        let height = (length(tl, bl) + length(tr, br)) * 0.5;
+        SKIDSizeVector2::new(width.round().max(1.0) as usize, height.round().max(1.0) as usize)
+    });
+    let (w, h) = (out_size.width as f32, out_size.height as f32);
+    let target = [
+        SKIDVector2::new(0.0, 0.0),
+        SKIDVector2::new(w, 0.0),
+        SKIDVector2::new(w, h),
+        SKIDVector2::new(0.0, h),
+    ];
+    let matrix = homography_from_corners(corners, &target)?;
+    perspective_warp::<R>(run_device, image, &matrix, out_size, options)
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    inverse: &SKIDMatrix3,
+    out_size: SKIDSizeVector2,
+    options: &SKIDWarpOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (out_w, out_h) = (out_size.width as u32, out_size.height as u32);
+    let out_len = out_size.width * out_size.height * channels;
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let constant_handle = client.create(bytemuck::cast_slice(&options.constant_color.to_f32_array()));
+    let inverse_handle = client.create(bytemuck::cast_slice(inverse.to_rows().as_flattened()));
+    let output_handle = client.empty(out_len * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(out_w, out_h);
+
+    unsafe {
+        kernel_perspective_warp::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, size.width * size.height, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+            ArrayArg::from_raw_parts::<f32>(&inverse_handle, 9, 1),
+            ScalarArg { elem: size.width as u32 },
+            ScalarArg { elem: size.height as u32 },
+            ScalarArg { elem: out_w },
+            ScalarArg { elem: out_h },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: options.edge_mode.to_u32() },
+            ScalarArg { elem: options.sampling.to_u32() },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, out_len, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(out_size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
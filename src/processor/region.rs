@@ -0,0 +1,157 @@
+use cubecl::Runtime;
+
+use crate::{
+    model::{
+        skid_alpha_mode::SKIDAlphaMode,
+        skid_color_space::SKIDColorSpace,
+        skid_image::{SKIDImage, SKIDRect},
+    },
+    processor::{alpha, color_space, convolution::SKIDEdgeMode},
+};
+
+/// CPU 측 `convolution::resolve_edge_coord`. `Constant` 모드에서 밖이면 None.
+fn resolve_edge(coord: i64, size: i64, edge_mode: SKIDEdgeMode) -> Option<usize> {
+    if (0..size).contains(&coord) {
+        return Some(coord as usize);
+    }
+    match edge_mode {
+        SKIDEdgeMode::Clamp => Some(coord.clamp(0, size - 1) as usize),
+        SKIDEdgeMode::Wrap => Some(coord.rem_euclid(size) as usize),
+        SKIDEdgeMode::Mirror => {
+            let m = coord.rem_euclid(size * 2);
+            let reflected = if m >= size { size * 2 - 1 - m } else { m };
+            Some(reflected as usize)
+        }
+        SKIDEdgeMode::Constant => None,
+    }
+}
+
+/// `rect`를 네 변으로 `halo`만큼 넓힌 영역을 네이티브 바이트 그대로 복사해 온다.
+/// 이미지 밖에 걸친 픽셀은 `edge_mode`로 채우고, `Constant`이면 0(투명 검정)이다.
+pub fn extract_with_halo(image: &SKIDImage, rect: SKIDRect, halo: usize, edge_mode: SKIDEdgeMode) -> Result<SKIDImage, String> {
+    let size = image.get_size();
+    if rect.is_empty() || !rect.fits_in(size) {
+        return Err(format!("Region {:?} is empty or outside of the {}x{} image", rect, size.width, size.height));
+    }
+    let out = SKIDRect::new(0, 0, rect.width + halo * 2, rect.height + halo * 2);
+    if halo == 0 {
+        return Ok(image.view(rect)?.to_image());
+    }
+    let source = image.as_view();
+    let bpp = image.get_pixel_format().bytes_per_pixel();
+    let (left, top) = (rect.x as i64 - halo as i64, rect.y as i64 - halo as i64);
+    let mut bytes = vec![0u8; out.width * out.height * bpp];
+    for (y, dst_row) in bytes.chunks_exact_mut(out.width * bpp).enumerate() {
+        let Some(sy) = resolve_edge(top + y as i64, size.height as i64, edge_mode) else {
+            continue;
+        };
+        let src_row = source.row(sy);
+        // 가운데(이미지 안쪽)는 통째로 복사하고, 이미지 밖으로 나간 열만 한 픽셀씩 가장자리 모드를 적용한다.
+        let inside_start = (-left).max(0) as usize;
+        let inside_end = (size.width as i64 - left).clamp(0, out.width as i64) as usize;
+        if inside_start < inside_end {
+            let src_start = (left + inside_start as i64) as usize * bpp;
+            dst_row[inside_start * bpp..inside_end * bpp]
+                .copy_from_slice(&src_row[src_start..src_start + (inside_end - inside_start) * bpp]);
+        }
+        for x in (0..inside_start).chain(inside_end..out.width) {
+            if let Some(sx) = resolve_edge(left + x as i64, size.width as i64, edge_mode) {
+                dst_row[x * bpp..(x + 1) * bpp].copy_from_slice(&src_row[sx * bpp..(sx + 1) * bpp]);
+            }
+        }
+    }
+    Ok(SKIDImage::from_native_bytes(out.get_size(), image.get_pixel_format(), bytes).with_metadata_from(image))
+}
+
+/// `region`을 `target`의 `rect` 위치에 제자리로 쓴다.
+///
+/// 픽셀 형식이 다르면 변환하고, 4채널 결과의 색 공간/알파 모드가 `target`과 다르면 GPU 로 맞춘 뒤 쓴다.
+pub fn write_region<R: Runtime>(
+    run_device: &R::Device,
+    target: &mut SKIDImage,
+    rect: SKIDRect,
+    region: &SKIDImage,
+) -> Result<(), String> {
+    let converted = match_tags::<R>(run_device, region, target.get_color_space(), target.get_alpha_mode());
+    target.view_mut(rect)?.copy_from(&converted.as_ref().unwrap_or(region).as_view())
+}
+
+/// 4채널 이미지의 색 공간/알파 모드를 주어진 태그로 맞춘 복사본. 이미 같거나 색이 아닌 데이터(1·2채널)이면 None.
+pub(crate) fn match_tags<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    color_space: SKIDColorSpace,
+    alpha_mode: SKIDAlphaMode,
+) -> Option<SKIDImage> {
+    let tags_differ = image.get_color_space() != color_space || image.get_alpha_mode() != alpha_mode;
+    if !tags_differ || image.get_channel_count() != 4 {
+        return None;
+    }
+    let straight = alpha::convert_alpha_mode::<R>(run_device, image, SKIDAlphaMode::Straight);
+    let encoded = color_space::convert_color_space::<R>(run_device, &straight, color_space);
+    Some(alpha::convert_alpha_mode::<R>(run_device, &encoded, alpha_mode))
+}
+
+/// 관심 영역(ROI)만 처리한다.
+///
+/// `source`의 `roi`를 `halo`만큼 넓혀 잘라낸 이미지에 `processor`를 실행하고, 결과에서 테두리를 뺀 `roi` 부분만
+/// `target`의 같은 위치에 제자리로 쓴다. `halo`는 프로세서가 읽는 이웃 반경(노멀맵 1, 블러/리사이즈는 필터 반경)이며,
+/// 이미지 밖으로 나간 테두리는 프로세서가 전체 이미지에서 쓰는 것과 같은 `edge_mode`로 채워야 결과가 전체 처리와 같아진다.
+///
+/// `roi`가 None 이면 이미지 전체를 처리한다. `processor`는 입력과 같은 크기의 이미지를 돌려줘야 한다.
+pub fn process_region<R: Runtime>(
+    run_device: &R::Device,
+    source: &SKIDImage,
+    target: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    halo: usize,
+    edge_mode: SKIDEdgeMode,
+    processor: impl FnOnce(&SKIDImage) -> SKIDImage,
+) -> Result<(), String> {
+    if source.get_size() != target.get_size() {
+        return Err(format!(
+            "Source size {:?} does not match target size {:?}",
+            source.get_size(),
+            target.get_size()
+        ));
+    }
+    let (rect, region) = run_region(source, roi, halo, edge_mode, processor)?;
+    write_region::<R>(run_device, target, rect, &region)
+}
+
+/// 결과를 `image` 자신에 쓰는 `process_region`.
+pub fn process_region_in_place<R: Runtime>(
+    run_device: &R::Device,
+    image: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    halo: usize,
+    edge_mode: SKIDEdgeMode,
+    processor: impl FnOnce(&SKIDImage) -> SKIDImage,
+) -> Result<(), String> {
+    let (rect, region) = run_region(image, roi, halo, edge_mode, processor)?;
+    write_region::<R>(run_device, image, rect, &region)
+}
+
+/// ROI 를 잘라 `processor`를 실행하고, (ROI, 테두리를 뺀 결과)를 돌려준다.
+fn run_region(
+    source: &SKIDImage,
+    roi: Option<SKIDRect>,
+    halo: usize,
+    edge_mode: SKIDEdgeMode,
+    processor: impl FnOnce(&SKIDImage) -> SKIDImage,
+) -> Result<(SKIDRect, SKIDImage), String> {
+    let size = source.get_size();
+    let rect = roi.unwrap_or(SKIDRect::new(0, 0, size.width, size.height));
+    let halo = if roi.is_some() { halo } else { 0 };
+    let input = extract_with_halo(source, rect, halo, edge_mode)?;
+    let output = processor(&input);
+    if output.get_size() != input.get_size() {
+        return Err(format!(
+            "Region processor changed the size from {:?} to {:?}",
+            input.get_size(),
+            output.get_size()
+        ));
+    }
+    let inner = output.view(SKIDRect::new(halo, halo, rect.width, rect.height))?.to_image();
+    Ok((rect, inner))
+}
@@ -1,224 +1,164 @@
-use cubecl::{cube, frontend::*, terminate, CubeCount, CubeDim, CubeElement, Runtime, prelude::*,Kernel};
-
-use crate::model::{skid_color::SKIDColor, skid_image::{SKIDImage, SKIDSizeVector2}};
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
 
+use crate::model::skid_image::{SKIDImage, SKIDSizeVector2};
+use crate::processor::{convolution::{filter_image, SKIDEdgeMode}, sampling::{sample_texel, SKIDSampling}};
+use crate::utils::gpu_opt::image_cube_config;
 
 
+/// 이미지를 `new_size`로 바이리니어 리샘플링한다.
+///
+/// `thread_count`는 이전 런치 방식의 흔적으로, 지금은 출력 크기로 런치 설정을 정하므로 쓰이지 않는다.
+#[deprecated(note = "thread_count 는 무시된다. resize_image_with_sampling(.., SKIDSampling::Bilinear)을 사용한다.")]
 pub fn resize_image<R:Runtime>(
     runtime: &R::Device,
     image: &SKIDImage,
     new_size:SKIDSizeVector2,
-    thread_count: Option<usize>
+    _thread_count: Option<usize>
 ) -> SKIDImage {
-    launch::<R>(
-        runtime,
-        image,
-        new_size,
-        thread_count
-    )
+    resize_image_with_sampling::<R>(runtime, image, new_size, SKIDSampling::Bilinear)
 }
 
-#[cube(launch_unchecked)]
-pub fn resize_scaleup_kernel<F: Float>(
-    input: &Array<F>,
-    width: u32,
-    height: u32,
-    new_width: u32,
-    new_height: u32,
-    output: &mut Array<F>,
-) {
-    let width_f = F::cast_from(width);
-    let height_f = F::cast_from(height);
-    let new_width_f = F::cast_from(new_width);
-    let new_height_f = F::cast_from(new_height);
-    let width_u = width as u32;
-
-    for x in 0..CUBE_CLUSTER_DIM_X {
-        let px = ABSOLUTE_POS_X + x;
-        for y in 0..CUBE_CLUSTER_DIM_Y {
-            let py = ABSOLUTE_POS_Y + y;
-
-            if px < new_width && py < new_height {
-                // Calculate corresponding coordinates in the original image
-                let original_x_f = (F::cast_from(px) + F::new(0.5)) * width_f / new_width_f - F::new(0.5);
-                let original_y_f = (F::cast_from(py) + F::new(0.5)) * height_f / new_height_f - F::new(0.5);
-
-                // Get integer coordinates and fractional parts for interpolation
-                let x0 = F::floor(original_x_f);
-                let y0 = F::floor(original_y_f);
-                let tx = original_x_f - x0;
-                let ty = original_y_f - y0;
-
-                let x0_u = u32::cast_from(F::max(x0, F::new(0.)));  
-                let y0_u = u32::cast_from(F::max(y0, F::new(0.)));  
-
-                
-                // Clamp coordinates to be within bounds
-                let x1_u = if x0_u + 1 < width - 1 {
-                    x0_u + 1
-                } else {
-                    width - 1
-                };
-                let y1_u = if y0_u + 1 < height - 1 {
-                    y0_u + 1
-                } else {
-                    height - 1
-                };
-                let x0_clamped_u = x0_u;
-                let y0_clamped_u = y0_u;
-
-                // Get the four surrounding pixels (c00, c10, c01, c11)
-                let c00_idx = y0_clamped_u * width_u + x0_clamped_u;
-                let c10_idx = y0_clamped_u * width_u + x1_u;
-                let c01_idx = y1_u * width_u + x0_clamped_u;
-                let c11_idx = y1_u * width_u + x1_u;
-
-                let new_idx = (py * new_width + px) * 4;
+/// 지정한 보간 방식으로 이미지를 `new_size`로 리샘플링한다.
+///
+/// 축소할 때는 출력 픽셀 하나가 덮는 원본 영역을 여러 점으로 나눠 샘플링한 평균을 쓰므로
+/// 가는 선이 사라지거나 모아레가 생기지 않는다. 이미지 밖은 가장자리 픽셀을 반복한다.
+pub fn resize_image_with_sampling<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    new_size: SKIDSizeVector2,
+    sampling: SKIDSampling,
+) -> SKIDImage {
+    let size = image.get_size();
+    let scale = (
+        size.width as f32 / new_size.width.max(1) as f32,
+        size.height as f32 / new_size.height.max(1) as f32,
+    );
+    resize_tile::<R>(run_device, image, new_size, scale, (0.0, 0.0), sampling)
+}
 
-                // Interpolate for each channel (R, G, B, A)
-                for i in 0..4 {
-                    let c00 = input[c00_idx][i];
-                    let c10 = input[c10_idx][i];
-                    let c01 = input[c01_idx][i];
-                    let c11 = input[c11_idx][i];
+/// `source`를 `scale`(출력 1픽셀당 원본 픽셀 수) 배율로 샘플링해 `out_size` 크기로 만든다.
+/// 출력 (0, 0) 픽셀의 왼쪽 위 모서리는 `source`의 `origin` 좌표에 해당한다.
+///
+/// 큰 이미지를 타일로 나눠 리사이즈할 때, 각 타일이 전체 이미지에서와 같은 위치를 샘플링하도록 쓴다.
+pub(crate) fn resize_tile<R: Runtime>(
+    run_device: &R::Device,
+    source: &SKIDImage,
+    out_size: SKIDSizeVector2,
+    scale: (f32, f32),
+    origin: (f32, f32),
+    sampling: SKIDSampling,
+) -> SKIDImage {
+    // 1·2채널 데이터(높이맵, 마스크)는 색이 아니므로 색 공간/알파 변환 없이 네이티브 채널 수로 리샘플링한다.
+    // 4채널은 감마 공간에서 보간하면 경계가 어두워지고, straight 알파로 보간하면 투명 픽셀의 색이 번지므로
+    // linear + 프리멀티플라이드 공간에서 리샘플링한 뒤 원래 형식으로 되돌린다.
+    filter_image::<R>(run_device, source, "resize_image", |img| {
+        launch::<R>(run_device, img, out_size, [scale.0, scale.1, origin.0, origin.1], sampling)
+    })
+}
 
-                    // Lerp horizontally
-                    let top_lerp = c00 * (F::new(1.0) - tx) + c10 * tx;
-                    let bottom_lerp = c01 * (F::new(1.0) - tx) + c11 * tx;
+/// 한 축에서 출력 픽셀 하나당 원본을 몇 점 샘플링할지. 확대할 때는 1 이다.
+fn samples_per_pixel(scale: f32) -> u32 {
+    (scale - 1.0e-4).ceil().max(1.0) as u32
+}
 
-                    // Lerp vertically
-                    let final_val = top_lerp * (F::new(1.0) - ty) + bottom_lerp * ty;
-                    output[new_idx + i] = final_val;
-                }
-            }
-        }
-    }
+/// `kernel_resize`의 샘플링 설정. comptime 이므로 값 조합마다 커널이 따로 컴파일된다.
+/// 배율에 따라 달라지는 샘플 수는 런타임 값(`params`)으로 넘겨 배율마다 다시 컴파일하지 않게 한다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ResizeSamplingConfig {
+    edge_mode: u32,
+    sampling: u32,
 }
 
-// This kernel uses a "gather" approach, iterating over output pixels.
-// This avoids race conditions from multiple threads writing to the same location.
+/// 출력 픽셀 (px, py)가 덮는 원본 영역을 samples_x x samples_y 개 점으로 나눠 공용 샘플러로 읽고 평균한다.
+/// `mapping`은 [scale_x, scale_y, origin_x, origin_y] 이다.
 #[cube(launch_unchecked)]
-fn resize_scaledown_kernel<F: Float>(
+fn kernel_resize<F: Float>(
     input: &Array<F>,
-    width: u32,
-    height: u32,
-    new_width: u32,
-    new_height: u32,
+    constant: &Array<F>,
+    mapping: &Array<F>,
+    params: &Array<u32>,
     output: &mut Array<F>,
+    #[comptime] config: ResizeSamplingConfig,
 ) {
-    //new_width < width && new_height < height 인 경우에만 작동합니다.
-    let width_f = F::cast_from(width);
-    let height_f = F::cast_from(height);
-    let new_width_f = F::cast_from(new_width);
-    let new_height_f = F::cast_from(new_height);
-    let width_u = width as u32;
-    let height_u = height as u32;
-
-    for x in 0..CUBE_CLUSTER_DIM_X {
-        let px = ABSOLUTE_POS_X + x;
-        for y in 0..CUBE_CLUSTER_DIM_Y {
-            let py = ABSOLUTE_POS_Y + y;
-            if px < new_width && py < new_height {
-                let new_idx = (py * new_width + px) * 4;
-
+    // params: [width, height, new_width, new_height, channels, samples_x, samples_y]
+    let width = params[0];
+    let height = params[1];
+    let new_width = params[2];
+    let new_height = params[3];
+    let channels = params[4];
+    let samples_x = params[5];
+    let samples_y = params[6];
+    let edge_mode = config.edge_mode;
+    let sampling = config.sampling;
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < new_width && py < new_height {
+        let scale_x = mapping[0];
+        let scale_y = mapping[1];
+        let inv_count = F::new(1.) / F::cast_from(samples_x * samples_y);
+        let idx = (py * new_width + px) * channels;
+
+        for c in 0..channels {
+            let mut sum = F::new(0.);
+            for sy in 0..samples_y {
+                let y = mapping[3] + (F::cast_from(py) + (F::cast_from(sy) + F::new(0.5)) / F::cast_from(samples_y)) * scale_y;
+                for sx in 0..samples_x {
+                    let x = mapping[2] + (F::cast_from(px) + (F::cast_from(sx) + F::new(0.5)) / F::cast_from(samples_x)) * scale_x;
+                    sum += sample_texel::<F>(input, constant, x, y, c, width, height, edge_mode, sampling);
+                }
             }
+            output[idx + c] = sum * inv_count;
         }
     }
 }
 
-
-fn launch<T: Runtime>(
-    run_device: &T::Device,
+fn launch<R: Runtime>(
+    run_device: &R::Device,
     original_image: &SKIDImage,
     new_size: SKIDSizeVector2,
-    thread_count: Option<usize>
+    mapping: [f32; 4],
+    sampling: SKIDSampling,
 ) -> SKIDImage {
-    let client = T::client(run_device);
-    let thread_count = thread_count.unwrap_or(4);
-    let input = original_image.get_1d_data_as_f32();
-    
-    let new_width = new_size.width as u32;
-    let new_height = new_size.height as u32;
-    let output_handle = client.empty(new_size.width * new_size.height * 4 * core::mem::size_of::<f32>());
-    let pixel_count = input.len() / 4; // Assuming each color has 4 components (RGBA)
-    let input_handle = client.create(bytemuck::cast_slice(&input));
-    let (max_thread_x, max_thread_y, _max_thread_z) = T::max_cube_count();
+    let client = R::client(run_device);
+    let size = original_image.get_size();
+    let channels = original_image.get_channel_count();
+    let (new_width, new_height) = (new_size.width as u32, new_size.height as u32);
+    let out_len = new_size.width * new_size.height * channels;
 
-    let thread_x_o = if new_width < max_thread_x { new_width } else { max_thread_x };
-    let thread_y_o = if new_height < max_thread_y { new_height } else { max_thread_y };
-
-    let thread_x = if thread_x_o * thread_y_o > 1024 {
-        new_width / 32
-    } else {
-        thread_count as u32
-    };
-    let thread_y = if thread_x_o * thread_y_o > 1024 {
-        new_height / 32
-    } else {
-        thread_count as u32
+    let input = original_image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let constant_handle = client.create(bytemuck::cast_slice(&[0.0f32; 4]));
+    let mapping_handle = client.create(bytemuck::cast_slice(&mapping));
+    let params = [
+        size.width as u32,
+        size.height as u32,
+        new_width,
+        new_height,
+        channels as u32,
+        samples_per_pixel(mapping[0]),
+        samples_per_pixel(mapping[1]),
+    ];
+    let params_handle = client.create(bytemuck::cast_slice(&params));
+    let output_handle = client.empty(out_len * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(new_width, new_height);
+
+    unsafe {
+        kernel_resize::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, size.width * size.height, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&constant_handle, 4, 1),
+            ArrayArg::from_raw_parts::<f32>(&mapping_handle, 4, 1),
+            ArrayArg::from_raw_parts::<u32>(&params_handle, params.len(), 1),
+            ArrayArg::from_raw_parts::<f32>(&output_handle, out_len, 1),
+            ResizeSamplingConfig {
+                edge_mode: SKIDEdgeMode::Clamp.to_u32(),
+                sampling: sampling.to_u32(),
+            },
+        )
     };
-    let block_x = (new_width as u32) / thread_x;
-    let block_y = (new_height as u32) / thread_y;
-
-    println!("Launching resize with runtime: {}x{}", new_width, new_height);
-    println!("Threads: {}x{}", thread_x, thread_y);
-    println!("Blocks: {}x{}", block_x, block_y);
-    if new_width > original_image.get_size().width as u32 {
-        unsafe {
-            resize_scaleup_kernel::launch_unchecked::<f32, T>(
-                &client,
-                CubeCount::Static(thread_x as u32, thread_y as u32, 1),
-                CubeDim::new(block_x as u32, block_y as u32, 1),
-                ArrayArg::from_raw_parts::<f32>(
-                    &input_handle,
-                    pixel_count, 
-                    4
-                ),
-                ScalarArg { elem: original_image.get_size().width as u32 },
-                ScalarArg { elem: original_image.get_size().height as u32 },
-                ScalarArg { elem: new_width },
-                ScalarArg { elem: new_height },
-                ArrayArg::from_raw_parts::<f32>(
-                    &output_handle, 
-                    new_size.width * new_size.height, 
-                    1
-                )
-            );
-        }
-    } else {
-        unsafe {
-            resize_scaledown_kernel::launch_unchecked::<f32, T>(
-                &client,
-                CubeCount::Static(thread_x as u32, thread_y as u32, 1),
-                CubeDim::new(block_x as u32, block_y as u32, 1),
-                ArrayArg::from_raw_parts::<f32>(
-                    &input_handle,
-                    pixel_count, 
-                    4
-                ),
-                ScalarArg { elem: original_image.get_size().width as u32 },
-                ScalarArg { elem: original_image.get_size().height as u32 },
-                ScalarArg { elem: new_width },
-                ScalarArg { elem: new_height },
-                ArrayArg::from_raw_parts::<f32>(
-                    &output_handle, 
-                    new_size.width * new_size.height, 
-                    1
-                )
-            );
-        }
-    }
-
 
     let bytes = client.read_one(output_handle.binding());
-    let output = f32::from_bytes(&bytes);
-    
-    let output_colors: Vec<SKIDColor> = output.chunks(4)
-        .map(|chunk| SKIDColor::from_f32_array(chunk.try_into().unwrap()))
-        .collect();
-    
-    SKIDImage::from_1d_data(
-        new_size, 
-        output_colors
-    )
-}
\ No newline at end of file
+    SKIDImage::from_f32_data(new_size, original_image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
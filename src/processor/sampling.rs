@@ -0,0 +1,105 @@
+use cubecl::{cube, prelude::*};
+
+use crate::processor::convolution::load_texel;
+
+/// 픽셀 사이 위치의 값을 읽을 때의 보간 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDSampling {
+    /// 가장 가까운 픽셀 (값을 섞지 않는다. 픽셀 아트, 마스크용)
+    Nearest = 0,
+    /// 주변 2x2 픽셀 선형 보간
+    #[default]
+    Bilinear = 1,
+    /// 주변 4x4 픽셀 Catmull-Rom 보간 (더 선명하지만 경계에서 약간 넘칠 수 있다)
+    Bicubic = 2,
+}
+
+impl SKIDSampling {
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDSampling::Nearest),
+            1 => Some(SKIDSampling::Bilinear),
+            2 => Some(SKIDSampling::Bicubic),
+            _ => None,
+        }
+    }
+    /// 한 점을 샘플링할 때 읽는 픽셀이 중심에서 떨어질 수 있는 최대 거리 (픽셀).
+    pub fn support_radius(self) -> usize {
+        match self {
+            SKIDSampling::Nearest => 0,
+            SKIDSampling::Bilinear => 1,
+            SKIDSampling::Bicubic => 2,
+        }
+    }
+}
+
+/// Catmull-Rom (a = -0.5) 3차 보간 가중치.
+#[cube]
+fn cubic_weight<F: Float>(t: F) -> F {
+    let x = F::abs(t);
+    let mut w = F::new(0.);
+    if x < F::new(1.) {
+        w = (F::new(1.5) * x - F::new(2.5)) * x * x + F::new(1.);
+    } else if x < F::new(2.) {
+        w = ((F::new(-0.5) * x + F::new(2.5)) * x - F::new(4.)) * x + F::new(2.);
+    }
+    w
+}
+
+/// 연속 좌표 (x, y)에서 채널 `c`의 값을 읽는다. 픽셀 (i, j)의 중심은 (i + 0.5, j + 0.5)이다.
+///
+/// 이미지 밖 픽셀은 `edge_mode`(`SKIDEdgeMode` 코드)에 따라 처리하고, `sampling`은 `SKIDSampling` 코드이다.
+/// 리사이즈/회전/아핀 변환 커널이 같은 샘플러를 쓴다.
+#[cube]
+pub fn sample_texel<F: Float>(
+    input: &Array<F>,
+    constant: &Array<F>,
+    x: F,
+    y: F,
+    c: u32,
+    width: u32,
+    height: u32,
+    edge_mode: u32,
+    sampling: u32,
+) -> F {
+    let mut value = F::new(0.);
+    if sampling == 0 {
+        let ix = i32::cast_from(F::floor(x));
+        let iy = i32::cast_from(F::floor(y));
+        value = load_texel::<F>(input, constant, ix, iy, c, width, height, edge_mode);
+    } else {
+        // 픽셀 중심이 정수 좌표가 되도록 옮긴 뒤 보간한다.
+        let fx = x - F::new(0.5);
+        let fy = y - F::new(0.5);
+        let x0 = F::floor(fx);
+        let y0 = F::floor(fy);
+        let tx = fx - x0;
+        let ty = fy - y0;
+        let ix = i32::cast_from(x0);
+        let iy = i32::cast_from(y0);
+        if sampling == 1 {
+            let c00 = load_texel::<F>(input, constant, ix, iy, c, width, height, edge_mode);
+            let c10 = load_texel::<F>(input, constant, ix + 1, iy, c, width, height, edge_mode);
+            let c01 = load_texel::<F>(input, constant, ix, iy + 1, c, width, height, edge_mode);
+            let c11 = load_texel::<F>(input, constant, ix + 1, iy + 1, c, width, height, edge_mode);
+            let top = c00 + (c10 - c00) * tx;
+            let bottom = c01 + (c11 - c01) * tx;
+            value = top + (bottom - top) * ty;
+        } else {
+            for j in 0..4u32 {
+                let wy = cubic_weight::<F>(ty + F::new(1.) - F::cast_from(j));
+                let sy = iy - 1 + j as i32;
+                for i in 0..4u32 {
+                    let wx = cubic_weight::<F>(tx + F::new(1.) - F::cast_from(i));
+                    let sx = ix - 1 + i as i32;
+                    value += wx * wy * load_texel::<F>(input, constant, sx, sy, c, width, height, edge_mode);
+                }
+            }
+        }
+    }
+    value
+}
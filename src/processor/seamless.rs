@@ -0,0 +1,276 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::skid_image::{SKIDImage, SKIDSizeVector2},
+    processor::convolution::filter_image,
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 이음매 없는(타일링 가능한) 텍스처를 만드는 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDSeamlessMode {
+    /// 이미지를 절반만큼 감아 돌린 사본을 가장자리 근처에 섞는다. 크기가 유지된다.
+    #[default]
+    OffsetBlend = 0,
+    /// 반대쪽 가장자리의 `blend_width` 픽셀을 시작 부분에 크로스페이드한다.
+    /// 결과는 가로/세로가 각각 `blend_width`만큼 줄어든다.
+    CrossFade = 1,
+}
+
+impl SKIDSeamlessMode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDSeamlessMode::OffsetBlend),
+            1 => Some(SKIDSeamlessMode::CrossFade),
+            _ => None,
+        }
+    }
+}
+
+#[cube]
+fn smooth_weight<F: Float>(t: F) -> F {
+    let c = F::min(F::max(t, F::new(0.)), F::new(1.));
+    c * c * (F::new(3.) - F::new(2.) * c)
+}
+
+/// 가장자리에서 `blend_width` 안쪽까지 원본을 반 바퀴 이동한 사본(가장자리에서 연속)으로 점차 바꾼다.
+/// 사본의 이음매는 이미지 중앙에 있으므로 `blend_width`가 크기의 절반보다 작으면 결과에 나타나지 않는다.
+#[cube(launch_unchecked)]
+fn kernel_offset_blend<F: Float>(
+    input: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    blend_width: F,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let ox = (px + width / 2) % width;
+        let oy = (py + height / 2) % height;
+        let offset_idx = oy * width + ox;
+
+        let edge_x = F::cast_from(select(px < width - 1 - px, px, width - 1 - px));
+        let edge_y = F::cast_from(select(py < height - 1 - py, py, height - 1 - py));
+        let wx = F::new(1.) - smooth_weight::<F>(edge_x / blend_width);
+        let wy = F::new(1.) - smooth_weight::<F>(edge_y / blend_width);
+        let w = F::max(wx, wy);
+
+        for c in 0..channels {
+            output[idx * channels + c] = input[idx][c] * (F::new(1.) - w) + input[offset_idx][c] * w;
+        }
+    }
+}
+
+/// 출력 (x, y)는 원본 (x, y)와 한 주기(out_width, out_height) 뒤의 원본을 섞는다.
+/// x = 0 에서는 원본 (out_width, y)를 그대로 쓰므로 출력의 오른쪽 끝 (out_width - 1)과 이어진다.
+#[cube(launch_unchecked)]
+fn kernel_cross_fade<F: Float>(
+    input: &Array<F>,
+    in_width: u32,
+    out_width: u32,
+    out_height: u32,
+    channels: u32,
+    blend_width: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < out_width && py < out_height {
+        let idx = py * out_width + px;
+        let mut tx = F::new(1.);
+        if px < blend_width {
+            tx = smooth_weight::<F>(F::cast_from(px) / F::cast_from(blend_width));
+        }
+        let mut ty = F::new(1.);
+        if py < blend_width {
+            ty = smooth_weight::<F>(F::cast_from(py) / F::cast_from(blend_width));
+        }
+        let i00 = py * in_width + px;
+        let i10 = py * in_width + px + out_width;
+        let i01 = (py + out_height) * in_width + px;
+        let i11 = (py + out_height) * in_width + px + out_width;
+        let w00 = tx * ty;
+        let w10 = (F::new(1.) - tx) * ty;
+        let w01 = tx * (F::new(1.) - ty);
+        let w11 = (F::new(1.) - tx) * (F::new(1.) - ty);
+
+        for c in 0..channels {
+            // 크로스페이드 구간 밖에서는 w10/w01/w11 이 0 이므로 범위 밖 인덱스를 읽지 않게 한다.
+            let mut value = input[i00][c] * w00;
+            if w10 > F::new(0.) {
+                value += input[i10][c] * w10;
+            }
+            if w01 > F::new(0.) {
+                value += input[i01][c] * w01;
+            }
+            if w11 > F::new(0.) {
+                value += input[i11][c] * w11;
+            }
+            output[idx * channels + c] = value;
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+fn kernel_tile<F: Float>(
+    input: &Array<F>,
+    in_width: u32,
+    in_height: u32,
+    out_width: u32,
+    out_height: u32,
+    channels: u32,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < out_width && py < out_height {
+        let src = (py % in_height) * in_width + (px % in_width);
+        let idx = py * out_width + px;
+        for c in 0..channels {
+            output[idx * channels + c] = input[src][c];
+        }
+    }
+}
+
+/// 타일링했을 때 가장자리 이음매가 보이지 않도록 이미지를 가공한다.
+///
+/// 4채널 입력은 다른 필터와 같이 linear + 프리멀티플라이드 공간에서 섞는다.
+/// - `OffsetBlend`: `blend_width`는 가장자리에서 섞이는 폭(픽셀)이며, 짧은 변의 절반 미만으로 제한된다.
+/// - `CrossFade`  : `blend_width`는 크로스페이드 폭(픽셀)이며, 결과 크기는 (w - blend_width, h - blend_width)가 된다.
+pub fn make_seamless<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    mode: SKIDSeamlessMode,
+    blend_width: u32,
+) -> Result<SKIDImage, String> {
+    let size = image.get_size();
+    let short_side = size.width.min(size.height);
+    if blend_width == 0 {
+        return Err("blend_width must be at least 1 pixel".to_string());
+    }
+    match mode {
+        SKIDSeamlessMode::OffsetBlend => {
+            let blend_width = Ord::min(blend_width, Ord::max((short_side as u32 / 2).saturating_sub(1), 1));
+            Ok(filter_image::<R>(run_device, image, "make_seamless", |img| {
+                launch_offset_blend::<R>(run_device, img, blend_width)
+            }))
+        }
+        SKIDSeamlessMode::CrossFade => {
+            if blend_width as usize * 2 > short_side {
+                return Err(format!(
+                    "Cross-fade width {} is larger than half of the image ({}x{})",
+                    blend_width, size.width, size.height
+                ));
+            }
+            Ok(filter_image::<R>(run_device, image, "make_seamless", |img| {
+                launch_cross_fade::<R>(run_device, img, blend_width)
+            }))
+        }
+    }
+}
+
+/// 이음매 확인용으로 이미지를 가로 `columns`개, 세로 `rows`개 반복해 붙인 이미지를 만든다.
+/// 값은 변환 없이 그대로 복사한다.
+pub fn tile_preview<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    columns: u32,
+    rows: u32,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let out_size = SKIDSizeVector2::new(size.width * Ord::max(columns, 1) as usize, size.height * Ord::max(rows, 1) as usize);
+    let (out_w, out_h) = (out_size.width as u32, out_size.height as u32);
+    let out_pixel_count = out_size.width * out_size.height;
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(out_pixel_count * channels * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(out_w, out_h);
+
+    unsafe {
+        kernel_tile::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, size.width * size.height, channels as u8),
+            ScalarArg { elem: size.width as u32 },
+            ScalarArg { elem: size.height as u32 },
+            ScalarArg { elem: out_w },
+            ScalarArg { elem: out_h },
+            ScalarArg { elem: channels as u32 },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, out_pixel_count * channels, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(out_size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+        .with_metadata_from(image)
+}
+
+fn launch_offset_blend<R: Runtime>(run_device: &R::Device, image: &SKIDImage, blend_width: u32) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_offset_blend::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: blend_width as f32 },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
+
+fn launch_cross_fade<R: Runtime>(run_device: &R::Device, image: &SKIDImage, blend_width: u32) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let out_size = SKIDSizeVector2::new(size.width - blend_width as usize, size.height - blend_width as usize);
+    let (out_w, out_h) = (out_size.width as u32, out_size.height as u32);
+    let out_pixel_count = out_size.width * out_size.height;
+
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(out_pixel_count * channels * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(out_w, out_h);
+
+    unsafe {
+        kernel_cross_fade::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, size.width * size.height, channels as u8),
+            ScalarArg { elem: size.width as u32 },
+            ScalarArg { elem: out_w },
+            ScalarArg { elem: out_h },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: blend_width },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, out_pixel_count * channels, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(out_size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+}
@@ -0,0 +1,241 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{skid_image::{SKIDImage, SKIDRect}, skid_pixel_format::SKIDPixelFormat},
+    processor::{
+        alpha,
+        blur::{gaussian_blur_values, gaussian_halo, SKIDGaussianMode},
+        convolution::{SKIDConvolutionOptions, SKIDEdgeMode},
+        region,
+    },
+    utils::{gpu_opt::image_cube_config, graphic_fn::compute_luminance},
+};
+
+/// 디테일(원본 - 블러)을 계산할 채널.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDDetailMode {
+    /// 색상 채널마다 따로 계산한다.
+    #[default]
+    PerChannel = 0,
+    /// 휘도(`compute_luminance`) 차이 하나를 모든 색상 채널에 쓴다. 색 번짐(fringe)이 생기지 않는다.
+    /// 3채널 미만 입력에서는 `PerChannel`과 같다.
+    Luminance = 1,
+}
+
+impl SKIDDetailMode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDDetailMode::PerChannel),
+            1 => Some(SKIDDetailMode::Luminance),
+            _ => None,
+        }
+    }
+}
+
+/// 이 반경(σ)을 넘으면 블러를 박스 3회 근사로 계산한다.
+const APPROXIMATE_BLUR_RADIUS: f32 = 8.0;
+
+/// 하이패스 필터: 0.5 + (원본 - 블러) * amount.
+///
+/// 결과는 0.5 를 중심으로 한 디테일 맵이므로 색 공간/알파 변환 없이 저장된 값 그대로 계산하며,
+/// 알파 채널은 원본을 유지한다. 높이맵의 고주파 성분을 `make_normal_map_base`에 넘길 때 사용한다.
+///
+/// - `radius`   : 블러 표준편차 (픽셀)
+/// - `amount`   : 디테일 배율
+/// - `threshold`: |원본 - 블러|가 이보다 작은 픽셀은 0.5 로 둔다 (노이즈 억제)
+pub fn high_pass<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    radius: f32,
+    amount: f32,
+    threshold: f32,
+    mode: SKIDDetailMode,
+) -> SKIDImage {
+    let (widened, mode) = widen(image, mode);
+    launch::<R>(run_device, &widened, radius, amount, threshold, mode, false).convert_format(image.get_pixel_format())
+}
+
+/// 언샤프 마스크: 원본 + (원본 - 블러) * amount.
+///
+/// 4채널 입력은 다른 필터와 같이 linear + 프리멀티플라이드 공간에서 계산한 뒤 원래 형식으로 되돌린다.
+///
+/// - `radius`   : 블러 표준편차 (픽셀)
+/// - `amount`   : 선명도 배율 (1.0 = 디테일을 한 번 더 더함)
+/// - `threshold`: |원본 - 블러|가 이보다 작은 픽셀은 건드리지 않는다 (평탄한 영역의 노이즈 증폭 방지)
+pub fn unsharp_mask<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    radius: f32,
+    amount: f32,
+    threshold: f32,
+    mode: SKIDDetailMode,
+) -> SKIDImage {
+    if image.get_channel_count() < 4 {
+        let (widened, mode) = widen(image, mode);
+        return launch::<R>(run_device, &widened, radius, amount, threshold, mode, true)
+            .convert_format(image.get_pixel_format());
+    }
+    let (prepared, state) = alpha::prepare_filter_input::<R>(run_device, image, "unsharp_mask");
+    let sharpened = launch::<R>(run_device, &prepared, radius, amount, threshold, mode, true);
+    alpha::restore_filter_output::<R>(run_device, sharpened, &state)
+}
+
+/// `image`의 `roi` 영역만 `high_pass`로 처리해 `target`의 같은 위치에 제자리로 쓴다.
+///
+/// 영역을 블러 반경만큼 넓혀 잘라낸 뒤 처리한다. 블러와 같이 이미지 밖은 가장자리 픽셀을 반복한다.
+#[allow(clippy::too_many_arguments)]
+pub fn high_pass_region<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    radius: f32,
+    amount: f32,
+    threshold: f32,
+    mode: SKIDDetailMode,
+) -> Result<(), String> {
+    region::process_region::<R>(run_device, image, target, roi, detail_halo(radius), SKIDEdgeMode::Clamp, |region| {
+        high_pass::<R>(run_device, region, radius, amount, threshold, mode)
+    })
+}
+
+/// `image`의 `roi` 영역만 `unsharp_mask`로 처리해 `target`의 같은 위치에 제자리로 쓴다. (`high_pass_region` 참고)
+#[allow(clippy::too_many_arguments)]
+pub fn unsharp_mask_region<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    target: &mut SKIDImage,
+    roi: Option<SKIDRect>,
+    radius: f32,
+    amount: f32,
+    threshold: f32,
+    mode: SKIDDetailMode,
+) -> Result<(), String> {
+    region::process_region::<R>(run_device, image, target, roi, detail_halo(radius), SKIDEdgeMode::Clamp, |region| {
+        unsharp_mask::<R>(run_device, region, radius, amount, threshold, mode)
+    })
+}
+
+/// 반경(σ)에 따라 고른 블러 방식.
+fn blur_mode(radius: f32) -> SKIDGaussianMode {
+    if radius > APPROXIMATE_BLUR_RADIUS {
+        SKIDGaussianMode::Approximate
+    } else {
+        SKIDGaussianMode::Exact
+    }
+}
+
+/// 디테일 계산이 읽는 이웃 반경. 블러 반경과 같다.
+fn detail_halo(radius: f32) -> usize {
+    if radius <= 0.0 {
+        return 0;
+    }
+    gaussian_halo(radius, blur_mode(radius))
+}
+
+/// 블러 결과가 8/16비트로 양자화되지 않도록 f32 형식으로 넓힌다.
+///
+/// 2채널 f32 형식이 없으므로 RG8 은 RGBA32F 로 넓히고, 늘어난 B 채널이 휘도에 섞이지 않도록 `PerChannel`로 계산한다.
+fn widen(image: &SKIDImage, mode: SKIDDetailMode) -> (SKIDImage, SKIDDetailMode) {
+    match image.get_channel_count() {
+        1 => (image.convert_format(SKIDPixelFormat::R32F), mode),
+        2 => (image.convert_format(SKIDPixelFormat::RGBA32F), SKIDDetailMode::PerChannel),
+        _ => (image.convert_format(SKIDPixelFormat::RGBA32F), mode),
+    }
+}
+
+#[cube(launch_unchecked)]
+fn kernel_detail<F: Float>(
+    original: &Array<F>,
+    blurred: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    luminance: u32,
+    unsharp: u32,
+    amount: F,
+    threshold: F,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let color_channels = select(channels < 3, channels, 3u32);
+        let use_luminance = luminance == 1 && channels >= 3;
+
+        let mut luminance_diff = F::new(0.);
+        if use_luminance {
+            luminance_diff = compute_luminance::<F>(original[idx][0], original[idx][1], original[idx][2])
+                - compute_luminance::<F>(blurred[idx][0], blurred[idx][1], blurred[idx][2]);
+        }
+
+        for c in 0..channels {
+            let value = original[idx][c];
+            let mut result = value;
+            if c < color_channels {
+                let diff = if use_luminance { luminance_diff } else { value - blurred[idx][c] };
+                let passes = F::abs(diff) >= threshold;
+                if unsharp == 1 {
+                    if passes {
+                        result = F::max(value + diff * amount, F::new(0.));
+                    }
+                } else {
+                    result = F::new(0.5);
+                    if passes {
+                        result = F::new(0.5) + diff * amount;
+                    }
+                }
+            }
+            output[idx * channels + c] = result;
+        }
+    }
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    radius: f32,
+    amount: f32,
+    threshold: f32,
+    mode: SKIDDetailMode,
+    unsharp: bool,
+) -> SKIDImage {
+    let blurred = gaussian_blur_values::<R>(run_device, image, radius, blur_mode(radius), &SKIDConvolutionOptions::default());
+
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let channels = image.get_channel_count();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let original_data = image.get_1d_data_as_f32_channels();
+    let blurred_data = blurred.get_1d_data_as_f32_channels();
+    let original_handle = client.create(bytemuck::cast_slice(&original_data));
+    let blurred_handle = client.create(bytemuck::cast_slice(&blurred_data));
+    let output_handle = client.empty(original_data.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_detail::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&original_handle, pixel_count, channels as u8),
+            ArrayArg::from_raw_parts::<f32>(&blurred_handle, pixel_count, channels as u8),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: (mode == SKIDDetailMode::Luminance) as u32 },
+            ScalarArg { elem: unsharp as u32 },
+            ScalarArg { elem: amount },
+            ScalarArg { elem: threshold.max(0.0) },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, original_data.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), channels)
+        .with_metadata_from(image)
+}
@@ -0,0 +1,216 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{model::skid_image::SKIDImage, utils::gpu_opt::image_cube_config};
+
+/// 채널별 이미지 통계. 값은 색 공간 변환 없이 저장된 값 기준이다.
+/// `channel_count`를 넘는 채널 항목은 0 이다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SKIDImageStatistics {
+    pub channel_count: u32,
+    pub min: [f32; 4],
+    pub max: [f32; 4],
+    pub mean: [f32; 4],
+    /// 모표준편차 (n 으로 나눔)
+    pub std_dev: [f32; 4],
+}
+
+/// 리덕션 큐브 하나의 유닛 수.
+const REDUCE_UNITS: u32 = 128;
+/// 리덕션 큐브 수의 상한. 부분 결과는 호스트에서 f64 로 합친다.
+const MAX_REDUCE_CUBES: u32 = 512;
+/// 큐브당 부분 결과: [min; 4], [max; 4], [Σ(x - shift); 4], [Σ(x - shift)²; 4]
+const PARTIAL_FLOATS: u32 = 16;
+
+/// 큐브마다 격자 간격(stride)으로 픽셀을 나눠 읽고 공유 메모리에서 트리 리덕션한다.
+/// 분산의 정밀도를 위해 합은 `shift`(1차 패스의 평균)를 뺀 값으로 누적한다.
+#[cube(launch_unchecked)]
+fn kernel_reduce_statistics<F: Float>(
+    input: &Array<F>,
+    shift: &Array<F>,
+    pixel_count: u32,
+    channels: u32,
+    partials: &mut Array<F>,
+) {
+    let mut shared = SharedMemory::<F>::new(comptime!(REDUCE_UNITS * PARTIAL_FLOATS));
+    let unit = UNIT_POS_X;
+    let base = unit * PARTIAL_FLOATS;
+    for c in 0..4u32 {
+        shared[base + c] = F::new(3.0e38);
+        shared[base + 4 + c] = F::new(-3.0e38);
+        shared[base + 8 + c] = F::new(0.);
+        shared[base + 12 + c] = F::new(0.);
+    }
+
+    let stride = CUBE_COUNT_X * REDUCE_UNITS;
+    let mut i = CUBE_POS_X * REDUCE_UNITS + unit;
+    while i < pixel_count {
+        for c in 0..channels {
+            let value = input[i][c];
+            let d = value - shift[c];
+            shared[base + c] = F::min(shared[base + c], value);
+            shared[base + 4 + c] = F::max(shared[base + 4 + c], value);
+            shared[base + 8 + c] += d;
+            shared[base + 12 + c] += d * d;
+        }
+        i += stride;
+    }
+    sync_units();
+
+    let mut half = REDUCE_UNITS / 2;
+    while half > 0 {
+        if unit < half {
+            let other = (unit + half) * PARTIAL_FLOATS;
+            for c in 0..4u32 {
+                shared[base + c] = F::min(shared[base + c], shared[other + c]);
+                shared[base + 4 + c] = F::max(shared[base + 4 + c], shared[other + 4 + c]);
+                let other_sum = shared[other + 8 + c];
+                let other_square_sum = shared[other + 12 + c];
+                shared[base + 8 + c] += other_sum;
+                shared[base + 12 + c] += other_square_sum;
+            }
+        }
+        sync_units();
+        half /= 2;
+    }
+
+    if unit < PARTIAL_FLOATS {
+        partials[CUBE_POS_X * PARTIAL_FLOATS + unit] = shared[unit];
+    }
+}
+
+/// 값을 [range_min, range_max] 구간의 bins 개 칸으로 나눠 센다. 범위 밖 값은 양 끝 칸에 넣는다.
+#[cube(launch_unchecked)]
+fn kernel_histogram<F: Float>(
+    input: &Array<F>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    bins: u32,
+    range_min: F,
+    range_max: F,
+    histogram: &mut Array<Atomic<u32>>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let i = py * width + px;
+        let scale = F::cast_from(bins) / (range_max - range_min);
+        for c in 0..channels {
+            let t = F::floor((input[i][c] - range_min) * scale);
+            let clamped = F::min(F::max(t, F::new(0.)), F::cast_from(bins - 1));
+            let bin = u32::cast_from(clamped);
+            Atomic::add(&histogram[c * bins + bin], 1u32);
+        }
+    }
+}
+
+/// 채널별 최솟값, 최댓값, 평균, 표준편차를 GPU 리덕션으로 계산한다.
+///
+/// 평균을 구하는 1차 패스와 평균을 뺀 제곱합을 구하는 2차 패스로 나눠 큰 이미지에서도 분산이 안정적이다.
+pub fn compute_statistics<R: Runtime>(run_device: &R::Device, image: &SKIDImage) -> SKIDImageStatistics {
+    let size = image.get_size();
+    let pixel_count = size.width * size.height;
+    let channels = image.get_channel_count();
+    let mut stats = SKIDImageStatistics { channel_count: channels as u32, ..Default::default() };
+    if pixel_count == 0 {
+        return stats;
+    }
+
+    let client = R::client(run_device);
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let cubes = (pixel_count as u32).div_ceil(REDUCE_UNITS).clamp(1, MAX_REDUCE_CUBES);
+
+    let reduce = |shift: [f32; 4]| -> Vec<f32> {
+        let shift_handle = client.create(bytemuck::cast_slice(&shift));
+        let partial_handle = client.empty((cubes * PARTIAL_FLOATS) as usize * core::mem::size_of::<f32>());
+        unsafe {
+            kernel_reduce_statistics::launch_unchecked::<f32, R>(
+                &client,
+                CubeCount::Static(cubes, 1, 1),
+                CubeDim::new(REDUCE_UNITS, 1, 1),
+                ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+                ArrayArg::from_raw_parts::<f32>(&shift_handle, 4, 1),
+                ScalarArg { elem: pixel_count as u32 },
+                ScalarArg { elem: channels as u32 },
+                ArrayArg::from_raw_parts::<f32>(&partial_handle, (cubes * PARTIAL_FLOATS) as usize, 1),
+            )
+        };
+        f32::from_bytes(&client.read_one(partial_handle.binding())).to_vec()
+    };
+
+    let n = pixel_count as f64;
+    let first = reduce([0.0; 4]);
+    let mut mean = [0.0f32; 4];
+    for c in 0..channels {
+        let partials = first.chunks_exact(PARTIAL_FLOATS as usize);
+        stats.min[c] = partials.clone().map(|p| p[c]).fold(f32::INFINITY, f32::min);
+        stats.max[c] = partials.clone().map(|p| p[4 + c]).fold(f32::NEG_INFINITY, f32::max);
+        mean[c] = (partials.map(|p| p[8 + c] as f64).sum::<f64>() / n) as f32;
+    }
+
+    let second = reduce(mean);
+    for c in 0..channels {
+        let partials = second.chunks_exact(PARTIAL_FLOATS as usize);
+        let sum = partials.clone().map(|p| p[8 + c] as f64).sum::<f64>();
+        let sum_sq = partials.map(|p| p[12 + c] as f64).sum::<f64>();
+        // 평균을 뺀 합은 0 에 가깝지만 f32 누적 오차를 보정한다.
+        let variance = (sum_sq / n - (sum / n).powi(2)).max(0.0);
+        stats.mean[c] = (mean[c] as f64 + sum / n) as f32;
+        stats.std_dev[c] = variance.sqrt() as f32;
+    }
+    stats
+}
+
+/// 채널별 `bins`칸 히스토그램을 계산한다. 결과는 채널 순서로 이어 붙인 `channel_count * bins` 개의 개수이다.
+///
+/// [range_min, range_max] 밖의 값은 양 끝 칸에 센다. 높이맵 자동 레벨링에는 `compute_statistics`의
+/// min/max 를 범위로 쓰면 된다.
+pub fn compute_histogram<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    bins: u32,
+    range_min: f32,
+    range_max: f32,
+) -> Result<Vec<u32>, String> {
+    if bins == 0 {
+        return Err("Histogram needs at least one bin".to_string());
+    }
+    if range_max <= range_min {
+        return Err(format!("Invalid histogram range [{}, {}]", range_min, range_max));
+    }
+    let size = image.get_size();
+    let pixel_count = size.width * size.height;
+    let channels = image.get_channel_count();
+    let bin_count = bins as usize * channels;
+    if pixel_count == 0 {
+        return Ok(vec![0; bin_count]);
+    }
+
+    let client = R::client(run_device);
+    let input = image.get_1d_data_as_f32_channels();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let histogram_handle = client.create(bytemuck::cast_slice(&vec![0u32; bin_count]));
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_histogram::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, channels as u8),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: channels as u32 },
+            ScalarArg { elem: bins },
+            ScalarArg { elem: range_min },
+            ScalarArg { elem: range_max },
+            ArrayArg::from_raw_parts::<u32>(&histogram_handle, bin_count, 1),
+        )
+    };
+
+    let bytes = client.read_one(histogram_handle.binding());
+    Ok(u32::from_bytes(&bytes).to_vec())
+}
@@ -0,0 +1,319 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_color::SKIDColor,
+        skid_image::{SKIDImage, SKIDSizeVector2},
+        skid_pixel_format::SKIDPixelFormat,
+    },
+    utils::gpu_opt::image_cube_config,
+};
+
+/// 리사이즈/회전 결과를 눈으로 확인하기 위한 테스트 패턴 종류.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDTestPattern {
+    /// `cell_size` 픽셀 칸을 `color_a`/`color_b`로 번갈아 칠한 체커보드
+    #[default]
+    Checkerboard = 0,
+    /// `angle` 방향으로 `color_a` → `color_b` 선형 그라디언트
+    LinearGradient = 1,
+    /// 중심(`color_a`)에서 모서리(`color_b`)로 가는 원형 그라디언트
+    RadialGradient = 2,
+    /// SMPTE 컬러 바 (75% 바, 역순 블루 바, -I/백색/+Q/PLUGE)
+    ColorBars = 3,
+    /// R = u, G = v 배경에 `cell_size` 간격 격자선과 칸 번호(열, 행)를 `color_a`로 그린 UV 디버그 격자
+    UvGrid = 4,
+    /// `color_a` 단색
+    Solid = 5,
+    /// 위: 11단계 회색(0~1), 아래: 원색/보색 견본 (R, G, B, C, M, Y)
+    Swatches = 6,
+}
+
+impl SKIDTestPattern {
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDTestPattern::Checkerboard),
+            1 => Some(SKIDTestPattern::LinearGradient),
+            2 => Some(SKIDTestPattern::RadialGradient),
+            3 => Some(SKIDTestPattern::ColorBars),
+            4 => Some(SKIDTestPattern::UvGrid),
+            5 => Some(SKIDTestPattern::Solid),
+            6 => Some(SKIDTestPattern::Swatches),
+            _ => None,
+        }
+    }
+    /// CLI 등에서 쓰는 이름 ("checkerboard", "linear-gradient", "uv-grid" ...). 대소문자와 `-`/`_`를 구분하지 않는다.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "checkerboard" | "checker" => Some(SKIDTestPattern::Checkerboard),
+            "linear-gradient" | "linear" => Some(SKIDTestPattern::LinearGradient),
+            "radial-gradient" | "radial" => Some(SKIDTestPattern::RadialGradient),
+            "color-bars" | "smpte" => Some(SKIDTestPattern::ColorBars),
+            "uv-grid" | "uv" => Some(SKIDTestPattern::UvGrid),
+            "solid" => Some(SKIDTestPattern::Solid),
+            "swatches" => Some(SKIDTestPattern::Swatches),
+            _ => None,
+        }
+    }
+}
+
+/// 테스트 패턴 옵션. 패턴마다 쓰는 필드만 의미가 있다.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SKIDTestPatternOptions {
+    pub pattern: SKIDTestPattern,
+    /// 체커보드 칸 / UV 격자 간격 (픽셀)
+    pub cell_size: u32,
+    /// 선형 그라디언트 방향 (라디안, 0 = 왼쪽 → 오른쪽)
+    pub angle: f32,
+    pub color_a: SKIDColor,
+    pub color_b: SKIDColor,
+}
+
+impl Default for SKIDTestPatternOptions {
+    fn default() -> Self {
+        SKIDTestPatternOptions {
+            pattern: SKIDTestPattern::Checkerboard,
+            cell_size: 64,
+            angle: 0.0,
+            color_a: SKIDColor::new(1.0, 1.0, 1.0, 1.0),
+            color_b: SKIDColor::new(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+}
+
+/// 3x5 숫자 글꼴. 위 행부터 3비트씩, 각 행은 왼쪽 픽셀이 상위 비트이다.
+const DIGIT_GLYPHS: [u32; 10] = [
+    0b111_101_101_101_111,
+    0b010_110_010_010_111,
+    0b111_001_111_100_111,
+    0b111_001_111_001_111,
+    0b101_101_111_001_001,
+    0b111_100_111_001_111,
+    0b111_100_111_101_111,
+    0b111_001_001_001_001,
+    0b111_101_111_101_111,
+    0b111_101_111_001_111,
+];
+
+#[cube]
+fn write_rgba<F: Float>(output: &mut Array<F>, idx: u32, r: F, g: F, b: F, a: F) {
+    output[idx * 4] = r;
+    output[idx * 4 + 1] = g;
+    output[idx * 4 + 2] = b;
+    output[idx * 4 + 3] = a;
+}
+
+/// 칸 안의 (lx, ly)가 "열 행" 라벨(두 자리씩)의 글자 픽셀이면 true.
+#[cube]
+fn is_label_pixel(glyphs: &Array<u32>, lx: u32, ly: u32, column: u32, row: u32, scale: u32) -> bool {
+    let margin = scale * 2;
+    let mut hit = false;
+    if lx >= margin && ly >= margin {
+        let gx_total = (lx - margin) / scale;
+        let gy = (ly - margin) / scale;
+        let glyph_index = gx_total / 4;
+        let gx = gx_total % 4;
+        // 글자 배치: [열 십의 자리][열 일의 자리][공백][행 십의 자리][행 일의 자리]
+        if gy < 5 && gx < 3 && glyph_index < 5 && glyph_index != 2 {
+            let mut digit = row % 10;
+            if glyph_index == 0 {
+                digit = (column / 10) % 10;
+            } else if glyph_index == 1 {
+                digit = column % 10;
+            } else if glyph_index == 3 {
+                digit = (row / 10) % 10;
+            }
+            let bit = 14 - (gy * 3 + gx);
+            hit = ((glyphs[digit] >> bit) & 1) == 1;
+        }
+    }
+    hit
+}
+
+#[cube(launch_unchecked)]
+fn kernel_test_pattern<F: Float>(
+    glyphs: &Array<u32>,
+    colors: &Array<F>,
+    width: u32,
+    height: u32,
+    pattern: u32,
+    cell_size: u32,
+    angle: F,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let u = (F::cast_from(px) + F::new(0.5)) / F::cast_from(width);
+        let v = (F::cast_from(py) + F::new(0.5)) / F::cast_from(height);
+        // 0 = color_a, 1 = color_b 로 섞는 패턴은 t 만 정하고 마지막에 보간한다.
+        let mut t = F::new(0.);
+        let mut direct: bool = false;
+        let mut r = F::new(0.);
+        let mut g = F::new(0.);
+        let mut b = F::new(0.);
+
+        if pattern == 0 {
+            if ((px / cell_size) + (py / cell_size)) % 2 == 1 {
+                t = F::new(1.);
+            }
+        } else if pattern == 1 {
+            let dx = F::cos(angle);
+            let dy = F::sin(angle);
+            let extent = (F::abs(dx) + F::abs(dy)) * F::new(0.5);
+            t = ((u - F::new(0.5)) * dx + (v - F::new(0.5)) * dy) / extent * F::new(0.5) + F::new(0.5);
+        } else if pattern == 2 {
+            let cx = F::cast_from(px) + F::new(0.5) - F::cast_from(width) * F::new(0.5);
+            let cy = F::cast_from(py) + F::new(0.5) - F::cast_from(height) * F::new(0.5);
+            let fw = F::cast_from(width);
+            let fh = F::cast_from(height);
+            let half_diagonal = F::sqrt(fw * fw + fh * fh) * F::new(0.5);
+            t = F::sqrt(cx * cx + cy * cy) / half_diagonal;
+        } else if pattern == 3 {
+            direct = true;
+            let column = px * 7 / width;
+            let bar = select(column < 6, column, 6u32);
+            if py * 3 < height * 2 {
+                // 75% 바: 회색, 노랑, 시안, 초록, 마젠타, 빨강, 파랑
+                let level = F::new(0.75);
+                if bar != 4 && bar != 5 && bar != 6 {
+                    g = level;
+                }
+                if bar == 0 || bar == 1 || bar == 4 || bar == 5 {
+                    r = level;
+                }
+                if bar == 0 || bar == 2 || bar == 4 || bar == 6 {
+                    b = level;
+                }
+            } else if py * 4 < height * 3 {
+                // 역순 블루 바: 파랑, 검정, 마젠타, 검정, 시안, 검정, 회색
+                let level = F::new(0.75);
+                if bar == 0 || bar == 2 || bar == 4 || bar == 6 {
+                    b = level;
+                }
+                if bar == 2 || bar == 6 {
+                    r = level;
+                }
+                if bar == 4 || bar == 6 {
+                    g = level;
+                }
+            } else {
+                // -I, 100% 백색, +Q 가 바 5/4 폭씩, 이어서 검정, PLUGE(0%, 2%, 4%), 검정
+                let slot = px * 28 / width;
+                if slot < 5 {
+                    g = F::new(0.129);
+                    b = F::new(0.298);
+                } else if slot < 10 {
+                    r = F::new(1.);
+                    g = F::new(1.);
+                    b = F::new(1.);
+                } else if slot < 15 {
+                    r = F::new(0.196);
+                    b = F::new(0.416);
+                } else if slot / 4 == 5 {
+                    // 슬롯 20~23: PLUGE
+                    let step = F::cast_from((px * 84 / width - 60) / 4) * F::new(0.02);
+                    r = step;
+                    g = step;
+                    b = step;
+                }
+            }
+        } else if pattern == 4 {
+            direct = true;
+            r = u;
+            g = v;
+            b = F::new(0.25);
+            let lx = px % cell_size;
+            let ly = py % cell_size;
+            let line = select(cell_size < 64, 1u32, cell_size / 64);
+            let scale = select(cell_size < 40, 1u32, cell_size / 40);
+            if lx < line || ly < line || is_label_pixel(glyphs, lx, ly, px / cell_size, py / cell_size, scale) {
+                t = F::new(-1.);
+            }
+        } else if pattern == 6 {
+            direct = true;
+            if py * 2 < height {
+                let column = px * 11 / width;
+                let step = F::cast_from(select(column < 10, column, 10u32)) / F::new(10.);
+                r = step;
+                g = step;
+                b = step;
+            } else {
+                let column = px * 6 / width;
+                let swatch = select(column < 5, column, 5u32);
+                if swatch == 0 || swatch == 4 || swatch == 5 {
+                    r = F::new(1.);
+                }
+                if swatch == 1 || swatch == 3 || swatch == 5 {
+                    g = F::new(1.);
+                }
+                if swatch == 2 || swatch == 3 || swatch == 4 {
+                    b = F::new(1.);
+                }
+            }
+        }
+
+        if direct {
+            if t < F::new(0.) {
+                // 격자선/라벨은 color_a
+                write_rgba::<F>(output, idx, colors[0], colors[1], colors[2], colors[3]);
+            } else {
+                write_rgba::<F>(output, idx, r, g, b, F::new(1.));
+            }
+        } else {
+            let w = F::min(F::max(t, F::new(0.)), F::new(1.));
+            write_rgba::<F>(
+                output,
+                idx,
+                colors[0] + (colors[4] - colors[0]) * w,
+                colors[1] + (colors[5] - colors[1]) * w,
+                colors[2] + (colors[6] - colors[2]) * w,
+                colors[3] + (colors[7] - colors[3]) * w,
+            );
+        }
+    }
+}
+
+/// 테스트 패턴 이미지를 GPU 에서 생성한다. 결과는 `format`으로 저장되며 sRGB 로 태그된다.
+pub fn generate_test_pattern<R: Runtime>(
+    run_device: &R::Device,
+    size: SKIDSizeVector2,
+    format: SKIDPixelFormat,
+    options: &SKIDTestPatternOptions,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let mut colors = options.color_a.to_f32_array().to_vec();
+    colors.extend_from_slice(&options.color_b.to_f32_array());
+    let glyph_handle = client.create(bytemuck::cast_slice(&DIGIT_GLYPHS));
+    let color_handle = client.create(bytemuck::cast_slice(&colors));
+    let output_handle = client.empty(pixel_count * 4 * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_test_pattern::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<u32>(&glyph_handle, DIGIT_GLYPHS.len(), 1),
+            ArrayArg::from_raw_parts::<f32>(&color_handle, colors.len(), 1),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: options.pattern.to_u32() },
+            ScalarArg { elem: Ord::max(options.cell_size, 1) },
+            ScalarArg { elem: options.angle },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, pixel_count * 4, 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, format, f32::from_bytes(&bytes), 4)
+}
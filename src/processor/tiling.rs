@@ -0,0 +1,191 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+use cubecl::Runtime;
+
+use crate::{
+    model::skid_image::{SKIDImage, SKIDRect, SKIDSizeVector2},
+    processor::{
+        convolution::SKIDEdgeMode,
+        make_normal_map::make_normal_map_base,
+        region::extract_with_halo,
+        resize_image::resize_tile,
+        sampling::SKIDSampling,
+    },
+};
+
+/// 타일 분할 처리 옵션.
+///
+/// GPU 에는 한 번에 타일 하나(와 테두리)만 올라가므로, 16K 높이맵처럼 전체 버퍼가 GPU 메모리에 들어가지 않는
+/// 이미지도 처리할 수 있다. 결과 이미지는 CPU 메모리에 이어 붙인다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SKIDTilingOptions {
+    /// 출력 타일 한 변의 최대 픽셀 수. 테두리(halo)는 여기에 더해진다.
+    pub tile_size: usize,
+    /// 동시에 처리할 타일 수. 1 이면 순서대로 처리하고, 늘리면 타일 잘라내기/읽어 오기와 GPU 작업이 겹친다.
+    /// GPU 메모리는 대략 이 수만큼의 타일 버퍼를 쓴다.
+    pub parallel_tiles: usize,
+}
+
+impl SKIDTilingOptions {
+    pub fn new(tile_size: usize, parallel_tiles: usize) -> Self {
+        SKIDTilingOptions { tile_size, parallel_tiles }
+    }
+}
+
+impl Default for SKIDTilingOptions {
+    fn default() -> Self {
+        SKIDTilingOptions { tile_size: 2048, parallel_tiles: 1 }
+    }
+}
+
+/// `size`를 한 변이 최대 `tile_size`인 타일로 나눈다. 행 우선(왼쪽 위 → 오른쪽 아래) 순서이다.
+pub fn tile_grid(size: SKIDSizeVector2, tile_size: usize) -> Vec<SKIDRect> {
+    let tile_size = tile_size.max(1);
+    let mut tiles = Vec::new();
+    for y in (0..size.height).step_by(tile_size) {
+        for x in (0..size.width).step_by(tile_size) {
+            tiles.push(SKIDRect::new(x, y, tile_size.min(size.width - x), tile_size.min(size.height - y)));
+        }
+    }
+    tiles
+}
+
+/// 출력 `out_size`를 타일로 나눠 `produce`로 각 타일을 만들고 하나의 이미지로 이어 붙인다.
+///
+/// `produce`는 출력 타일 영역을 받아 그 크기의 이미지를 돌려줘야 한다. 결과의 픽셀 형식과 태그는 처음 끝난 타일을 따른다.
+/// 한 타일이라도 실패하면 남은 타일을 건너뛰고 그 오류를 돌려준다.
+pub fn run_tiles(
+    out_size: SKIDSizeVector2,
+    options: &SKIDTilingOptions,
+    produce: impl Fn(SKIDRect) -> Result<SKIDImage, String> + Sync,
+) -> Result<SKIDImage, String> {
+    if out_size.is_empty() {
+        return Err("Output size must be non-zero".to_string());
+    }
+    let tiles = tile_grid(out_size, options.tile_size);
+    let output: Mutex<Option<SKIDImage>> = Mutex::new(None);
+    let error: Mutex<Option<String>> = Mutex::new(None);
+    let next = AtomicUsize::new(0);
+
+    let worker = || loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        if index >= tiles.len() || error.lock().unwrap().is_some() {
+            break;
+        }
+        let rect = tiles[index];
+        let result = produce(rect).and_then(|tile| {
+            if tile.get_size() != rect.get_size() {
+                return Err(format!("Tile {:?} produced an image of size {:?}", rect, tile.get_size()));
+            }
+            let mut output = output.lock().unwrap();
+            let target = output.get_or_insert_with(|| {
+                SKIDImage::new_with_format(out_size.width, out_size.height, tile.get_pixel_format())
+                    .with_metadata_from(&tile)
+            });
+            target.view_mut(rect)?.copy_from(&tile.as_view())
+        });
+        if let Err(e) = result {
+            error.lock().unwrap().get_or_insert(e);
+            break;
+        }
+    };
+
+    let workers = options.parallel_tiles.clamp(1, tiles.len());
+    if workers == 1 {
+        worker();
+    } else {
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(worker);
+            }
+        });
+    }
+
+    if let Some(e) = error.into_inner().unwrap() {
+        return Err(e);
+    }
+    output.into_inner().unwrap().ok_or_else(|| "No tiles were processed".to_string())
+}
+
+/// 입력과 출력 크기가 같은 이웃 연산(노멀맵, 블러 등)을 타일로 나눠 실행한다.
+///
+/// 각 타일은 네 변에 `halo`만큼 테두리를 붙여 잘라낸 뒤 `processor`에 넘기고, 결과에서 테두리를 뺀 안쪽만 이어 붙인다.
+/// `halo`는 프로세서가 읽는 이웃 반경이고 `edge_mode`는 프로세서가 전체 이미지 가장자리에서 쓰는 방식이어야
+/// 결과가 한 번에 처리한 것과 같아진다. (`region::process_region`과 같은 규약)
+pub fn process_tiled(
+    image: &SKIDImage,
+    halo: usize,
+    edge_mode: SKIDEdgeMode,
+    options: &SKIDTilingOptions,
+    processor: impl Fn(&SKIDImage) -> SKIDImage + Sync,
+) -> Result<SKIDImage, String> {
+    run_tiles(image.get_size(), options, |rect| {
+        let input = extract_with_halo(image, rect, halo, edge_mode)?;
+        let output = processor(&input);
+        if output.get_size() != input.get_size() {
+            return Err(format!(
+                "Tile processor changed the size from {:?} to {:?}",
+                input.get_size(),
+                output.get_size()
+            ));
+        }
+        Ok(output.view(SKIDRect::new(halo, halo, rect.width, rect.height))?.to_image())
+    })
+}
+
+/// 타일 단위로 노멀맵을 만든다. 결과는 `make_normal_map_base`로 한 번에 만든 것과 같다.
+///
+/// 커널이 상하좌우 1픽셀을 읽고 가장자리에서 반대편으로 감기므로 테두리는 1픽셀, `Wrap`이다.
+pub fn make_normal_map_tiled<R: Runtime>(
+    runtime: R::Device,
+    height_map: &SKIDImage,
+    x_factor: Option<f32>,
+    y_factor: Option<f32>,
+    options: &SKIDTilingOptions,
+) -> Result<SKIDImage, String> {
+    process_tiled(height_map, 1, SKIDEdgeMode::Wrap, options, |tile| {
+        make_normal_map_base::<R>(runtime.clone(), tile, x_factor, y_factor)
+    })
+}
+
+/// 타일 단위로 리샘플링한다. 결과는 `resize_image_with_sampling`으로 한 번에 만든 것과 같다.
+///
+/// 출력 타일마다 그 타일이 덮는 원본 영역에 보간 필터 반경(`SKIDSampling::support_radius`)만큼 테두리를 붙여
+/// 잘라내고, 전체 이미지 기준의 배율과 원점으로 샘플링한다. 원본 가장자리는 전체 리사이즈와 같이 `Clamp`이다.
+pub fn resize_image_tiled<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    new_size: SKIDSizeVector2,
+    sampling: SKIDSampling,
+    options: &SKIDTilingOptions,
+) -> Result<SKIDImage, String> {
+    let size = image.get_size();
+    if size.is_empty() {
+        return Err("Source image is empty".to_string());
+    }
+    let scale_x = size.width as f32 / new_size.width.max(1) as f32;
+    let scale_y = size.height as f32 / new_size.height.max(1) as f32;
+    let halo = sampling.support_radius();
+
+    // 출력 구간 [start, start + len)이 덮는 원본 구간. 비어 있지 않도록 최소 한 픽셀을 잡는다.
+    let source_span = |start: usize, len: usize, scale: f32, limit: usize| {
+        let begin = ((start as f32 * scale).floor() as usize).min(limit - 1);
+        let end = (((start + len) as f32 * scale).ceil() as usize).clamp(begin + 1, limit);
+        (begin, end - begin)
+    };
+
+    run_tiles(new_size, options, |rect| {
+        let (sx, sw) = source_span(rect.x, rect.width, scale_x, size.width);
+        let (sy, sh) = source_span(rect.y, rect.height, scale_y, size.height);
+        let source = extract_with_halo(image, SKIDRect::new(sx, sy, sw, sh), halo, SKIDEdgeMode::Clamp)?;
+        // 잘라낸 원본의 (0, 0)은 전체 원본의 (sx - halo, sy - halo)이다.
+        let origin = (
+            rect.x as f32 * scale_x - (sx as f32 - halo as f32),
+            rect.y as f32 * scale_y - (sy as f32 - halo as f32),
+        );
+        Ok(resize_tile::<R>(run_device, &source, rect.get_size(), (scale_x, scale_y), origin, sampling))
+    })
+}
@@ -0,0 +1,253 @@
+use cubecl::{cube, prelude::*, CubeElement, Runtime};
+
+use crate::{
+    model::{
+        skid_alpha_mode::SKIDAlphaMode,
+        skid_image::SKIDImage,
+    },
+    processor::{alpha, color_space},
+    utils::{gpu_opt::image_cube_config, graphic_fn::ColorTriple},
+};
+
+/// HDR → 디스플레이 범위 톤 매핑 연산자.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDToneMapOperator {
+    /// 채널별 x / (1 + x)
+    Reinhard = 0,
+    /// 흰색 점(`white_point`)이 정확히 1.0 이 되도록 늘린 Reinhard
+    ReinhardExtended = 1,
+    /// Stephen Hill 의 ACES RRT + sRGB ODT 근사
+    #[default]
+    AcesFitted = 2,
+    /// Troy Sobotka 의 AgX (기본 대비, 다항식 시그모이드 근사)
+    AgX = 3,
+    /// John Hable 의 Uncharted 2 필르믹 커브
+    Uncharted2 = 4,
+}
+
+impl SKIDToneMapOperator {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDToneMapOperator::Reinhard),
+            1 => Some(SKIDToneMapOperator::ReinhardExtended),
+            2 => Some(SKIDToneMapOperator::AcesFitted),
+            3 => Some(SKIDToneMapOperator::AgX),
+            4 => Some(SKIDToneMapOperator::Uncharted2),
+            _ => None,
+        }
+    }
+    /// `white_point`를 지정하지 않았을 때 쓰는 흰색 점 (선형 값).
+    fn default_white_point(self) -> f32 {
+        match self {
+            SKIDToneMapOperator::Uncharted2 => 11.2,
+            _ => 4.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SKIDToneMapOptions {
+    pub operator: SKIDToneMapOperator,
+    /// 노출 보정 (EV 단위, 값에 2^exposure 를 곱한다)
+    pub exposure: f32,
+    /// `ReinhardExtended`와 `Uncharted2`가 1.0 으로 보낼 선형 값. None 이면 연산자 기본값 (4.0 / 11.2)
+    pub white_point: Option<f32>,
+}
+
+impl Default for SKIDToneMapOptions {
+    fn default() -> Self {
+        SKIDToneMapOptions { operator: SKIDToneMapOperator::default(), exposure: 0.0, white_point: None }
+    }
+}
+
+#[cube]
+fn hable_partial<F: Float>(x: F) -> F {
+    let shoulder = F::new(0.15);
+    let linear = F::new(0.50);
+    let angle = F::new(0.10);
+    let toe = F::new(0.20);
+    let toe_num = F::new(0.02);
+    let toe_den = F::new(0.30);
+    (x * (shoulder * x + angle * linear) + toe * toe_num) / (x * (shoulder * x + linear) + toe * toe_den)
+        - toe_num / toe_den
+}
+
+#[cube]
+fn aces_rrt_odt_fit<F: Float>(v: F) -> F {
+    let a = v * (v + F::new(0.0245786)) - F::new(0.000090537);
+    let b = v * (F::new(0.983729) * v + F::new(0.432951)) + F::new(0.238081);
+    a / b
+}
+
+#[cube]
+fn aces_fitted<F: Float>(r: F, g: F, b: F) -> ColorTriple<F> {
+    // sRGB → ACES 근사 입력 행렬 (RRT_SAT 포함)
+    let ar = F::new(0.59719) * r + F::new(0.35458) * g + F::new(0.04823) * b;
+    let ag = F::new(0.07600) * r + F::new(0.90834) * g + F::new(0.01566) * b;
+    let ab = F::new(0.02840) * r + F::new(0.13383) * g + F::new(0.83777) * b;
+    let fr = aces_rrt_odt_fit::<F>(ar);
+    let fg = aces_rrt_odt_fit::<F>(ag);
+    let fb = aces_rrt_odt_fit::<F>(ab);
+    // ODT_SAT → sRGB 출력 행렬
+    ColorTriple::<F> {
+        x: F::new(1.60475) * fr - F::new(0.53108) * fg - F::new(0.07367) * fb,
+        y: F::new(-0.10208) * fr + F::new(1.10813) * fg - F::new(0.00605) * fb,
+        z: F::new(-0.00327) * fr - F::new(0.07276) * fg + F::new(1.07602) * fb,
+    }
+}
+
+#[cube]
+fn agx_contrast<F: Float>(v: F) -> F {
+    let min_ev = F::new(-12.47393);
+    let max_ev = F::new(4.026069);
+    let log2 = F::log(F::max(v, F::new(1.0e-10))) * F::new(core::f32::consts::LOG2_E);
+    let x = (F::min(F::max(log2, min_ev), max_ev) - min_ev) / (max_ev - min_ev);
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    F::new(15.5) * x4 * x2 - F::new(40.14) * x4 * x + F::new(31.96) * x4 - F::new(6.868) * x2 * x
+        + F::new(0.4298) * x2
+        + F::new(0.1191) * x
+        - F::new(0.00232)
+}
+
+#[cube]
+fn agx<F: Float>(r: F, g: F, b: F) -> ColorTriple<F> {
+    // 입력 인셋 행렬로 채도를 눌러 밝은 원색이 흰색으로 자연스럽게 바래도록 한다.
+    let ir = F::new(0.84247906) * r + F::new(0.0784336) * g + F::new(0.079223745) * b;
+    let ig = F::new(0.042328242) * r + F::new(0.87846864) * g + F::new(0.07916613) * b;
+    let ib = F::new(0.042375655) * r + F::new(0.0784336) * g + F::new(0.879143) * b;
+    let cr = agx_contrast::<F>(ir);
+    let cg = agx_contrast::<F>(ig);
+    let cb = agx_contrast::<F>(ib);
+    let or = F::new(1.196879) * cr - F::new(0.09802088) * cg - F::new(0.09902974) * cb;
+    let og = F::new(-0.052896852) * cr + F::new(1.1519031) * cg - F::new(0.098961177) * cb;
+    let ob = F::new(-0.052971636) * cr - F::new(0.09804345) * cg + F::new(1.1510737) * cb;
+    // AgX 출력은 감마 2.2 로 인코딩되어 있으므로 선형으로 되돌린다.
+    ColorTriple::<F> {
+        x: F::powf(F::max(or, F::new(0.)), F::new(2.2)),
+        y: F::powf(F::max(og, F::new(0.)), F::new(2.2)),
+        z: F::powf(F::max(ob, F::new(0.)), F::new(2.2)),
+    }
+}
+
+#[cube(launch_unchecked)]
+fn kernel_tone_map<F: Float>(
+    input: &Array<F>,
+    width: u32,
+    height: u32,
+    operator: u32,
+    exposure_scale: F,
+    white_point: F,
+    output: &mut Array<F>,
+) {
+    let px = ABSOLUTE_POS_X;
+    let py = ABSOLUTE_POS_Y;
+    if px < width && py < height {
+        let idx = py * width + px;
+        let r = F::max(input[idx][0] * exposure_scale, F::new(0.));
+        let g = F::max(input[idx][1] * exposure_scale, F::new(0.));
+        let b = F::max(input[idx][2] * exposure_scale, F::new(0.));
+        let mut x = r;
+        let mut y = g;
+        let mut z = b;
+
+        if operator == 0 {
+            x = r / (F::new(1.) + r);
+            y = g / (F::new(1.) + g);
+            z = b / (F::new(1.) + b);
+        } else if operator == 1 {
+            let inv_white_sq = F::new(1.) / (white_point * white_point);
+            x = r * (F::new(1.) + r * inv_white_sq) / (F::new(1.) + r);
+            y = g * (F::new(1.) + g * inv_white_sq) / (F::new(1.) + g);
+            z = b * (F::new(1.) + b * inv_white_sq) / (F::new(1.) + b);
+        } else if operator == 2 {
+            let aces = aces_fitted::<F>(r, g, b);
+            x = aces.x;
+            y = aces.y;
+            z = aces.z;
+        } else if operator == 3 {
+            let mapped = agx::<F>(r, g, b);
+            x = mapped.x;
+            y = mapped.y;
+            z = mapped.z;
+        } else if operator == 4 {
+            // Hable 의 원래 구현처럼 노출을 2배로 올린 뒤 흰색 점으로 나눈다.
+            let white_scale = F::new(1.) / hable_partial::<F>(white_point);
+            x = hable_partial::<F>(r * F::new(2.)) * white_scale;
+            y = hable_partial::<F>(g * F::new(2.)) * white_scale;
+            z = hable_partial::<F>(b * F::new(2.)) * white_scale;
+        }
+
+        output[idx * 4] = F::min(F::max(x, F::new(0.)), F::new(1.));
+        output[idx * 4 + 1] = F::min(F::max(y, F::new(0.)), F::new(1.));
+        output[idx * 4 + 2] = F::min(F::max(z, F::new(0.)), F::new(1.));
+        output[idx * 4 + 3] = F::min(F::max(input[idx][3], F::new(0.)), F::new(1.));
+    }
+}
+
+/// HDR(1.0 을 넘는 선형 값) 이미지를 0~1 범위의 sRGB 이미지로 톤 매핑한다.
+///
+/// 입력은 선형 RGB 로 바꾼 뒤 straight 알파 상태에서 매핑하고, 결과는 sRGB 로 인코딩해 원래 알파 모드로 되돌린다.
+/// 픽셀 형식은 유지되며, 결과는 그대로 `export_to_png`에 넘길 수 있다.
+pub fn tone_map<R: Runtime>(run_device: &R::Device, image: &SKIDImage, options: &SKIDToneMapOptions) -> SKIDImage {
+    let (linear, _) = color_space::prepare_linear_input::<R>(run_device, image, "tone_map");
+    let straight = alpha::convert_alpha_mode::<R>(run_device, &linear, SKIDAlphaMode::Straight);
+    let white_point = options.white_point.unwrap_or(options.operator.default_white_point()).max(1.0e-3);
+    let mapped = launch::<R>(run_device, &straight, options.operator, options.exposure.exp2(), white_point);
+    let encoded = color_space::to_srgb::<R>(run_device, &mapped);
+    alpha::convert_alpha_mode::<R>(run_device, &encoded, image.get_alpha_mode())
+}
+
+/// `tone_map`을 거친 뒤 PNG 로 저장한다. `options`가 None 이면 `export_to_png`와 같다.
+#[cfg(feature = "use_image")]
+pub fn export_to_png_tone_mapped<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    file_path: &str,
+    thread_count: Option<usize>,
+    options: Option<&SKIDToneMapOptions>,
+) -> Result<(), String> {
+    use crate::utils::file_io;
+
+    match options {
+        Some(options) => file_io::export_to_png(&tone_map::<R>(run_device, image, options), file_path, thread_count),
+        None => file_io::export_to_png(image, file_path, thread_count),
+    }
+}
+
+fn launch<R: Runtime>(
+    run_device: &R::Device,
+    image: &SKIDImage,
+    operator: SKIDToneMapOperator,
+    exposure_scale: f32,
+    white_point: f32,
+) -> SKIDImage {
+    let client = R::client(run_device);
+    let size = image.get_size();
+    let (w_u32, h_u32) = (size.width as u32, size.height as u32);
+    let pixel_count = size.width * size.height;
+
+    let input = image.get_1d_data_as_f32();
+    let input_handle = client.create(bytemuck::cast_slice(&input));
+    let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
+    let (cube_count, cube_dim) = image_cube_config(w_u32, h_u32);
+
+    unsafe {
+        kernel_tone_map::launch_unchecked::<f32, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, 4),
+            ScalarArg { elem: w_u32 },
+            ScalarArg { elem: h_u32 },
+            ScalarArg { elem: operator as u32 },
+            ScalarArg { elem: exposure_scale },
+            ScalarArg { elem: white_point },
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one(output_handle.binding());
+    SKIDImage::from_f32_data(size, image.get_pixel_format(), f32::from_bytes(&bytes), 4).with_metadata_from(image)
+}
@@ -48,4 +48,36 @@ pub fn test_file_read() {
     assert_eq!(image.get_size().width, 2560, "Image width does not match expected value");
     assert_eq!(image.get_size().height, 1440, "Image height does not match expected value");
     println!("Image loaded successfully with size: {}x{}", image.get_size().width, image.get_size().height);
-}
\ No newline at end of file
+}
+#[test]
+pub fn test_tone_mapped_export() {
+    use crate::model::{skid_color_space::SKIDColorSpace, skid_image::SKIDSizeVector2};
+    use crate::processor::tone_map::{export_to_png_tone_mapped, tone_map, SKIDToneMapOperator, SKIDToneMapOptions};
+
+    // 선형 HDR 회색 단계: 0, 0.5, 2, 8
+    let data = [0.0, 0.5, 2.0, 8.0].iter().map(|&v| SKIDColor::new(v, v, v, 1.0)).collect();
+    let image = SKIDImage::from_1d_data(SKIDSizeVector2::new(4, 1), data).with_color_space(SKIDColorSpace::Linear);
+    let device = Default::default();
+    let options = SKIDToneMapOptions { operator: SKIDToneMapOperator::Reinhard, ..Default::default() };
+
+    let mapped = tone_map::<cubecl::wgpu::WgpuRuntime>(&device, &image, &options);
+    assert_eq!(mapped.get_pixel_format(), image.get_pixel_format());
+    assert_eq!(mapped.get_color_space(), SKIDColorSpace::Srgb);
+
+    let dir = std::env::temp_dir();
+    let mapped_path = dir.join("skid_tone_mapped.png");
+    let plain_path = dir.join("skid_tone_mapped_plain.png");
+    export_to_png_tone_mapped::<cubecl::wgpu::WgpuRuntime>(&device, &image, mapped_path.to_str().unwrap(), None, Some(&options))
+        .expect("Failed to export tone mapped image");
+    export_to_png_tone_mapped::<cubecl::wgpu::WgpuRuntime>(&device, &image, plain_path.to_str().unwrap(), None, None)
+        .expect("Failed to export image");
+
+    // 톤 매핑하면 1.0 을 넘는 단계도 구분되고, 하지 않으면 잘린다.
+    let red = |path: &std::path::Path, x: u32| {
+        utils::file_io::import_from_png(path.to_str().unwrap(), None).unwrap().get_pixel(x, 0).unwrap().r
+    };
+    let levels: Vec<f32> = (0..4).map(|x| red(&mapped_path, x)).collect();
+    assert!(levels.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", levels);
+    assert!(levels[3] < 1.0, "{:?}", levels);
+    assert_eq!((red(&plain_path, 2), red(&plain_path, 3)), (1.0, 1.0));
+}
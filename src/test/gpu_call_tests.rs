@@ -1,8 +1,8 @@
 use std::time::Instant;
 
-use cubecl::wgpu::{Dx12, Vulkan};
+use cubecl::wgpu::Vulkan;
 
-use crate::{model::{skid_color::SKIDColor, skid_image::{SKIDImage, SKIDSizeVector2}}, processor::{example_generator, make_normal_map, resize_image::{self, resize_image}}, utils::{file_io, gpu_opt}};
+use crate::{model::{skid_color::SKIDColor, skid_image::{SKIDImage, SKIDSizeVector2}, skid_pixel_format::SKIDPixelFormat}, processor::{example_generator, make_normal_map, resize_image, sharpen::{self, SKIDDetailMode}, statistics}, utils::{file_io, gpu_opt}};
 
 
 
@@ -50,6 +50,7 @@ fn gpu_normap_tests() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn gpu_upscale_tests() {
     let example_image = file_io::import_from_png("output/test_input.png", Some(4))
         .expect("Failed to load image from file");
@@ -92,7 +93,7 @@ fn gpu_example_generator_tests() {
             height: 2880,
 
         },
-        Some(2),
+        None,
     );
     let duration = start.elapsed();
     println!("Example generator took: {:?}", duration);
@@ -105,4 +106,421 @@ fn gpu_example_generator_tests() {
     ).expect("Failed to export example generator image");
     let duration_file = start_file.elapsed();
     println!("File export took: {:?}", duration_file);
-}
\ No newline at end of file
+}
+#[test]
+fn gpu_sharpen_tests() {
+    let device = Default::default();
+
+    // 평탄한 RG8 이미지의 하이패스는 모든 채널이 0.5 이고 형식은 그대로이다.
+    let flat = SKIDImage::new_with_color(16, 16, SKIDColor::new(0.4, 0.6, 0.0, 1.0)).convert_format(SKIDPixelFormat::RG8);
+    let detail = sharpen::high_pass::<cubecl::wgpu::WgpuRuntime>(&device, &flat, 2.0, 1.0, 0.0, SKIDDetailMode::Luminance);
+    assert_eq!(detail.get_pixel_format(), SKIDPixelFormat::RG8);
+    let pixel = detail.get_pixel(8, 8).unwrap();
+    assert!((pixel.r - 0.5).abs() < 1.0 / 255.0 && (pixel.g - 0.5).abs() < 1.0 / 255.0);
+
+    // 세로 경계: 언샤프 마스크는 경계 양쪽에 언더슈트/오버슈트를 만들고, 임계값을 넘지 못하면 원본을 유지한다.
+    let mut edge = SKIDImage::new_with_color(16, 4, SKIDColor::new(0.2, 0.2, 0.0, 1.0));
+    for y in 0..4 {
+        for x in 8..16 {
+            edge.set_pixel(x, y, SKIDColor::new(0.8, 0.8, 0.0, 1.0)).unwrap();
+        }
+    }
+    let edge = edge.convert_format(SKIDPixelFormat::RG8);
+    let sharpened = sharpen::unsharp_mask::<cubecl::wgpu::WgpuRuntime>(&device, &edge, 1.0, 1.0, 0.0, SKIDDetailMode::PerChannel);
+    let (dark, bright) = (sharpened.get_pixel(7, 1).unwrap(), sharpened.get_pixel(8, 1).unwrap());
+    assert!(dark.r < 0.19 && dark.g < 0.19, "{:?}", dark);
+    assert!(bright.r > 0.81 && bright.g > 0.81, "{:?}", bright);
+    assert_eq!(sharpened.get_pixel(0, 1), edge.get_pixel(0, 1));
+
+    let untouched = sharpen::unsharp_mask::<cubecl::wgpu::WgpuRuntime>(&device, &edge, 1.0, 1.0, 1.0, SKIDDetailMode::PerChannel);
+    assert_eq!(untouched.get_native_data(), edge.get_native_data());
+}
+
+#[test]
+fn gpu_statistics_tests() {
+    let device = Default::default();
+
+    // R 채널은 0.0, 0.25, 0.5, 1.0 이 두 번씩, 나머지 채널은 상수.
+    let values = [0.0, 0.25, 0.5, 1.0, 0.0, 0.25, 0.5, 1.0];
+    let data = values.iter().map(|&r| SKIDColor::new(r, 0.5, 0.0, 1.0)).collect();
+    let image = SKIDImage::from_1d_data(SKIDSizeVector2::new(4, 2), data);
+
+    let stats = statistics::compute_statistics::<cubecl::wgpu::WgpuRuntime>(&device, &image);
+    assert_eq!(stats.channel_count, 4);
+    assert_eq!((stats.min[0], stats.max[0]), (0.0, 1.0));
+    assert!((stats.mean[0] - 0.4375).abs() < 1e-5);
+    let variance = values.iter().map(|v| (v - 0.4375f32).powi(2)).sum::<f32>() / values.len() as f32;
+    assert!((stats.std_dev[0] - variance.sqrt()).abs() < 1e-5);
+    assert!((stats.mean[1] - 0.5).abs() < 1e-6 && stats.std_dev[1].abs() < 1e-6);
+    assert_eq!((stats.min[3], stats.max[3]), (1.0, 1.0));
+
+    // 4칸 [0, 1] 히스토그램: 1.0 은 마지막 칸, 범위 밖 값은 양 끝 칸에 센다.
+    let histogram = statistics::compute_histogram::<cubecl::wgpu::WgpuRuntime>(&device, &image, 4, 0.0, 1.0).unwrap();
+    assert_eq!(histogram.len(), 16);
+    assert_eq!(&histogram[0..4], &[2, 2, 2, 2]);
+    assert_eq!(&histogram[4..8], &[0, 0, 8, 0]);
+    assert_eq!(&histogram[12..16], &[0, 0, 0, 8]);
+    let narrow = statistics::compute_histogram::<cubecl::wgpu::WgpuRuntime>(&device, &image, 2, 0.25, 0.5).unwrap();
+    assert_eq!(&narrow[0..2], &[4, 4]);
+
+    assert!(statistics::compute_histogram::<cubecl::wgpu::WgpuRuntime>(&device, &image, 0, 0.0, 1.0).is_err());
+    assert!(statistics::compute_histogram::<cubecl::wgpu::WgpuRuntime>(&device, &image, 4, 1.0, 1.0).is_err());
+}
+
+#[test]
+fn gpu_filter_input_roundtrip_tests() {
+    use crate::model::{skid_alpha_mode::SKIDAlphaMode, skid_color_space::SKIDColorSpace};
+    use crate::processor::alpha;
+    let device = Default::default();
+
+    // sRGB + straight 알파 RGBA8 입력은 한 번의 패스로 linear + 프리멀티플라이드 RGBA32F 가 된다.
+    let image = SKIDImage::new_with_color(4, 4, SKIDColor::new(0.8, 0.4, 0.2, 0.5)).convert_format(SKIDPixelFormat::RGBA8);
+    let (prepared, state) = alpha::prepare_filter_input::<cubecl::wgpu::WgpuRuntime>(&device, &image, "test");
+    assert_eq!(prepared.get_pixel_format(), SKIDPixelFormat::RGBA32F);
+    assert_eq!(prepared.get_color_space(), SKIDColorSpace::Linear);
+    assert_eq!(prepared.get_alpha_mode(), SKIDAlphaMode::Premultiplied);
+    let source = image.get_pixel(1, 1).unwrap();
+    let pixel = prepared.get_pixel(1, 1).unwrap();
+    let srgb_to_linear = |c: f32| if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+    assert!((pixel.r - srgb_to_linear(source.r) * source.a).abs() < 1e-4, "{:?}", pixel);
+    assert!((pixel.b - srgb_to_linear(source.b) * source.a).abs() < 1e-4, "{:?}", pixel);
+    assert_eq!(pixel.a, source.a);
+
+    // 되돌리면 원래 형식과 값으로 돌아온다.
+    let restored = alpha::restore_filter_output::<cubecl::wgpu::WgpuRuntime>(&device, prepared, &state);
+    assert_eq!(restored.get_pixel_format(), SKIDPixelFormat::RGBA8);
+    assert_eq!(restored.get_color_space(), image.get_color_space());
+    assert_eq!(restored.get_alpha_mode(), SKIDAlphaMode::Straight);
+    assert_eq!(restored.get_native_data(), image.get_native_data());
+}
+
+/// 가장자리 모드를 적용해 1채널 이미지를 직접 2D 상관으로 필터링하는 CPU 참조 구현.
+#[allow(clippy::too_many_arguments)]
+fn reference_convolve_2d(
+    values: &[f32],
+    width: usize,
+    height: usize,
+    kernel: &[f32],
+    kernel_w: usize,
+    kernel_h: usize,
+    edge_mode: crate::processor::convolution::SKIDEdgeMode,
+    constant: f32,
+) -> Vec<f32> {
+    use crate::processor::convolution::SKIDEdgeMode;
+    let resolve = |coord: i32, size: i32| -> Option<i32> {
+        if (0..size).contains(&coord) {
+            return Some(coord);
+        }
+        match edge_mode {
+            SKIDEdgeMode::Clamp => Some(coord.clamp(0, size - 1)),
+            SKIDEdgeMode::Wrap => Some(coord.rem_euclid(size)),
+            SKIDEdgeMode::Mirror => {
+                let m = coord.rem_euclid(size * 2);
+                Some(if m >= size { size * 2 - 1 - m } else { m })
+            }
+            SKIDEdgeMode::Constant => None,
+        }
+    };
+    let mut out = vec![0.0; width * height];
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let mut sum = 0.0;
+            for j in 0..kernel_h as i32 {
+                for i in 0..kernel_w as i32 {
+                    let sx = resolve(x + i - kernel_w as i32 / 2, width as i32);
+                    let sy = resolve(y + j - kernel_h as i32 / 2, height as i32);
+                    let value = match (sx, sy) {
+                        (Some(sx), Some(sy)) => values[sy as usize * width + sx as usize],
+                        _ => constant,
+                    };
+                    sum += kernel[j as usize * kernel_w + i as usize] * value;
+                }
+            }
+            out[y as usize * width + x as usize] = sum;
+        }
+    }
+    out
+}
+
+#[test]
+fn gpu_convolution_separable_matches_direct_tests() {
+    use crate::processor::convolution::{self, SKIDConvolutionOptions, SKIDEdgeMode};
+    let device = Default::default();
+
+    let (width, height) = (7, 5);
+    let values: Vec<f32> = (0..width * height).map(|i| ((i * 37) % 11) as f32 / 10.0).collect();
+    let image = SKIDImage::from_f32_data(SKIDSizeVector2::new(width, height), SKIDPixelFormat::R32F, &values, 1);
+
+    // 비대칭 커널로 가로/세로 방향과 중심 위치까지 확인한다.
+    let horizontal = [0.1, 0.6, 0.3];
+    let vertical = [0.2, 0.3, 0.5, 0.4, 0.1];
+    let kernel_2d: Vec<f32> = vertical.iter().flat_map(|v| horizontal.iter().map(move |h| v * h)).collect();
+
+    for edge_mode in [SKIDEdgeMode::Clamp, SKIDEdgeMode::Wrap, SKIDEdgeMode::Mirror, SKIDEdgeMode::Constant] {
+        let options = SKIDConvolutionOptions {
+            edge_mode,
+            constant_color: SKIDColor::new(0.7, 0.7, 0.7, 0.7),
+            ..Default::default()
+        };
+        let result = convolution::convolve_separable_values::<cubecl::wgpu::WgpuRuntime>(&device, &image, &horizontal, &vertical, &options);
+        let expected = reference_convolve_2d(&values, width, height, &kernel_2d, 3, 5, edge_mode, 0.7);
+        for (i, (a, b)) in result.get_1d_data_as_f32_channels().iter().zip(&expected).enumerate() {
+            assert!((a - b).abs() < 1e-5, "{:?} pixel {}: {} != {}", edge_mode, i, a, b);
+        }
+    }
+}
+
+#[test]
+fn gpu_convolution_edge_mode_tests() {
+    use crate::processor::convolution::{self, SKIDConvolutionOptions, SKIDEdgeMode};
+    let device = Default::default();
+
+    // 한 행 [1, 2, 3, 4] 에서 두 칸 왼쪽/오른쪽을 읽는 커널로 양쪽 가장자리 값을 확인한다.
+    let values = [1.0, 2.0, 3.0, 4.0];
+    let image = SKIDImage::from_f32_data(SKIDSizeVector2::new(4, 1), SKIDPixelFormat::R32F, &values, 1);
+    let read_left = [1.0, 0.0, 0.0, 0.0, 0.0];
+    let read_right = [0.0, 0.0, 0.0, 0.0, 1.0];
+    let run = |edge_mode: SKIDEdgeMode, kernel: &[f32]| {
+        let options = SKIDConvolutionOptions {
+            edge_mode,
+            constant_color: SKIDColor::new(9.0, 9.0, 9.0, 9.0),
+            ..Default::default()
+        };
+        convolution::convolve_separable_values::<cubecl::wgpu::WgpuRuntime>(&device, &image, kernel, &[1.0], &options)
+            .get_1d_data_as_f32_channels()
+    };
+
+    // Clamp: 가장자리 픽셀 반복
+    assert_eq!(run(SKIDEdgeMode::Clamp, &read_left), vec![1.0, 1.0, 1.0, 2.0]);
+    assert_eq!(run(SKIDEdgeMode::Clamp, &read_right), vec![3.0, 4.0, 4.0, 4.0]);
+    // Wrap: 반대편에서 이어짐
+    assert_eq!(run(SKIDEdgeMode::Wrap, &read_left), vec![3.0, 4.0, 1.0, 2.0]);
+    assert_eq!(run(SKIDEdgeMode::Wrap, &read_right), vec![3.0, 4.0, 1.0, 2.0]);
+    // Mirror: 가장자리 픽셀을 포함해 반사 (-1 -> 0, -2 -> 1, 4 -> 3, 5 -> 2)
+    assert_eq!(run(SKIDEdgeMode::Mirror, &read_left), vec![2.0, 1.0, 1.0, 2.0]);
+    assert_eq!(run(SKIDEdgeMode::Mirror, &read_right), vec![3.0, 4.0, 4.0, 3.0]);
+    // Constant: 이미지 밖은 constant_color
+    assert_eq!(run(SKIDEdgeMode::Constant, &read_left), vec![9.0, 9.0, 1.0, 2.0]);
+    assert_eq!(run(SKIDEdgeMode::Constant, &read_right), vec![3.0, 4.0, 9.0, 9.0]);
+}
+
+#[test]
+fn gpu_box_blur_matches_reference_tests() {
+    use crate::processor::blur;
+    use crate::processor::convolution::{SKIDConvolutionOptions, SKIDEdgeMode};
+    let device = Default::default();
+
+    // 긴 줄에서 누적합이 커져도 작은 반경의 결과가 직접 계산과 같아야 한다.
+    let (width, height) = (1024, 6);
+    let values: Vec<f32> = (0..width * height).map(|i| 0.5 + ((i * 7919) % 101) as f32 / 202.0).collect();
+    let image = SKIDImage::from_f32_data(SKIDSizeVector2::new(width, height), SKIDPixelFormat::R32F, &values, 1);
+
+    for radius in [1u32, 4] {
+        let taps = (radius * 2 + 1) as usize;
+        let kernel = vec![1.0 / (taps * taps) as f32; taps * taps];
+        for edge_mode in [SKIDEdgeMode::Clamp, SKIDEdgeMode::Wrap, SKIDEdgeMode::Mirror, SKIDEdgeMode::Constant] {
+            let options = SKIDConvolutionOptions {
+                edge_mode,
+                constant_color: SKIDColor::new(0.25, 0.25, 0.25, 0.25),
+                ..Default::default()
+            };
+            let result = blur::box_blur::<cubecl::wgpu::WgpuRuntime>(&device, &image, radius, &options);
+            assert_eq!(result.get_pixel_format(), SKIDPixelFormat::R32F);
+            let expected = reference_convolve_2d(&values, width, height, &kernel, taps, taps, edge_mode, 0.25);
+            for (i, (a, b)) in result.get_1d_data_as_f32_channels().iter().zip(&expected).enumerate() {
+                assert!((a - b).abs() < 1e-5, "r={} {:?} pixel {}: {} != {}", radius, edge_mode, i, a, b);
+            }
+        }
+    }
+}
+
+#[test]
+fn gpu_ao_map_tests() {
+    use crate::processor::make_ao_map::{self, SKIDAOMapOptions};
+    let device = Default::default();
+    let options = SKIDAOMapOptions { radius: 4.0, step_count: 8, ..Default::default() };
+
+    // 평탄한 높이맵은 어디에서도 가려지지 않는다.
+    let flat = SKIDImage::from_f32_data(SKIDSizeVector2::new(16, 16), SKIDPixelFormat::R32F, &[0.5; 256], 1);
+    let ao = make_ao_map::make_ao_map::<cubecl::wgpu::WgpuRuntime>(&device, &flat, &options);
+    assert_eq!(ao.get_pixel_format(), SKIDPixelFormat::R32F);
+    assert!(ao.get_1d_data_as_f32_channels().iter().all(|&v| v == 1.0));
+
+    // 가운데 5x5 구덩이: 바닥 중심은 가려지고, 반경 밖의 평지는 그대로 1 이다.
+    let size: usize = 32;
+    let values: Vec<f32> = (0..size * size)
+        .map(|i| if (i % size).abs_diff(16) <= 2 && (i / size).abs_diff(16) <= 2 { 0.0 } else { 1.0 })
+        .collect();
+    let pit = SKIDImage::from_f32_data(SKIDSizeVector2::new(size, size), SKIDPixelFormat::R32F, &values, 1);
+    let ao = make_ao_map::make_ao_map::<cubecl::wgpu::WgpuRuntime>(&device, &pit, &options).get_1d_data_as_f32_channels();
+    assert!(ao[16 * size + 16] < 0.9, "{}", ao[16 * size + 16]);
+    assert_eq!(ao[0], 1.0);
+}
+
+#[test]
+fn gpu_curvature_map_tests() {
+    use crate::processor::make_curvature_map::{self, SKIDCurvatureOptions, SKIDCurvatureSource};
+    let device = Default::default();
+    let options = SKIDCurvatureOptions::default();
+    let normal_options = SKIDCurvatureOptions { source: SKIDCurvatureSource::NormalMap, ..Default::default() };
+
+    // 평탄한 입력은 Signed 모드에서 0.5 (곡률 없음)
+    let flat = SKIDImage::from_f32_data(SKIDSizeVector2::new(16, 16), SKIDPixelFormat::R32F, &[0.5; 256], 1);
+    let curvature = make_curvature_map::make_curvature_map::<cubecl::wgpu::WgpuRuntime>(&device, &flat, &options);
+    assert_eq!(curvature.get_pixel_format(), SKIDPixelFormat::R32F);
+    assert!(curvature.get_1d_data_as_f32_channels().iter().all(|&v| v == 0.5));
+
+    // 가운데 가우시안 언덕(+)과 구덩이(-): 높이맵과, 그 높이맵으로 만든 노멀맵에서 같은 부호가 나와야 한다.
+    let size: usize = 32;
+    for (amplitude, convex) in [(0.4f32, true), (-0.4, false)] {
+        let heights: Vec<f32> = (0..size * size)
+            .flat_map(|i| {
+                let (dx, dy) = ((i % size) as f32 - 16.0, (i / size) as f32 - 16.0);
+                let h = 0.5 + amplitude * (-(dx * dx + dy * dy) / 32.0).exp();
+                [h, h, h, 1.0]
+            })
+            .collect();
+        let height_map = SKIDImage::from_f32_data(SKIDSizeVector2::new(size, size), SKIDPixelFormat::RGBA32F, &heights, 4);
+        let center = 16 * size + 16;
+
+        let from_height = make_curvature_map::make_curvature_map::<cubecl::wgpu::WgpuRuntime>(&device, &height_map, &options)
+            .get_1d_data_as_f32_channels()[center];
+        let normal_map = make_normal_map::make_normal_map_base::<cubecl::wgpu::WgpuRuntime>(device.clone(), &height_map, None, None);
+        let from_normal = make_curvature_map::make_curvature_map::<cubecl::wgpu::WgpuRuntime>(&device, &normal_map, &normal_options)
+            .get_1d_data_as_f32_channels()[center];
+
+        assert_eq!(from_height > 0.5, convex, "height map curvature {}", from_height);
+        assert_eq!(from_normal > 0.5, convex, "normal map curvature {}", from_normal);
+        assert!(from_height != 0.5 && from_normal != 0.5);
+    }
+}
+
+#[test]
+fn gpu_seamless_tests() {
+    use crate::processor::seamless::{self, SKIDSeamlessMode};
+    let device = Default::default();
+
+    // 가로 경사(0 → 1)는 타일링하면 왼쪽/오른쪽 끝이 크게 어긋난다.
+    let size: usize = 32;
+    let ramp: Vec<f32> = (0..size * size).map(|i| (i % size) as f32 / size as f32).collect();
+    let image = SKIDImage::from_f32_data(SKIDSizeVector2::new(size, size), SKIDPixelFormat::R32F, &ramp, 1);
+    let seam = |img: &SKIDImage, y: usize| {
+        let values = img.get_1d_data_as_f32_channels();
+        let w = img.get_size().width;
+        (values[y * w] - values[y * w + w - 1]).abs()
+    };
+    assert!(seam(&image, 16) > 0.9);
+
+    // 처리 후에는 왼쪽 열과 오른쪽 열이 원본의 이웃 픽셀 정도로만 차이 난다.
+    let offset = seamless::make_seamless::<cubecl::wgpu::WgpuRuntime>(&device, &image, SKIDSeamlessMode::OffsetBlend, 8).unwrap();
+    assert_eq!(offset.get_size(), image.get_size());
+    let cross = seamless::make_seamless::<cubecl::wgpu::WgpuRuntime>(&device, &image, SKIDSeamlessMode::CrossFade, 8).unwrap();
+    assert_eq!((cross.get_size().width, cross.get_size().height), (24, 24));
+    for y in [0, 12, 20] {
+        assert!(seam(&offset, y) <= 1.5 / size as f32, "offset blend row {}: {}", y, seam(&offset, y));
+        assert!(seam(&cross, y) <= 1.5 / size as f32, "cross fade row {}: {}", y, seam(&cross, y));
+    }
+    assert!(seamless::make_seamless::<cubecl::wgpu::WgpuRuntime>(&device, &image, SKIDSeamlessMode::CrossFade, 17).is_err());
+
+    // 3x2 타일 미리보기는 (3w, 2h) 크기이고 각 타일은 원본과 같다.
+    let preview = seamless::tile_preview::<cubecl::wgpu::WgpuRuntime>(&device, &image, 3, 2);
+    assert_eq!((preview.get_size().width, preview.get_size().height), (size * 3, size * 2));
+    assert_eq!(preview.get_pixel_format(), SKIDPixelFormat::R32F);
+    let values = preview.get_1d_data_as_f32_channels();
+    assert_eq!(values[(size + 7) * size * 3 + 2 * size + 5], ramp[7 * size + 5]);
+}
+
+#[test]
+fn gpu_noise_tests() {
+    use crate::processor::noise::{self, SKIDFractalType, SKIDNoiseOptions, SKIDNoiseType};
+    let device = Default::default();
+    let size = SKIDSizeVector2::new(64, 32);
+    let generate = |options: &SKIDNoiseOptions| {
+        noise::generate_noise::<cubecl::wgpu::WgpuRuntime>(&device, size, SKIDPixelFormat::R32F, options).get_1d_data_as_f32_channels()
+    };
+
+    for noise_type in [SKIDNoiseType::Value, SKIDNoiseType::Perlin, SKIDNoiseType::Simplex, SKIDNoiseType::Worley] {
+        for fractal in [SKIDFractalType::None, SKIDFractalType::Fbm, SKIDFractalType::Ridged, SKIDFractalType::Turbulence] {
+            let options = SKIDNoiseOptions { noise_type, fractal, seed: 7, warp_strength: 0.25, ..Default::default() };
+
+            // 같은 시드는 같은 결과, 다른 시드는 다른 결과
+            let values = generate(&options);
+            assert_eq!(values, generate(&options), "{:?}/{:?}", noise_type, fractal);
+            assert_ne!(values, generate(&SKIDNoiseOptions { seed: 8, ..options }), "{:?}/{:?}", noise_type, fractal);
+
+            // 값은 [0, 1] 범위이고 상수가 아니다.
+            assert!(values.iter().all(|v| (0.0..=1.0).contains(v)), "{:?}/{:?}", noise_type, fractal);
+            let (min, max) = values.iter().fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+            assert!(max - min > 0.1, "{:?}/{:?}: {}..{}", noise_type, fractal, min, max);
+        }
+    }
+}
+
+#[test]
+fn gpu_filter_region_tests() {
+    use crate::model::skid_image::SKIDRect;
+    use crate::processor::blur::{self, SKIDGaussianMode};
+    use crate::processor::convolution::{self, SKIDConvolutionKernel, SKIDConvolutionOptions, SKIDEdgeMode};
+    let device = Default::default();
+
+    let (width, height) = (24, 16);
+    let values: Vec<f32> = (0..width * height).map(|i| ((i * 7919) % 97) as f32 / 97.0).collect();
+    let image = SKIDImage::from_f32_data(SKIDSizeVector2::new(width, height), SKIDPixelFormat::R32F, &values, 1);
+    // 왼쪽 가장자리에 닿는 영역과 안쪽 영역
+    let rois = [SKIDRect::new(0, 2, 9, 7), SKIDRect::new(10, 5, 8, 6)];
+
+    // ROI 안은 전체 처리 결과와 같고, 밖은 원본 그대로여야 한다.
+    let check = |name: &str, full: &SKIDImage, region: &SKIDImage, roi: SKIDRect| {
+        let (full, region) = (full.get_1d_data_as_f32_channels(), region.get_1d_data_as_f32_channels());
+        for y in 0..height {
+            for x in 0..width {
+                let i = y * width + x;
+                let inside = (roi.x..roi.x + roi.width).contains(&x) && (roi.y..roi.y + roi.height).contains(&y);
+                let expected = if inside { full[i] } else { values[i] };
+                assert!((region[i] - expected).abs() < 1e-5, "{} {:?} ({}, {}): {} != {}", name, roi, x, y, region[i], expected);
+            }
+        }
+    };
+
+    let kernel = SKIDConvolutionKernel::new(3, 3, vec![0.0, -1.0, 0.0, -1.0, 5.0, -1.0, 0.0, -1.0, 0.0]).unwrap();
+    for edge_mode in [SKIDEdgeMode::Clamp, SKIDEdgeMode::Wrap, SKIDEdgeMode::Mirror] {
+        let options = SKIDConvolutionOptions { edge_mode, ..Default::default() };
+        for roi in rois {
+            let full = convolution::convolve::<cubecl::wgpu::WgpuRuntime>(&device, &image, &kernel, &options);
+            let mut target = image.clone();
+            convolution::convolve_region::<cubecl::wgpu::WgpuRuntime>(&device, &image, &mut target, Some(roi), &kernel, &options).unwrap();
+            check("convolve", &full, &target, roi);
+
+            let full = blur::gaussian_blur::<cubecl::wgpu::WgpuRuntime>(&device, &image, 1.5, SKIDGaussianMode::Exact, &options);
+            let mut target = image.clone();
+            blur::gaussian_blur_region::<cubecl::wgpu::WgpuRuntime>(&device, &image, &mut target, Some(roi), 1.5, SKIDGaussianMode::Exact, &options).unwrap();
+            check("gaussian_blur", &full, &target, roi);
+
+            let full = blur::box_blur::<cubecl::wgpu::WgpuRuntime>(&device, &image, 2, &options);
+            let mut target = image.clone();
+            blur::box_blur_region::<cubecl::wgpu::WgpuRuntime>(&device, &image, &mut target, Some(roi), 2, &options).unwrap();
+            check("box_blur", &full, &target, roi);
+
+            let full = blur::bilateral_blur::<cubecl::wgpu::WgpuRuntime>(&device, &image, 1.5, 0.2, &options);
+            let mut target = image.clone();
+            blur::bilateral_blur_region::<cubecl::wgpu::WgpuRuntime>(&device, &image, &mut target, Some(roi), 1.5, 0.2, &options).unwrap();
+            check("bilateral_blur", &full, &target, roi);
+        }
+    }
+
+    for roi in rois {
+        let full = sharpen::unsharp_mask::<cubecl::wgpu::WgpuRuntime>(&device, &image, 1.5, 1.0, 0.0, SKIDDetailMode::PerChannel);
+        let mut target = image.clone();
+        sharpen::unsharp_mask_region::<cubecl::wgpu::WgpuRuntime>(&device, &image, &mut target, Some(roi), 1.5, 1.0, 0.0, SKIDDetailMode::PerChannel).unwrap();
+        check("unsharp_mask", &full, &target, roi);
+
+        let full = sharpen::high_pass::<cubecl::wgpu::WgpuRuntime>(&device, &image, 1.5, 1.0, 0.0, SKIDDetailMode::PerChannel);
+        let mut target = image.clone();
+        sharpen::high_pass_region::<cubecl::wgpu::WgpuRuntime>(&device, &image, &mut target, Some(roi), 1.5, 1.0, 0.0, SKIDDetailMode::PerChannel).unwrap();
+        check("high_pass", &full, &target, roi);
+    }
+
+    // ROI 가 이미지를 벗어나면 오류
+    let mut target = image.clone();
+    assert!(blur::box_blur_region::<cubecl::wgpu::WgpuRuntime>(&device, &image, &mut target, Some(SKIDRect::new(20, 0, 8, 4)), 1, &SKIDConvolutionOptions::default()).is_err());
+}
@@ -0,0 +1,246 @@
+use once_cell::sync::Lazy;
+
+/// f32 값을 정수 단계(8/16비트)로 줄일 때의 양자화 방식.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDDitherMethod {
+    /// 가장 가까운 단계로 반올림 (디더링 없음)
+    #[default]
+    Round = 0,
+    /// 8x8 Bayer 행렬 순서 디더링
+    Bayer = 1,
+    /// 64x64 블루 노이즈 임계값 디더링 (Bayer 보다 격자 무늬가 덜 보인다)
+    BlueNoise = 2,
+    /// Floyd-Steinberg 오차 확산 (지그재그 순회, 단일 스레드)
+    FloydSteinberg = 3,
+}
+
+impl SKIDDitherMethod {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SKIDDitherMethod::Round),
+            1 => Some(SKIDDitherMethod::Bayer),
+            2 => Some(SKIDDitherMethod::BlueNoise),
+            3 => Some(SKIDDitherMethod::FloydSteinberg),
+            _ => None,
+        }
+    }
+    /// 명령행 등에서 쓰는 이름으로 찾는다. 대소문자, `-`, `_`는 구분하지 않는다.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let key: String = name.chars().filter(|c| *c != '-' && *c != '_').collect::<String>().to_ascii_lowercase();
+        match key.as_str() {
+            "round" | "none" => Some(SKIDDitherMethod::Round),
+            "bayer" | "ordered" => Some(SKIDDitherMethod::Bayer),
+            "bluenoise" => Some(SKIDDitherMethod::BlueNoise),
+            "floydsteinberg" | "fs" => Some(SKIDDitherMethod::FloydSteinberg),
+            _ => None,
+        }
+    }
+}
+
+/// 양자화 결과로 쓸 수 있는 정수 채널 타입.
+pub trait SKIDQuantizedSample: Copy + Default + Send {
+    /// 1.0 에 대응하는 최대 단계
+    const MAX_LEVEL: f32;
+    /// 0..=MAX_LEVEL 범위로 이미 정수화된 단계 값을 저장한다.
+    fn from_level(level: f32) -> Self;
+}
+
+impl SKIDQuantizedSample for u8 {
+    const MAX_LEVEL: f32 = 255.0;
+    fn from_level(level: f32) -> Self {
+        level as u8
+    }
+}
+
+impl SKIDQuantizedSample for u16 {
+    const MAX_LEVEL: f32 = 65535.0;
+    fn from_level(level: f32) -> Self {
+        level as u16
+    }
+}
+
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+const BLUE_NOISE_SIZE: usize = 64;
+
+/// void-and-cluster 로 만든 64x64 블루 노이즈 임계값 (0~1). 처음 사용할 때 한 번 생성된다.
+static BLUE_NOISE: Lazy<Vec<f32>> = Lazy::new(generate_blue_noise);
+
+/// Ulichney 의 void-and-cluster 방식으로 토러스 위 블루 노이즈 순위 행렬을 만든다.
+/// 초기 패턴은 고정 시드로 뿌리므로 결과는 항상 같다.
+fn generate_blue_noise() -> Vec<f32> {
+    let n = BLUE_NOISE_SIZE;
+    let total = n * n;
+    let sigma = 1.5f32;
+    let kernel: Vec<f32> = (0..total)
+        .map(|i| {
+            let (dx, dy) = (i % n, i / n);
+            let (dx, dy) = (dx.min(n - dx) as f32, dy.min(n - dy) as f32);
+            (-(dx * dx + dy * dy) / (2.0 * sigma * sigma)).exp()
+        })
+        .collect();
+    let splat = |energy: &mut [f32], p: usize, sign: f32| {
+        let (px, py) = (p % n, p / n);
+        for (i, e) in energy.iter_mut().enumerate() {
+            let (x, y) = (i % n, i / n);
+            *e += sign * kernel[((y + n - py) % n) * n + (x + n - px) % n];
+        }
+    };
+    // 켜진 점 중 에너지가 가장 큰 곳 (가장 빽빽한 클러스터) / 꺼진 점 중 가장 작은 곳 (가장 큰 빈 곳)
+    let tightest = |energy: &[f32], on: &[bool]| {
+        (0..total).filter(|&i| on[i]).max_by(|&a, &b| energy[a].total_cmp(&energy[b])).unwrap()
+    };
+    let largest_void = |energy: &[f32], on: &[bool]| {
+        (0..total).filter(|&i| !on[i]).min_by(|&a, &b| energy[a].total_cmp(&energy[b])).unwrap()
+    };
+
+    let mut on = vec![false; total];
+    let mut energy = vec![0.0f32; total];
+    let initial = total / 10;
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    let mut placed = 0;
+    while placed < initial {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let p = (state % total as u64) as usize;
+        if !on[p] {
+            on[p] = true;
+            splat(&mut energy, p, 1.0);
+            placed += 1;
+        }
+    }
+    // 가장 빽빽한 점을 가장 큰 빈 곳으로 옮기는 일을 더 이상 바뀌지 않을 때까지 반복한다.
+    loop {
+        let cluster = tightest(&energy, &on);
+        on[cluster] = false;
+        splat(&mut energy, cluster, -1.0);
+        let void = largest_void(&energy, &on);
+        on[void] = true;
+        splat(&mut energy, void, 1.0);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut rank = vec![0usize; total];
+    let (mut phase_on, mut phase_energy) = (on.clone(), energy.clone());
+    for r in (0..initial).rev() {
+        let cluster = tightest(&phase_energy, &phase_on);
+        rank[cluster] = r;
+        phase_on[cluster] = false;
+        splat(&mut phase_energy, cluster, -1.0);
+    }
+    for r in initial..total {
+        let void = largest_void(&energy, &on);
+        rank[void] = r;
+        on[void] = true;
+        splat(&mut energy, void, 1.0);
+    }
+    rank.into_iter().map(|r| (r as f32 + 0.5) / total as f32).collect()
+}
+
+/// (x, y) 위치의 순서 디더링 임계값 (0~1).
+fn threshold(method: SKIDDitherMethod, x: usize, y: usize) -> f32 {
+    match method {
+        SKIDDitherMethod::Bayer => (BAYER_8X8[y % 8][x % 8] as f32 + 0.5) / 64.0,
+        SKIDDitherMethod::BlueNoise => BLUE_NOISE[(y % BLUE_NOISE_SIZE) * BLUE_NOISE_SIZE + x % BLUE_NOISE_SIZE],
+        _ => 0.5,
+    }
+}
+
+/// 순서 디더링 방식의 임계값 타일 (행 우선, 0~1)과 한 변의 길이. 순서 디더링이 아니면 None.
+/// GPU 커널에서 같은 임계값을 쓰는 프로세서(팔레트 매핑 등)용.
+pub fn threshold_map(method: SKIDDitherMethod) -> Option<(Vec<f32>, usize)> {
+    match method {
+        SKIDDitherMethod::Bayer => Some(((0..64).map(|i| threshold(method, i % 8, i / 8)).collect(), 8)),
+        SKIDDitherMethod::BlueNoise => Some((BLUE_NOISE.clone(), BLUE_NOISE_SIZE)),
+        _ => None,
+    }
+}
+
+/// 행 우선 인터리브 f32 데이터(`channels`채널, 0~1)를 정수 채널로 양자화한다.
+///
+/// 범위 밖 값은 클램프되며, 0.0 과 1.0 은 어떤 방식에서도 정확히 0 과 최대값이 된다.
+/// 오차 확산을 제외한 방식은 행 단위로 `thread_count`개 스레드에 나눠 처리한다.
+pub fn quantize<T: SKIDQuantizedSample>(
+    data: &[f32],
+    width: usize,
+    channels: usize,
+    method: SKIDDitherMethod,
+    thread_count: usize,
+) -> Vec<T> {
+    let row_len = width * channels;
+    let mut out = vec![T::default(); data.len()];
+    if row_len == 0 || data.is_empty() {
+        return out;
+    }
+    if method == SKIDDitherMethod::FloydSteinberg {
+        floyd_steinberg(data, &mut out, width, channels);
+        return out;
+    }
+    if method == SKIDDitherMethod::BlueNoise {
+        Lazy::force(&BLUE_NOISE);
+    }
+
+    let height = data.len() / row_len;
+    let rows_per_thread = height.div_ceil(thread_count.max(1));
+    std::thread::scope(|scope| {
+        for (chunk_idx, chunk) in out.chunks_mut(rows_per_thread * row_len).enumerate() {
+            let start_row = chunk_idx * rows_per_thread;
+            scope.spawn(move || {
+                for (i, sample) in chunk.iter_mut().enumerate() {
+                    let y = start_row + i / row_len;
+                    let x = (i % row_len) / channels;
+                    let value = data[y * row_len + i % row_len].clamp(0.0, 1.0) * T::MAX_LEVEL;
+                    let level = (value + threshold(method, x, y)).floor().min(T::MAX_LEVEL);
+                    *sample = T::from_level(level);
+                }
+            });
+        }
+    });
+    out
+}
+
+fn floyd_steinberg<T: SKIDQuantizedSample>(data: &[f32], out: &mut [T], width: usize, channels: usize) {
+    let row_len = width * channels;
+    let height = data.len() / row_len;
+    // 현재 행과 다음 행으로 넘길 오차 (단계 단위)
+    let mut current = vec![0.0f32; row_len];
+    let mut next = vec![0.0f32; row_len];
+    for y in 0..height {
+        let reverse = y % 2 == 1;
+        for step in 0..width {
+            let x = if reverse { width - 1 - step } else { step };
+            let forward = if reverse { x.checked_sub(1) } else { Some(x + 1).filter(|&v| v < width) };
+            let backward = if reverse { Some(x + 1).filter(|&v| v < width) } else { x.checked_sub(1) };
+            for c in 0..channels {
+                let i = x * channels + c;
+                let wanted = data[y * row_len + i].clamp(0.0, 1.0) * T::MAX_LEVEL + current[i];
+                let level = wanted.round().clamp(0.0, T::MAX_LEVEL);
+                out[y * row_len + i] = T::from_level(level);
+                let error = wanted - level;
+                if let Some(fx) = forward {
+                    current[fx * channels + c] += error * 7.0 / 16.0;
+                    next[fx * channels + c] += error / 16.0;
+                }
+                if let Some(bx) = backward {
+                    next[bx * channels + c] += error * 3.0 / 16.0;
+                }
+                next[i] += error * 5.0 / 16.0;
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+        next.fill(0.0);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::model::skid_image::SKIDSizeVector2;
 use crate::model::{skid_color::SKIDColor, skid_image::SKIDImage};
+use crate::utils::dither::{self, SKIDDitherMethod};
 use image::{ColorType, ImageEncoder, ImageFormat, Rgba};
 use std::fs::File;
 use std::io::{BufWriter,BufReader};
@@ -17,107 +18,81 @@ use std::path::Path;
 //     a: 0.0,
 // };
 
-fn get_u16_color_vectors(
-    width: usize,
-    height: usize,
-    origin_image: Arc<Vec<Vec<SKIDColor>>>,
-    num_threads: usize,
-    rows_per_thread: usize,
-) -> Vec<Vec<[u16; 4]>> {
-    let mut handles = Vec::new();
-    let start = std::time::Instant::now();
-    for thread_idx in 0..num_threads {
-        let origin_image = Arc::clone(&origin_image);
-        let start_row = thread_idx * rows_per_thread;
-        let end_row = ((thread_idx + 1) * rows_per_thread).min(height);
-
-        // 각 스레드별 버퍼를 미리 준비
-        let rows_count = end_row.saturating_sub(start_row);
-        let mut local_buffer = vec![vec![[0u16; 4]; width]; rows_count];
-
-        let handle = thread::spawn(move || {
-            for (i, y) in (start_row..end_row).enumerate() {
-                let now_row = &origin_image[y];
-                for x in 0..width {
-                    let color = now_row[x];
-                    local_buffer[i][x] = [
-                        (color.r * 65535.0) as u16,
-                        (color.g * 65535.0) as u16,
-                        (color.b * 65535.0) as u16,
-                        (color.a * 65535.0) as u16,
-                    ];
-                }
-            }
-            local_buffer
-        });
-        handles.push(handle);
-    }
-    // println!("Thread spawn time: {:?}", start.elapsed());
-    // 스레드 결과 합치기
-    let mut rows: Vec<Vec<[u16; 4]>> = Vec::with_capacity(height);
-    for handle in handles {
-        let buffer = handle.join().unwrap();
-        rows.extend(buffer);
-    }
-    rows
+/// PNG 로 저장할 때 채널당 비트 수.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SKIDPngBitDepth {
+    Eight,
+    #[default]
+    Sixteen,
 }
 
-
+/// 16비트 RGBA PNG 로 저장한다. 각 채널은 가장 가까운 단계로 반올림된다.
 pub fn export_to_png(
     image: &SKIDImage,
     file_path: &str,
     thread_count: Option<usize>,
 ) -> Result<(), String> {
-    // 최적화 방향:
-    // 1. 각 스레드가 자신의 결과를 별도의 버퍼에 저장하고, 마지막에 합치기만 하도록 Mutex 사용 최소화
-    // 2. Arc<Mutex<>> 대신 Arc<Vec<...>>로 각 스레드가 독립적으로 작업
-    // 3. clone 대신 참조만 사용 (가능하다면)
-    // 4. 불필요한 unwrap_or, get 등 제거
+    export_to_png_dithered(image, file_path, thread_count, SKIDPngBitDepth::Sixteen, SKIDDitherMethod::Round)
+}
 
+/// `bit_depth`비트 RGBA PNG 로 저장한다. f32 값을 `method`로 양자화하므로
+/// 8비트로 내보낼 때 Bayer/블루 노이즈/오차 확산 디더링으로 그라디언트 밴딩을 줄일 수 있다.
+pub fn export_to_png_dithered(
+    image: &SKIDImage,
+    file_path: &str,
+    thread_count: Option<usize>,
+    bit_depth: SKIDPngBitDepth,
+    method: SKIDDitherMethod,
+) -> Result<(), String> {
     let file = File::create(file_path).map_err(|e| e.to_string())?;
     let mut writer = BufWriter::new(file);
     let size = image.get_size();
-    let width = size.width;
-    let height = size.height;
+    let (width, height) = (size.width as u32, size.height as u32);
     let num_threads = thread_count.unwrap_or(4);
-    let rows_per_thread = (height + num_threads - 1) / num_threads;
-
-    let rows = get_u16_color_vectors(
-        width,
-        height,
-        Arc::new(image.get_data().clone()),
-        num_threads,
-        rows_per_thread,
-    );
-    let start = std::time::Instant::now();
-    // 1차원 u16 벡터로 변환
-    let flat: Vec<u16> = rows.into_iter()
-        .flat_map(|row| row.into_iter().flat_map(|px| px))
-        .collect();
-
-    
-    let img: image::ImageBuffer<Rgba<u16>, _> =
-        image::ImageBuffer::from_raw(width as u32, height as u32, flat)
-            .ok_or("Failed to create image buffer")?;
-    println!("Image buffer creation time: {:?}", start.elapsed());
+    let data = image.get_1d_data_as_f32();
+
     let start = std::time::Instant::now();
-    // Write the image to the file
-    img.write_to(&mut writer, ImageFormat::Png)
-        .map_err(|e| e.to_string())?;
+    let result = match bit_depth {
+        SKIDPngBitDepth::Eight => {
+            let flat: Vec<u8> = dither::quantize(&data, size.width, 4, method, num_threads);
+            let img: image::ImageBuffer<Rgba<u8>, _> =
+                image::ImageBuffer::from_raw(width, height, flat).ok_or("Failed to create image buffer")?;
+            println!("Image buffer creation time: {:?}", start.elapsed());
+            img.write_to(&mut writer, ImageFormat::Png)
+        }
+        SKIDPngBitDepth::Sixteen => {
+            let flat: Vec<u16> = dither::quantize(&data, size.width, 4, method, num_threads);
+            let img: image::ImageBuffer<Rgba<u16>, _> =
+                image::ImageBuffer::from_raw(width, height, flat).ok_or("Failed to create image buffer")?;
+            println!("Image buffer creation time: {:?}", start.elapsed());
+            img.write_to(&mut writer, ImageFormat::Png)
+        }
+    };
+    result.map_err(|e| e.to_string())?;
     println!("Image write time: {:?}", start.elapsed());
     Ok(())
-
 }
 
+/// R, G, B, A 채널을 각각 8비트 그레이스케일 PNG 로, 전체를 `combined.png`로 저장한다. 값은 반올림한다.
 pub fn export_rgba_channels_to_png(
     image: &SKIDImage,
     base_file_path: &str,
 ) -> Result<(), String> {
+    export_rgba_channels_to_png_dithered(image, base_file_path, None)
+}
+
+/// `dither`로 양자화하는 `export_rgba_channels_to_png`. `dither`가 None 이면 반올림한다.
+pub fn export_rgba_channels_to_png_dithered(
+    image: &SKIDImage,
+    base_file_path: &str,
+    dither: Option<SKIDDitherMethod>,
+) -> Result<(), String> {
 
     let size = image.get_size();
     let width = size.width;
     let height = size.height;
-    let data = image.get_data();
+    let data = Arc::new(image.get_1d_data_as_f32());
+    let method = dither.unwrap_or_default();
 
     let channels = ["r", "g", "b", "a"];
     let mut handles = Vec::new();
@@ -128,7 +103,7 @@ pub fn export_rgba_channels_to_png(
 
     println!("Exporting channels to PNG files...");
     for (i, &ch) in channels.iter().enumerate() {
-        let data = data.clone();
+        let data = Arc::clone(&data);
         let file_path = format!(
             "{}/{}.png",
             dir_path.display(),
@@ -136,19 +111,8 @@ pub fn export_rgba_channels_to_png(
         );
         println!("Exporting channel {} to {}", ch, file_path);
         let handle = thread::spawn(move || {
-            let mut flat: Vec<u8> = Vec::with_capacity(width * height);
-            for row in &data {
-                for color in row {
-                    let v = match i {
-                        0 => color.r,
-                        1 => color.g,
-                        2 => color.b,
-                        3 => color.a,
-                        _ => 0.0,
-                    };
-                    flat.push((v.clamp(0.0, 1.0) * 255.0) as u8);
-                }
-            }
+            let plane: Vec<f32> = data.iter().skip(i).step_by(4).copied().collect();
+            let flat: Vec<u8> = dither::quantize(&plane, width, 1, method, 1);
             let img: image::GrayImage =
                 image::ImageBuffer::from_vec(width as u32, height as u32, flat)
                     .ok_or("Failed to create channel image buffer")?;
@@ -159,7 +123,8 @@ pub fn export_rgba_channels_to_png(
         });
         handles.push(handle);
     }
-    export_to_png(image, &format!("{}/combined.png", dir_path.display()), None)?;
+    let combined = format!("{}/combined.png", dir_path.display());
+    export_to_png_dithered(image, &combined, None, SKIDPngBitDepth::Sixteen, method)?;
 
     for handle in handles {
         handle.join().map_err(|_| "Thread join failed".to_string())??;
@@ -187,7 +152,7 @@ pub fn import_from_png(file_path: &str,thread_count:Option<usize>) -> Result<SKI
         height as usize
     ]));
 
-    let row_per_thread = ((height + thread_count as u32 - 1) / thread_count as u32) as usize;
+    let row_per_thread = height.div_ceil(thread_count as u32) as usize;
     let img = Arc::new(img);
 
     // Fill the SKIDImage with pixel data
@@ -203,7 +168,7 @@ pub fn import_from_png(file_path: &str,thread_count:Option<usize>) -> Result<SKI
         let handle = thread::spawn(move || {
             for y in start_row..end_row {
                 for x in 0..width {
-                    let pixel = img.get_pixel(x as u32, y as u32);
+                    let pixel = img.get_pixel(x, y as u32);
                     let Rgba([r, g, b, a]) = *pixel;
                     let color = SKIDColor::new(
                         r as f32 / 65535.0,
@@ -242,6 +207,7 @@ pub fn import_from_png(file_path: &str,thread_count:Option<usize>) -> Result<SKI
 
 
 
+/// 압축/필터 설정을 지정해 16비트 RGBA PNG 로 저장한다. 값은 반올림한다.
 pub fn export_to_png_by_custom(
     image: &SKIDImage,
     file_path: &str,
@@ -249,6 +215,18 @@ pub fn export_to_png_by_custom(
     compression_profile: Option<CompressionType>,
     filter_profile: Option<FilterType>,
 ) -> Result<(), String> {
+    export_to_png_by_custom_dithered(image, file_path, thread_count, compression_profile, filter_profile, None)
+}
+
+/// `dither`로 양자화하는 `export_to_png_by_custom`. `dither`가 None 이면 반올림한다.
+pub fn export_to_png_by_custom_dithered(
+    image: &SKIDImage,
+    file_path: &str,
+    thread_count: Option<usize>,
+    compression_profile: Option<CompressionType>,
+    filter_profile: Option<FilterType>,
+    dither: Option<SKIDDitherMethod>,
+) -> Result<(), String> {
 
     let file = File::create(file_path).map_err(|e| e.to_string())?;
     let mut writer = BufWriter::new(file);
@@ -256,25 +234,11 @@ pub fn export_to_png_by_custom(
     let width = size.width;
     let height = size.height;
     let num_threads = thread_count.unwrap_or(4);
-    let rows_per_thread = (height + num_threads - 1) / num_threads;
-
-    // 최종 픽셀 데이터를 담을 2차원 벡터 (스레드별로 분할)
-    let origin_image = Arc::new(image.get_data().clone());
 
-    let rows = get_u16_color_vectors(
-        width,
-        height,
-        Arc::clone(&origin_image),
-        num_threads,
-        rows_per_thread,
-    );
-    // 1차원 u16 벡터로 변환
+    let samples: Vec<u16> =
+        dither::quantize(&image.get_1d_data_as_f32(), width, 4, dither.unwrap_or_default(), num_threads);
     // u16을 u8 두 개로 분리하여 1차원 벡터로 변환 (길이 2배)
-    let flat: Vec<u8> = rows.into_iter()
-        .flat_map(|row| row.into_iter().flat_map(|px| {
-            px.iter().flat_map(|&v| v.to_le_bytes()).collect::<Vec<_>>() // [u16;4] -> [u8;8]
-        }))
-        .collect();
+    let flat: Vec<u8> = samples.into_iter().flat_map(u16::to_le_bytes).collect();
 
     let img_writer = PngEncoder::new_with_quality(&mut writer, compression_profile.unwrap_or(CompressionType::Default),filter_profile.unwrap_or(FilterType::NoFilter));
 
@@ -282,4 +246,44 @@ pub fn export_to_png_by_custom(
         .map_err(|e| e.to_string())?;
     println!("Image exported to {} successfully.", file_path);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 인덱스 컬러(8비트 PLTE) PNG 로 저장한다. 팔레트에 불투명하지 않은 색이 있으면 tRNS 청크도 쓴다.
+///
+/// - `indices` : 행 우선 픽셀별 팔레트 인덱스 (width * height 개)
+/// - `palette` : 최대 256색, 감마 인코딩된 sRGB
+pub fn export_indexed_png(
+    file_path: &str,
+    size: SKIDSizeVector2,
+    indices: &[u8],
+    palette: &[SKIDColor],
+) -> Result<(), String> {
+    if palette.is_empty() || palette.len() > 256 {
+        return Err(format!("Indexed PNG needs 1..=256 palette colors, got {}", palette.len()));
+    }
+    if indices.len() != size.width * size.height {
+        return Err(format!("Expected {} indices, got {}", size.width * size.height, indices.len()));
+    }
+    if let Some(&bad) = indices.iter().find(|&&i| i as usize >= palette.len()) {
+        return Err(format!("Palette index {} out of range ({} colors)", bad, palette.len()));
+    }
+
+    let rgba: Vec<[u8; 4]> = palette.iter().map(SKIDColor::to_u8_array).collect();
+    let plte: Vec<u8> = rgba.iter().flat_map(|c| [c[0], c[1], c[2]]).collect();
+    // tRNS 는 마지막 불투명하지 않은 항목까지만 쓰면 된다.
+    let trns_len = rgba.iter().rposition(|c| c[3] != 255).map_or(0, |i| i + 1);
+    let trns: Vec<u8> = rgba[..trns_len].iter().map(|c| c[3]).collect();
+
+    let file = File::create(file_path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), size.width as u32, size.height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(plte);
+    if !trns.is_empty() {
+        encoder.set_trns(trns);
+    }
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(indices).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -24,18 +24,18 @@ pub fn launch<R: Runtime>(
     let h_u32 = height as u32;
     let output_a_handle = client.empty(input.len() * core::mem::size_of::<f32>());
     let (max_thread_x, max_thread_y, _max_thread_z) = R::max_cube_count();
-    let block_x = (w_u32 + max_thread_x - 1) / max_thread_x;
+    let block_x = w_u32.div_ceil(max_thread_x);
     let threads_x = if w_u32 < max_thread_x { w_u32  } else { max_thread_x };
 
-    let block_y = (h_u32 + max_thread_y - 1) / max_thread_y;
+    let block_y = h_u32.div_ceil(max_thread_y);
     let threads_y = if h_u32 < max_thread_y { h_u32 } else { max_thread_y };
 
     println!("Launching normalize with runtime: {}", input.len());
     unsafe {
         norm_test::launch_unchecked::<f32, R>(
             &client,
-            CubeCount::Static(threads_x as u32, threads_y as u32, 1),
-            CubeDim::new(block_x as u32, block_y as u32, 1),
+            CubeCount::Static(threads_x, threads_y, 1),
+            CubeDim::new(block_x, block_y, 1),
             ArrayArg::from_raw_parts::<f32>(&input_handle, pixel_count, 4),
             ArrayArg::from_raw_parts::<f32>(&output_a_handle, pixel_count, 4),
         )
@@ -51,4 +51,17 @@ pub fn launch<R: Runtime>(
         image_input.get_size(), 
         output_colors
     )
-}
\ No newline at end of file
+}
+/// 픽셀 단위 2D 커널이 사용하는 큐브 한 변의 크기.
+pub const IMAGE_CUBE_DIM: u32 = 16;
+
+/// `IMAGE_CUBE_DIM` x `IMAGE_CUBE_DIM` 큐브로 이미지 전체를 덮는 런치 설정을 계산한다.
+/// 커널은 `ABSOLUTE_POS_X`/`ABSOLUTE_POS_Y`가 이미지 범위를 벗어나는지 직접 검사해야 한다.
+pub fn image_cube_config(width: u32, height: u32) -> (CubeCount, CubeDim) {
+    let cubes_x = width.div_ceil(IMAGE_CUBE_DIM);
+    let cubes_y = height.div_ceil(IMAGE_CUBE_DIM);
+    (
+        CubeCount::Static(Ord::max(cubes_x, 1), Ord::max(cubes_y, 1), 1),
+        CubeDim::new(IMAGE_CUBE_DIM, IMAGE_CUBE_DIM, 1),
+    )
+}
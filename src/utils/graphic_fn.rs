@@ -1,4 +1,4 @@
-use cubecl::{cube, prelude::Float, terminate};
+use cubecl::{cube, prelude::*, terminate};
 #[cube]
 pub fn compute_grayscale<F:Float>(r: F, g: F, b: F) -> F {
     F::new(0.299) * r 
@@ -30,4 +30,169 @@ pub fn denormalize<F:Float>(value: F, min: F, max: F) -> F {
 pub fn normal_vector_size<F:Float>(v:F,min:F,max:F) -> F {
     let mid = (max + min) / F::new(2.);
     v * mid + mid
-}
\ No newline at end of file
+}
+
+/// 색 공간 변환 함수들이 세 채널을 한 번에 돌려주기 위한 묶음 타입.
+/// (x, y, z)의 의미는 변환 대상 공간에 따라 다르다 (RGB, HSV, Lab ...).
+#[derive(CubeType)]
+pub struct ColorTriple<F: Float> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+}
+
+// ─── sRGB 전달 함수 (IEC 61966-2-1) ───
+#[cube]
+pub fn srgb_to_linear<F:Float>(v: F) -> F {
+    if v <= F::new(0.04045) {
+        v / F::new(12.92)
+    } else {
+        F::powf((v + F::new(0.055)) / F::new(1.055), F::new(2.4))
+    }
+}
+#[cube]
+pub fn linear_to_srgb<F:Float>(v: F) -> F {
+    if v <= F::new(0.0031308) {
+        v * F::new(12.92)
+    } else {
+        F::new(1.055) * F::powf(v, F::new(1.0 / 2.4)) - F::new(0.055)
+    }
+}
+
+// ─── HSV / HSL (감마 인코딩된 sRGB 값 기준, H는 0~1) ───
+#[cube]
+fn rgb_hue<F:Float>(r: F, g: F, b: F, max: F, delta: F) -> F {
+    let mut h = F::new(0.);
+    if delta > F::new(0.) {
+        if max == r {
+            h = (g - b) / delta;
+            if h < F::new(0.) {
+                h += F::new(6.);
+            }
+        } else if max == g {
+            h = (b - r) / delta + F::new(2.);
+        } else {
+            h = (r - g) / delta + F::new(4.);
+        }
+    }
+    h / F::new(6.)
+}
+#[cube]
+pub fn rgb_to_hsv<F:Float>(r: F, g: F, b: F) -> ColorTriple<F> {
+    let max = F::max(F::max(r, g), b);
+    let min = F::min(F::min(r, g), b);
+    let delta = max - min;
+    let s = if max > F::new(0.) { delta / max } else { F::new(0.) };
+    ColorTriple::<F> { x: rgb_hue::<F>(r, g, b, max, delta), y: s, z: max }
+}
+#[cube]
+pub fn hsv_to_rgb<F:Float>(h: F, s: F, v: F) -> ColorTriple<F> {
+    let h6 = (h - F::floor(h)) * F::new(6.);
+    let sector_f = F::floor(h6);
+    let f = h6 - sector_f;
+    let sector = u32::cast_from(sector_f) % 6;
+    let p = v * (F::new(1.) - s);
+    let q = v * (F::new(1.) - s * f);
+    let t = v * (F::new(1.) - s * (F::new(1.) - f));
+    let mut r = v;
+    let mut g = t;
+    let mut b = p;
+    if sector == 1 {
+        r = q; g = v; b = p;
+    } else if sector == 2 {
+        r = p; g = v; b = t;
+    } else if sector == 3 {
+        r = p; g = q; b = v;
+    } else if sector == 4 {
+        r = t; g = p; b = v;
+    } else if sector == 5 {
+        r = v; g = p; b = q;
+    }
+    ColorTriple::<F> { x: r, y: g, z: b }
+}
+#[cube]
+pub fn rgb_to_hsl<F:Float>(r: F, g: F, b: F) -> ColorTriple<F> {
+    let max = F::max(F::max(r, g), b);
+    let min = F::min(F::min(r, g), b);
+    let delta = max - min;
+    let l = (max + min) / F::new(2.);
+    let denom = F::new(1.) - F::abs(F::new(2.) * l - F::new(1.));
+    let s = if denom > F::new(0.) { delta / denom } else { F::new(0.) };
+    ColorTriple::<F> { x: rgb_hue::<F>(r, g, b, max, delta), y: s, z: l }
+}
+#[cube]
+pub fn hsl_to_rgb<F:Float>(h: F, s: F, l: F) -> ColorTriple<F> {
+    // HSL → HSV 로 바꾼 뒤 hsv_to_rgb 재사용
+    let v = l + s * F::min(l, F::new(1.) - l);
+    let s_v = if v > F::new(0.) { F::new(2.) * (F::new(1.) - l / v) } else { F::new(0.) };
+    hsv_to_rgb::<F>(h, s_v, v)
+}
+
+// ─── YCbCr (BT.709 full range, Cb/Cr 은 +0.5 오프셋) ───
+#[cube]
+pub fn rgb_to_ycbcr<F:Float>(r: F, g: F, b: F) -> ColorTriple<F> {
+    let y = compute_luminance::<F>(r, g, b);
+    let cb = (b - y) / F::new(1.8556) + F::new(0.5);
+    let cr = (r - y) / F::new(1.5748) + F::new(0.5);
+    ColorTriple::<F> { x: y, y: cb, z: cr }
+}
+#[cube]
+pub fn ycbcr_to_rgb<F:Float>(y: F, cb: F, cr: F) -> ColorTriple<F> {
+    let r = y + F::new(1.5748) * (cr - F::new(0.5));
+    let b = y + F::new(1.8556) * (cb - F::new(0.5));
+    let g = (y - F::new(0.2126) * r - F::new(0.0722) * b) / F::new(0.7152);
+    ColorTriple::<F> { x: r, y: g, z: b }
+}
+
+// ─── CIE L*a*b* (D65, linear RGB 입력, 0~1 정규화 저장) ───
+#[cube]
+fn lab_f<F:Float>(t: F) -> F {
+    if t > F::new(0.008856) {
+        F::powf(t, F::new(1.0 / 3.0))
+    } else {
+        F::new(7.787) * t + F::new(16.0 / 116.0)
+    }
+}
+#[cube]
+fn lab_f_inv<F:Float>(t: F) -> F {
+    let t3 = t * t * t;
+    if t3 > F::new(0.008856) {
+        t3
+    } else {
+        (t - F::new(16.0 / 116.0)) / F::new(7.787)
+    }
+}
+#[cube]
+pub fn linear_rgb_to_lab<F:Float>(r: F, g: F, b: F) -> ColorTriple<F> {
+    let x = (F::new(0.4124) * r + F::new(0.3576) * g + F::new(0.1805) * b) / F::new(0.95047);
+    let y = F::new(0.2126) * r + F::new(0.7152) * g + F::new(0.0722) * b;
+    let z = (F::new(0.0193) * r + F::new(0.1192) * g + F::new(0.9505) * b) / F::new(1.08883);
+    let fx = lab_f::<F>(x);
+    let fy = lab_f::<F>(y);
+    let fz = lab_f::<F>(z);
+    let l = F::new(116.) * fy - F::new(16.);
+    let a = F::new(500.) * (fx - fy);
+    let b_star = F::new(200.) * (fy - fz);
+    ColorTriple::<F> {
+        x: l / F::new(100.),
+        y: a / F::new(256.) + F::new(0.5),
+        z: b_star / F::new(256.) + F::new(0.5),
+    }
+}
+#[cube]
+pub fn lab_to_linear_rgb<F:Float>(l_n: F, a_n: F, b_n: F) -> ColorTriple<F> {
+    let l = l_n * F::new(100.);
+    let a = (a_n - F::new(0.5)) * F::new(256.);
+    let b_star = (b_n - F::new(0.5)) * F::new(256.);
+    let fy = (l + F::new(16.)) / F::new(116.);
+    let fx = fy + a / F::new(500.);
+    let fz = fy - b_star / F::new(200.);
+    let x = lab_f_inv::<F>(fx) * F::new(0.95047);
+    let y = lab_f_inv::<F>(fy);
+    let z = lab_f_inv::<F>(fz) * F::new(1.08883);
+    ColorTriple::<F> {
+        x: F::new(3.2406) * x - F::new(1.5372) * y - F::new(0.4986) * z,
+        y: F::new(-0.9689) * x + F::new(1.8758) * y + F::new(0.0415) * z,
+        z: F::new(0.0557) * x - F::new(0.2040) * y + F::new(1.0570) * z,
+    }
+}
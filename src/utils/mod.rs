@@ -1,5 +1,6 @@
 pub mod gpu_opt;
 pub mod graphic_fn;
+pub mod dither;
 
 #[cfg(feature = "use_image")]
 pub mod file_io;
\ No newline at end of file